@@ -245,6 +245,12 @@ impl AptosGasMeter {
         self.balance += amount.into().to_unit_with_params(&self.gas_params.txn);
     }
 
+    /// Takes back the part of a cross-space top-up which is not used.
+    pub fn cross_space_withdraw(&mut self, amount: impl Into<Gas>) {
+        let amount = amount.into().to_unit_with_params(&self.gas_params.txn);
+        self.balance = self.balance.checked_sub(amount).unwrap_or_else(|| 0.into());
+    }
+
     #[inline]
     fn charge(&mut self, amount: InternalGas) -> PartialVMResult<()> {
         match self.balance.checked_sub(amount) {
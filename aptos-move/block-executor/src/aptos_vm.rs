@@ -30,8 +30,8 @@ use aptos_types::{
         WriteSetPayload,
     }, vm_status::{AbortLocation, DiscardedVMStatus, StatusCode, VMStatus}, write_set::{WriteSet, WriteSetMut}
 };
-use cfx_state::{CallMoveVMTrait, StateTrait};
-use ethereum_types::{Address, U256};
+use cfx_state::{CallMoveVMError, CallMoveVMOutput, CallMoveVMTrait, StateTrait};
+use ethereum_types::{Address, H256, U256};
 use fail::fail_point;
 use move_binary_format::{
     access::ModuleAccess,
@@ -1471,7 +1471,7 @@ impl<'r, 'l, S: MoveResolverExt + StateView> CrossSpaceHandler<'r, 'l, S> {
         caller: Address,
         data: Vec<Vec<u8>>,
         ty_args: Vec<TypeTag>,
-    ) -> Result<Vec<u8>, String> {
+    ) -> Result<Vec<u8>, CallMoveVMError> {
         let module = ModuleId::new(
             address,
             Identifier::new(module_name).map_err(|e| format!("{}", e))?,
@@ -1490,7 +1490,7 @@ impl<'r, 'l, S: MoveResolverExt + StateView> CrossSpaceHandler<'r, 'l, S> {
                 ],
                 &mut self.gas_meter,
             )
-            .map_err(|e| format!("{}", e))?;
+            .map_err(move_abort_or_message)?;
 
         let (raw, ty) = res
             .return_values
@@ -1510,9 +1510,9 @@ impl<'r, 'l, S: MoveResolverExt + StateView> CrossSpaceHandler<'r, 'l, S> {
                 .into_iter()
                 .map(extract_byte)
                 .collect::<Option<Vec<u8>>>()
-                .ok_or("Incorrect return type".to_string())
+                .ok_or_else(|| "Incorrect return type".to_string().into())
         } else {
-            Err("Incorrect return type".to_string())
+            Err("Incorrect return type".to_string().into())
         }
     }
 
@@ -1536,6 +1536,19 @@ impl<'r, 'l, S: MoveResolverExt + StateView> CrossSpaceHandler<'r, 'l, S> {
     }
 }
 
+/// Keeps the code and the location of a Move abort, so the EVM caller can
+/// decode them. Other errors are passed as messages.
+fn move_abort_or_message(err: VMError) -> CallMoveVMError {
+    match (err.major_status(), err.sub_status(), err.location()) {
+        (StatusCode::ABORTED, Some(code), Location::Module(module)) => CallMoveVMError::Abort {
+            address: H256(module.address().into_bytes()),
+            module: module.name().to_string(),
+            code,
+        },
+        _ => CallMoveVMError::Other(format!("{}", err)),
+    }
+}
+
 impl<'r, 'l, S: MoveResolverExt + StateView> CallMoveVMTrait for CrossSpaceHandler<'r, 'l, S> {
     fn call_move_vm(
         &mut self,
@@ -1547,7 +1560,8 @@ impl<'r, 'l, S: MoveResolverExt + StateView> CallMoveVMTrait for CrossSpaceHandl
         types: Vec<TypeTag>,
         value: U256,
         gas: U256,
-    ) -> std::result::Result<Vec<u8>, String> {
+        cross_space_depth: usize,
+    ) -> CallMoveVMOutput {
         // IMPORTANT (0xuki): call move vm
         let gas = if gas >= U256::from(u64::MAX) {
             u64::MAX
@@ -1563,19 +1577,45 @@ impl<'r, 'l, S: MoveResolverExt + StateView> CallMoveVMTrait for CrossSpaceHandl
 
         let address = AccountAddress::new(address.try_into().unwrap());
 
+        // An EVM call made back by the Move function continues the depth of
+        // this call, so the ping-pong is bounded by `max_cross_space_depth`.
+        if let Some(CrossVMContext { executor }) = self
+            .session
+            .get_native_extensions()
+            .get_mut::<Option<CrossVMContext>>()
+        {
+            executor.set_cross_space_depth(cross_space_depth);
+        }
+
+        let balance: u64 = self.gas_meter.balance().into();
         self.gas_meter.cross_space_topup(gas);
 
-        if value > 0 {
-            self.cross_space_transfer(address, value)?;
-        }
+        let call = || -> Result<Vec<u8>, CallMoveVMError> {
+            if value > 0 {
+                self.cross_space_transfer(address, value)?;
+            }
 
-        let return_value = if !module_name.is_empty() {
-            self.cross_space_call(address, module_name, func_name, caller, data, types)?
-        } else {
-            vec![]
+            if !module_name.is_empty() {
+                self.cross_space_call(address, module_name, func_name, caller, data, types)
+            } else {
+                Ok(vec![])
+            }
         };
-
-        Ok(return_value)
+        let result = call();
+
+        // The gas used by the call is charged from the budget first, and the
+        // unused budget is taken back, so the EVM caller can refund it.
+        let balance_left: u64 = self.gas_meter.balance().into();
+        let gas_used = balance
+            .saturating_add(gas)
+            .saturating_sub(balance_left)
+            .min(gas);
+        self.gas_meter.cross_space_withdraw(gas - gas_used);
+
+        CallMoveVMOutput {
+            result,
+            gas_used: U256::from(gas_used),
+        }
     }
 }
 
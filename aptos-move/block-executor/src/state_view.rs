@@ -7,6 +7,7 @@ use aptos_state_view::TStateView;
 use aptos_types::{
     access_path::AccessPath, account_address::AccountAddress, account_config::{NewBlockEvent, CORE_CODE_ADDRESS}, contract_event::{ContractEvent, EventWithVersion}, epoch_change::EpochChangeProof, epoch_state::EpochState, event::EventKey, ledger_info::LedgerInfoWithSignatures, move_resource::MoveStorage, on_chain_config::{access_path_for_config, ConfigID}, proof::{AccumulatorConsistencyProof, SparseMerkleProof, SparseMerkleProofExt, SparseMerkleRangeProof, TransactionAccumulatorRangeProof, TransactionAccumulatorSummary}, state_proof::StateProof, state_store::{state_key::StateKey, state_key_prefix::StateKeyPrefix, state_storage_usage::StateStorageUsage, state_value::{StateValue, StateValueChunkWithProof}, table::{TableHandle, TableInfo}}, transaction::{AccountTransactionsWithProof, Transaction, TransactionInfo, TransactionListWithProof, TransactionOutputListWithProof, TransactionToCommit, TransactionWithProof, Version}, write_set::WriteSet
 };
+use cfx_primitives::CrossSpaceReceipt;
 use std::sync::Arc;
 
 use crate::{executed_trees::ExecutedTrees, state_delta::StateDelta};
@@ -209,6 +210,22 @@ pub trait DbReader: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the hashes of the blocks whose `BlockMetadata` event version is in `[start, end)`,
+    /// i.e., `start` is inclusive and `end` is exclusive, sorted by version.
+    fn get_block_hashes_in_range(
+        &self,
+        start: Version,
+        end: Version,
+    ) -> Result<Vec<(Version, HashValue)>> {
+        unimplemented!()
+    }
+
+    /// Returns the receipts of the cross-space calls made by the transaction at `version`, in the
+    /// order of the calls.
+    fn get_cross_space_receipts(&self, version: Version) -> Result<Vec<CrossSpaceReceipt>> {
+        unimplemented!()
+    }
+
     /// Returns the start_version, end_version and NewBlockEvent of the block containing the input
     /// transaction version.
     fn get_block_info_by_height(&self, height: u64) -> Result<(Version, Version, NewBlockEvent)> {
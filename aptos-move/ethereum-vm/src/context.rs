@@ -26,6 +26,7 @@ impl EvmContext {
             last_hash: H256::zero(),
             accumulated_gas_used: U256::zero(),
             epoch_height: block_height,
+            base_fee_per_gas: None,
         };
         let spec = machine.params().spec(block_height);
         EvmContext { env, spec }
@@ -8,16 +8,27 @@ use cfx_evm::{
     vm::Error as VmError,
     ExecutionOutcome,
 };
-use move_core_types::vm_status::{AbortLocation::Script, StatusCode::*, VMStatus};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
+    vm_status::{
+        AbortLocation::{Module, Script},
+        StatusCode::*,
+        VMStatus,
+    },
+};
 
 pub mod abort_code {
     pub const BUILTIN_CODE: u64 = 1029;
     pub const INTERNAL_CONTRACT_CODE: u64 = 1030;
     pub const REVERT_CODE: u64 = 1031;
+    pub const STEP_LIMIT_CODE: u64 = 1032;
 
     pub const BUILTIN_DESC: &'static str = "Built-in function fail";
     pub const INTERNAL_CONTRACT_DESC: &'static str = "Internal contract fail";
     pub const REVERT_DESC: &'static str = "EVM reverted";
+    pub const STEP_LIMIT_DESC: &'static str = "EVM step limit reached";
 }
 
 use abort_code::*;
@@ -45,7 +56,8 @@ pub fn convert_exeuction_outcome(outcome: &ExecutionOutcome) -> (VMStatus, Trans
 pub fn extract_evm_executed(outcome: &ExecutionOutcome) -> Option<&Executed> {
     match outcome {
         ExecutionOutcome::NotExecutedDrop(_)
-        | ExecutionOutcome::NotExecutedToReconsiderPacking(_) => None,
+        | ExecutionOutcome::NotExecutedToReconsiderPacking(_)
+        | ExecutionOutcome::AlreadyExecuted(_) => None,
         ExecutionOutcome::ExecutionErrorBumpNonce(_, executed)
         | ExecutionOutcome::Finished(executed) => Some(executed),
     }
@@ -91,6 +103,7 @@ impl Into<ConvertedOutcome> for &ExecutionOutcome {
             ExecutionOutcome::NotExecutedToReconsiderPacking(err) => err.into(),
             ExecutionOutcome::ExecutionErrorBumpNonce(err, _) => err.into(),
             ExecutionOutcome::Finished(_) => VMStatus::Executed.into(),
+            ExecutionOutcome::AlreadyExecuted(_) => SEQUENCE_NUMBER_TOO_OLD.into(),
         }
     }
 }
@@ -102,6 +115,9 @@ impl Into<ConvertedOutcome> for &TxDropError {
             TxDropError::NotEnoughBaseGas { .. } => {
                 MAX_GAS_UNITS_BELOW_MIN_TRANSACTION_GAS_UNITS.into()
             },
+            TxDropError::FeeCapTooLow { .. } => GAS_UNIT_PRICE_BELOW_MIN_BOUND.into(),
+            TxDropError::InitCodeTooLarge { .. } => EXCEEDED_MAX_TRANSACTION_SIZE.into(),
+            TxDropError::CrossSpaceCalldataTooLarge { .. } => EXCEEDED_MAX_TRANSACTION_SIZE.into(),
         }
     }
 }
@@ -114,6 +130,9 @@ impl Into<ConvertedOutcome> for &ToRepackError {
                 debug!("SENDING ACCOUNT EVM TRANSFORMATION");
                 SENDING_ACCOUNT_DOES_NOT_EXIST.into()
             }
+            ToRepackError::BlockGasLimitExceeded { .. } => {
+                MAX_GAS_UNITS_EXCEEDS_MAX_GAS_UNITS_BOUND.into()
+            },
         }
     }
 }
@@ -122,6 +141,7 @@ impl Into<ConvertedOutcome> for &ExecutionError {
     fn into(self) -> ConvertedOutcome {
         match self {
             ExecutionError::NotEnoughCash { .. } => ARITHMETIC_ERROR.into(),
+            ExecutionError::OutOfGas { .. } => OUT_OF_GAS.into(),
             ExecutionError::VmError(err) => err.into(),
         }
     }
@@ -139,6 +159,20 @@ impl Into<ConvertedOutcome> for &VmError {
             | VmError::OutOfStack { .. }
             | VmError::SubStackUnderflow { .. }
             | VmError::OutOfSubStack { .. } => EXECUTION_STACK_OVERFLOW.into(),
+            VmError::CrossSpaceReentrancyLimit | VmError::OutOfDepth => {
+                CALL_STACK_OVERFLOW.into()
+            },
+            VmError::InitCodeTooLarge { .. } | VmError::CrossSpaceCalldataTooLarge { .. } => {
+                EXCEEDED_MAX_TRANSACTION_SIZE.into()
+            },
+            VmError::InvalidFunctionName(_)
+            | VmError::MissingCrossSpaceCalldata
+            | VmError::CrossSpaceTargetNotContract(_) => FUNCTION_RESOLUTION_FAILURE.into(),
+
+            VmError::NotEnoughSponsorBalance { .. } => {
+                INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE.into()
+            },
+            VmError::UnauthorizedSponsor(_) => INVALID_AUTH_KEY.into(),
 
             VmError::NotEnoughBalanceForStorage { .. }
             | VmError::ExceedStorageLimit
@@ -160,6 +194,13 @@ impl Into<ConvertedOutcome> for &VmError {
                     description: msg.into(),
                 }),
             },
+            VmError::StepLimitReached => ConvertedOutcome {
+                status: VMStatus::MoveAbort(Script, STEP_LIMIT_CODE),
+                reason: Some(AbortInfo {
+                    reason_name: STEP_LIMIT_DESC.into(),
+                    description: String::new(),
+                }),
+            },
             VmError::Reverted => ConvertedOutcome {
                 status: VMStatus::MoveAbort(Script, REVERT_CODE),
                 reason: Some(AbortInfo {
@@ -167,9 +208,30 @@ impl Into<ConvertedOutcome> for &VmError {
                     description: String::new(),
                 }),
             },
+            VmError::MoveAbort {
+                address,
+                module,
+                code,
+            } => {
+                let location = match Identifier::new(module.as_str()) {
+                    Ok(name) => Module(ModuleId::new(AccountAddress::new(address.0), name)),
+                    Err(_) => Script,
+                };
+                VMStatus::MoveAbort(location, *code).into()
+            },
+            VmError::RevertedWithReason(reason) => ConvertedOutcome {
+                status: VMStatus::MoveAbort(Script, REVERT_CODE),
+                reason: Some(AbortInfo {
+                    reason_name: REVERT_DESC.into(),
+                    description: reason.into(),
+                }),
+            },
 
-            VmError::MutableCallInStaticContext => UNKNOWN_RUNTIME_STATUS.into(),
-            VmError::StateDbError(_) => UNKNOWN_RUNTIME_STATUS.into(),
+            VmError::MutableCallInStaticContext
+            | VmError::UnsupportedCrossSpaceCallType(_)
+            | VmError::UnsupportedCrossSpaceTarget(_)
+            | VmError::CrossSpaceDelegateCallWithValue => UNKNOWN_RUNTIME_STATUS.into(),
+            VmError::StateDbError(_) | VmError::Message(_) => UNKNOWN_RUNTIME_STATUS.into(),
 
             VmError::OutOfBounds => VECTOR_OPERATION_ERROR.into(),
         }
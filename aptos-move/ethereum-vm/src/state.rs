@@ -28,6 +28,14 @@ impl<'a, S: StateView> StorageTrait for &mut ViewWrapper<'a, S> {
             .map(|value| value.into_boxed_slice()))
     }
 
+    // The pending writes and deletions in the cache take precedence.
+    fn contains_key(&self, key: StorageKey) -> Result<bool> {
+        if let Some(cached_value) = self.cache.get(&key) {
+            return Ok(cached_value.is_some());
+        }
+        Ok(self.inner.get_state_value(&key.into()).unwrap().is_some())
+    }
+
     fn set(&mut self, access_key: StorageKey, value: Box<[u8]>) -> Result<()> {
         self.cache.insert(access_key, Some(value));
         Ok(())
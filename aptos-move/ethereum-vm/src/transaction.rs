@@ -3,7 +3,7 @@ use aptos_types::transaction::{
 };
 use cfx_evm::TransactionInfo;
 use cfx_primitives::Action;
-use cfx_types::{AddressSpaceUtil, AddressWithSpace, U256};
+use cfx_types::{AddressSpaceUtil, AddressWithSpace, H256, U256};
 use std::borrow::Cow;
 use Cow::{Borrowed, Owned};
 
@@ -58,4 +58,8 @@ impl<'a> TransactionInfo for EvmTransaction<'a> {
     fn value(&self) -> Cow<U256> {
         Borrowed(&self.payload.value)
     }
+
+    fn hash(&self) -> Option<H256> {
+        Some(self.transaction.committed_hash_ref().into())
+    }
 }
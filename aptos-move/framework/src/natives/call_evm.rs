@@ -1,7 +1,7 @@
 use super::code::get_move_string;
 use aptos_types::vm_status::StatusCode;
 use better_any::{Tid, TidAble};
-use cfx_evm::{execution::revert_reason_decode, CrossVMParams, FinalizationResult, TXExecutor};
+use cfx_evm::{CrossVMAction, CrossVMParams, CrossVMParamsBuilder, FinalizationResult, TXExecutor};
 use ethereum_types::{Address, U256};
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_core_types::language_storage::{StructTag, TypeTag};
@@ -13,8 +13,8 @@ use move_vm_types::{
     values::{Struct, Value},
 };
 use smallvec::smallvec;
-use solidity_abi::ABIDecodable;
-use std::{collections::VecDeque, ops::Deref, sync::Arc};
+use solidity_abi::ABIValue;
+use std::{collections::VecDeque, sync::Arc};
 
 pub mod abort_codes {
     pub const INCORRECT_TYPE_TAG: u64 = 0x1;
@@ -79,16 +79,19 @@ fn make_cross_vm_params(
         format!("0x{}::{module}::{name}", hex::encode(&address))
     };
 
-    let params = CrossVMParams {
-        receiver,
-        function_name,
-        gas,
-        gas_price,
-        value,
-        evm_params,
-        caller_info,
+    let builder = CrossVMParamsBuilder::new(CrossVMAction::Call, receiver, gas)
+        .gas_price(gas_price)
+        .value(value)
+        .evm_params(evm_params)
+        .caller_info(caller_info)
+        .return_types(vec!["bytes".to_string()]);
+    // An empty function name is a plain transfer without calldata.
+    let builder = if function_name.is_empty() {
+        builder.raw_calldata(vec![])
+    } else {
+        builder.function_name(function_name)
     };
-    Ok(params)
+    builder.build().map_err(|_| err)
 }
 // cross_vm:: call_evm
 fn native_call_evm(
@@ -132,21 +135,25 @@ fn native_call_evm(
             ..
         }) => {
             println!("{:?}", return_data);
-            println!(
-                "EVM Reverted {:?}",
-                revert_reason_decode(return_data.deref())
-            );
             Ok(NativeResult::err(0.into(), abort_codes::EVM_CALL_REVERT))
         },
         Ok(res) => {
             let decoded = if res.return_data.is_empty() {
                 vec![]
             } else {
-                match Vec::<u8>::abi_decode(res.return_data.as_ref()) {
-                    Err(_) => {
+                match output.decoded_output {
+                    Some(Ok(mut values)) => match values.pop() {
+                        Some(ABIValue::Bytes(v)) => v,
+                        _ => {
+                            return Ok(NativeResult::err(0.into(), abort_codes::MALFORMED_OUTPUT));
+                        },
+                    },
+                    Some(Err(_)) => {
+                        return Ok(NativeResult::err(0.into(), abort_codes::MALFORMED_OUTPUT));
+                    },
+                    None => {
                         return Ok(NativeResult::err(0.into(), abort_codes::MALFORMED_OUTPUT));
                     },
-                    Ok(v) => v,
                 }
             };
             match String::from_utf8(decoded.clone()) {
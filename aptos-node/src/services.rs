@@ -113,7 +113,7 @@ pub fn start_mempool_runtime_and_get_consensus_sender(
 
     // Bootstrap and start mempool
     let instant = Instant::now();
-    let mempool = aptos_mempool::bootstrap(
+    let (mempool, _mempool_handle) = aptos_mempool::bootstrap(
         node_config,
         Arc::clone(&db_rw.reader),
         deconstructed_network_handles,
@@ -6,7 +6,7 @@ pub mod state_trait;
 pub mod tracer;
 
 use cfx_types::AddressWithSpace;
-pub use state_trait::{CallMoveVMTrait, StateTrait};
+pub use state_trait::{CallMoveVMError, CallMoveVMOutput, CallMoveVMTrait, StateTrait};
 use std::collections::HashSet;
 
 /// Mode of dealing with null accounts.
@@ -11,6 +11,9 @@ pub trait StateTrait: CheckpointTrait + AsStateOpsTrait {
 }
 
 pub trait CallMoveVMTrait {
+    /// Calls the Move VM with the gas budget `gas`. `cross_space_depth` is
+    /// the number of EVM-Move transitions on the call chain, including this
+    /// one. An EVM call made back from the Move side continues from it.
     fn call_move_vm(
         &mut self,
         caller: Address,
@@ -21,7 +24,50 @@ pub trait CallMoveVMTrait {
         types: Vec<TypeTag>,
         value: U256,
         gas: U256,
-    ) -> Result<Vec<u8>, String>;
+        cross_space_depth: usize,
+    ) -> CallMoveVMOutput;
+}
+
+/// The outcome of a call into the Move VM.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallMoveVMOutput {
+    pub result: Result<Vec<u8>, CallMoveVMError>,
+    /// The gas used by the call, whether it succeeds or not. It is at most the
+    /// gas budget of the call.
+    pub gas_used: U256,
+}
+
+/// The error of a call into the Move VM.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallMoveVMError {
+    /// The Move function aborted with `code` in module `module` published at
+    /// `address`.
+    Abort {
+        address: H256,
+        module: String,
+        code: u64,
+    },
+    /// Any other failure of the call.
+    Other(String),
+}
+
+impl From<String> for CallMoveVMError {
+    fn from(msg: String) -> Self {
+        CallMoveVMError::Other(msg)
+    }
+}
+
+impl fmt::Display for CallMoveVMError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CallMoveVMError::Abort {
+                address,
+                module,
+                code,
+            } => write!(f, "Move abort {} in {:?}::{}", code, address, module),
+            CallMoveVMError::Other(msg) => write!(f, "{}", msg),
+        }
+    }
 }
 
 impl CallMoveVMTrait for &mut dyn CallMoveVMTrait {
@@ -35,8 +81,19 @@ impl CallMoveVMTrait for &mut dyn CallMoveVMTrait {
         types: Vec<TypeTag>,
         value: U256,
         gas: U256,
-    ) -> Result<Vec<u8>, String> {
-        (*self).call_move_vm(caller, address, module, function, data, types, value, gas)
+        cross_space_depth: usize,
+    ) -> CallMoveVMOutput {
+        (*self).call_move_vm(
+            caller,
+            address,
+            module,
+            function,
+            data,
+            types,
+            value,
+            gas,
+            cross_space_depth,
+        )
     }
 }
 
@@ -122,6 +179,14 @@ pub trait StateOpsTrait {
     fn set_system_storage(&mut self, key: Vec<u8>, value: U256) -> DbResult<()>;
 
     fn get_system_storage(&self, key: &[u8]) -> DbResult<U256>;
+
+    /// Read the transient storage (EIP-1153). Unset slots are zero.
+    fn transient_storage_at(&self, address: &AddressWithSpace, key: &[u8]) -> U256;
+
+    /// Write the transient storage (EIP-1153). The write is undone by
+    /// reverting to an earlier checkpoint, and all the slots are dropped when
+    /// the outermost checkpoint is closed, i.e., at the end of the transaction.
+    fn set_transient_storage(&mut self, address: &AddressWithSpace, key: Vec<u8>, value: U256);
 }
 
 pub trait AsStateOpsTrait: StateOpsTrait {
@@ -152,4 +217,4 @@ use cfx_statedb::Result as DbResult;
 use cfx_types::{Address, AddressWithSpace, H256, U256};
 use move_core_types::language_storage::TypeTag;
 use primitives::{EpochId, StorageLayout};
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
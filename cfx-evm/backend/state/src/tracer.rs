@@ -11,6 +11,16 @@ pub trait StateTracer: Send {
     /// Prepares internal transfer action
     fn trace_internal_transfer(&mut self, from: AddressPocket, to: AddressPocket, value: U256);
 
+    /// Called when a cross-space call from Move enters the EVM space.
+    fn trace_cross_space_enter(&mut self, _receiver: Address, _selector: [u8; 4], _value: U256) {}
+
+    /// Called when a cross-space call from Move exits the EVM space.
+    fn trace_cross_space_exit(&mut self, _success: bool, _gas_used: U256) {}
+
+    /// Called when the storage of a contract is read, by `SLOAD` or an
+    /// internal contract.
+    fn trace_storage_read(&mut self, _address: &AddressWithSpace, _key: &[u8]) {}
+
     /// Make a checkpoint for validity mark
     fn checkpoint(&mut self);
 
@@ -39,6 +49,18 @@ where
         (*self).trace_internal_transfer(from, to, value);
     }
 
+    fn trace_cross_space_enter(&mut self, receiver: Address, selector: [u8; 4], value: U256) {
+        (*self).trace_cross_space_enter(receiver, selector, value);
+    }
+
+    fn trace_cross_space_exit(&mut self, success: bool, gas_used: U256) {
+        (*self).trace_cross_space_exit(success, gas_used);
+    }
+
+    fn trace_storage_read(&mut self, address: &AddressWithSpace, key: &[u8]) {
+        (*self).trace_storage_read(address, key);
+    }
+
     fn checkpoint(&mut self) {
         (*self).checkpoint();
     }
@@ -62,6 +84,21 @@ where
         self.1.trace_internal_transfer(from, to, value);
     }
 
+    fn trace_cross_space_enter(&mut self, receiver: Address, selector: [u8; 4], value: U256) {
+        self.0.trace_cross_space_enter(receiver, selector, value);
+        self.1.trace_cross_space_enter(receiver, selector, value);
+    }
+
+    fn trace_cross_space_exit(&mut self, success: bool, gas_used: U256) {
+        self.0.trace_cross_space_exit(success, gas_used);
+        self.1.trace_cross_space_exit(success, gas_used);
+    }
+
+    fn trace_storage_read(&mut self, address: &AddressWithSpace, key: &[u8]) {
+        self.0.trace_storage_read(address, key);
+        self.1.trace_storage_read(address, key);
+    }
+
     fn checkpoint(&mut self) {
         self.0.checkpoint();
         self.1.checkpoint();
@@ -83,6 +120,9 @@ pub enum AddressPocket {
     Balance(AddressWithSpace),
     MintBurn,
     GasPayment,
+    /// The value held by the Move space, which is moved in and out of the EVM
+    /// space by cross-space calls.
+    MoveSpace,
 }
 
 impl AddressPocket {
@@ -90,7 +130,7 @@ impl AddressPocket {
         use AddressPocket::*;
         match self {
             Balance(AddressWithSpace { address: addr, .. }) => Some(addr),
-            MintBurn | GasPayment => None,
+            MintBurn | GasPayment | MoveSpace => None,
         }
     }
 
@@ -104,6 +144,7 @@ impl AddressPocket {
             Balance(_) => "balance",
             MintBurn => "mint_or_burn",
             GasPayment => "gas_payment",
+            MoveSpace => "move_space",
         }
     }
 
@@ -111,7 +152,7 @@ impl AddressPocket {
         use AddressPocket::*;
         match self {
             Balance(AddressWithSpace { space, .. }) => space.clone().into(),
-            MintBurn | GasPayment => "none",
+            MintBurn | GasPayment | MoveSpace => "none",
         }
     }
 
@@ -120,6 +161,7 @@ impl AddressPocket {
         match self {
             MintBurn => 0,
             GasPayment => 1,
+            MoveSpace => 3,
             Balance(AddressWithSpace {
                 space: Space::Ethereum,
                 ..
@@ -154,6 +196,7 @@ impl Decodable for AddressPocket {
             2 => rlp
                 .val_at(1)
                 .map(|addr: Address| Balance(addr.with_evm_space())),
+            3 => Ok(MoveSpace),
             _ => Err(DecoderError::Custom("Invalid internal transfer address.")),
         }
     }
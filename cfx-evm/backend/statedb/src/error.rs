@@ -3,8 +3,8 @@
 // See http://www.gnu.org/licenses/
 
 use cfx_storage::Error as StorageError;
-use cfx_types::Address;
-use primitives::account::AccountError;
+use cfx_types::{Address, H256};
+use primitives::{account::AccountError, OwnedStateKey};
 use rlp::DecoderError;
 
 error_chain! {
@@ -22,5 +22,55 @@ error_chain! {
             description("incomplete database")
             display("incomplete database: address={:?}", address)
         }
+
+        UnknownCheckpoint(id: usize) {
+            description("unknown checkpoint")
+            display("unknown checkpoint: {}", id)
+        }
+
+        CheckpointOpen(count: usize) {
+            description("checkpoints are still open")
+            display("checkpoints are still open: count={}", count)
+        }
+
+        StagedCommitPending(epoch_id: H256) {
+            description("a staged commit is pending")
+            display("a staged commit is pending: epoch_id={:?}", epoch_id)
+        }
+
+        UnknownStagedCommit(epoch_id: H256) {
+            description("unknown staged commit")
+            display("unknown staged commit: epoch_id={:?}", epoch_id)
+        }
+
+        NotRevertibleEpoch(epoch_id: H256) {
+            description("the epoch is not the last revertible epoch")
+            display("the epoch is not the last revertible epoch: epoch_id={:?}", epoch_id)
+        }
+
+        EpochAlreadyCommitted(epoch_id: H256) {
+            description("the epoch is already committed")
+            display("the epoch is already committed: epoch_id={:?}", epoch_id)
+        }
+
+        UncommittedChanges {
+            description("the state has uncommitted changes")
+            display("the state has uncommitted changes")
+        }
+
+        EpochJournalingDisabled {
+            description("epoch journaling is disabled")
+            display("epoch journaling is disabled")
+        }
+
+        MalformedStateKey(key: Vec<u8>) {
+            description("malformed state key")
+            display("malformed state key: {:?}", key)
+        }
+
+        Decode(key: OwnedStateKey, expected: &'static str) {
+            description("malformed stored value")
+            display("malformed stored value: key={:?}, expected={}", key, expected)
+        }
     }
 }
@@ -1,15 +1,108 @@
 // Put StateDb in mod to make sure that methods from statedb_ext don't access
 // its fields directly.
 
-use super::*;
+use super::{read_cache::ReadCache, *};
 use cfx_internal_common::debug::ComputeEpochDebugRecord;
-use cfx_storage::{StorageKeyWrapper, StorageTrait};
-use primitives::{OwnedStateKey, StateKey};
-use std::marker::PhantomData;
+use cfx_storage::{StateProof, StorageKeyWrapper, StorageTrait};
+use parking_lot::Mutex;
+use primitives::{EpochId, OwnedStateKey, StateKey};
+use std::{
+    collections::{HashMap, VecDeque},
+    marker::PhantomData,
+};
+
+/// The index of a checkpoint in the checkpoint stack of `StateDb`.
+pub type CheckpointId = usize;
+
+/// The changes of an epoch which are staged by `StateDbTrait::commit_staged`
+/// and not written to the backing store yet.
+#[derive(Debug)]
+pub struct StagedCommit {
+    epoch_id: EpochId,
+    /// The serialized values of the changed keys. `None` means the key is
+    /// deleted.
+    delta: Vec<(OwnedStateKey, Option<Box<[u8]>>)>,
+}
+
+impl StagedCommit {
+    pub fn epoch_id(&self) -> &EpochId {
+        &self.epoch_id
+    }
+
+    pub fn delta(&self) -> &[(OwnedStateKey, Option<Box<[u8]>>)] {
+        &self.delta
+    }
+}
+
+/// The changes written to the backing store by a commit. With epoch
+/// journaling, the keys whose values are unchanged since the last commit are
+/// not counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommitSummary {
+    pub keys_written: usize,
+    pub keys_deleted: usize,
+    /// The total size of the written values.
+    pub bytes_written: usize,
+}
+
+/// The number of the last committed epochs which can be reverted by
+/// `StateDbTrait::revert_epoch`.
+pub const MAX_REVERTIBLE_EPOCHS: usize = 128;
+
+/// A key changed since the last commit.
+struct UncommittedValue {
+    /// The value before the first change since the last commit, only recorded
+    /// with epoch journaling. `Some(None)` means the key did not exist.
+    old_value: Option<Option<Box<[u8]>>>,
+    /// The current value. `None` means the key is deleted.
+    value: Option<Box<[u8]>>,
+}
+
+impl UncommittedValue {
+    fn is_unchanged(&self) -> bool {
+        self.old_value.as_ref() == Some(&self.value)
+    }
+}
+
+/// The values of the keys changed by a committed epoch, before the epoch.
+struct EpochJournal {
+    epoch_id: EpochId,
+    old_values: Vec<(OwnedStateKey, Option<Box<[u8]>>)>,
+}
+
+/// Whether a commit of `StateDb` is flushed to the backing store at once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FlushMode {
+    #[default]
+    Immediate,
+    /// The commits are flushed by `StateDb::flush` or the next commit in the
+    /// `Immediate` mode. The deferred commits are still visible to the reads
+    /// of the same `StateDb`.
+    Deferred,
+}
 
 // Use generic type for better test-ability.
 pub struct StateDb<'a> {
     storage: Box<dyn StorageTrait<StorageKey = OwnedStateKey> + 'a>,
+    /// Each checkpoint records the values of the keys before their first
+    /// modification after the checkpoint is created. `None` means the key did
+    /// not exist.
+    checkpoints: Vec<HashMap<OwnedStateKey, Option<Box<[u8]>>>>,
+    /// The keys modified after the last commit.
+    uncommitted: HashMap<OwnedStateKey, UncommittedValue>,
+    /// The epoch of the pending staged commit.
+    staged_epoch: Option<EpochId>,
+    /// The LRU cache of the values read by `get_raw`. `None` means the cache
+    /// is disabled.
+    read_cache: Option<Mutex<ReadCache>>,
+    flush_mode: FlushMode,
+    /// The epoch of the last commit which is not flushed yet.
+    deferred_epoch: Option<EpochId>,
+    /// Whether the values before each epoch are journaled, so the epoch can
+    /// be staged, reverted or summarized without the unchanged keys.
+    epoch_journaling: bool,
+    /// The journals of the last committed epochs, the last epoch at the back.
+    epoch_journals: VecDeque<EpochJournal>,
 }
 
 // Question: why do we need this wrapper?
@@ -23,13 +116,247 @@ impl<'a> StateDb<'a> {
             inner: storage,
             _key: PhantomData::<OwnedStateKey>,
         });
-        StateDb { storage }
+        StateDb {
+            storage,
+            checkpoints: Vec::new(),
+            uncommitted: HashMap::new(),
+            staged_epoch: None,
+            read_cache: None,
+            flush_mode: FlushMode::Immediate,
+            deferred_epoch: None,
+            epoch_journaling: false,
+            epoch_journals: VecDeque::new(),
+        }
+    }
+
+    /// Create a `StateDb` which caches at most `capacity` keys read by
+    /// `get_raw`. The keys changed since the last commit are always read from
+    /// the backing store. A zero `capacity` disables the cache like `new`.
+    pub fn with_read_cache<T, U>(storage: T, capacity: usize) -> Self
+    where
+        T: StorageTrait<StorageKey = U> + 'a,
+        U: From<OwnedStateKey>,
+    {
+        let mut state_db = Self::new(storage);
+        if capacity > 0 {
+            state_db.read_cache = Some(Mutex::new(ReadCache::new(capacity)));
+        }
+        state_db
+    }
+
+    /// The hit and miss counters of the read cache, or `None` if the cache
+    /// is disabled.
+    pub fn read_cache_stats(&self) -> Option<ReadCacheStats> {
+        self.read_cache.as_ref().map(|cache| cache.lock().stats())
+    }
+
+    /// Switching to `FlushMode::Immediate` does not flush the deferred
+    /// commits, call `flush` for it.
+    pub fn set_flush_mode(&mut self, mode: FlushMode) {
+        self.flush_mode = mode;
+    }
+
+    pub fn flush_mode(&self) -> FlushMode {
+        self.flush_mode
+    }
+
+    /// Enable or disable epoch journaling, which is disabled by default. With
+    /// it, the first modification of a key after a commit reads the old value
+    /// of the key, which `commit_staged`, `revert_epoch` and the unchanged
+    /// keys of `CommitSummary` depend on. It can only be switched without
+    /// uncommitted changes.
+    pub fn set_epoch_journaling(&mut self, enabled: bool) -> Result<()> {
+        if !self.uncommitted.is_empty() {
+            bail!(ErrorKind::UncommittedChanges);
+        }
+        self.epoch_journaling = enabled;
+        Ok(())
+    }
+
+    pub fn epoch_journaling(&self) -> bool {
+        self.epoch_journaling
+    }
+
+    /// Flush the deferred commits to the backing store. It does nothing if
+    /// there is no deferred commit.
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some(epoch_id) = self.deferred_epoch {
+            self.storage.commit(epoch_id)?;
+            self.deferred_epoch = None;
+        }
+        Ok(())
+    }
+
+    /// Commit the backing store, or defer it according to the flush mode.
+    /// Committing the backing store also flushes the earlier commits.
+    fn commit_storage(&mut self, epoch_id: EpochId) -> Result<()> {
+        match self.flush_mode {
+            FlushMode::Immediate => {
+                self.storage.commit(epoch_id)?;
+                self.deferred_epoch = None;
+            },
+            FlushMode::Deferred => self.deferred_epoch = Some(epoch_id),
+        }
+        Ok(())
+    }
+
+    /// Journal a committed epoch. Without epoch journaling, the journals of the
+    /// earlier epochs are dropped, since they can not be reverted past it.
+    fn push_epoch_journal(
+        &mut self,
+        epoch_id: EpochId,
+        old_values: Vec<(OwnedStateKey, Option<Box<[u8]>>)>,
+    ) {
+        if !self.epoch_journaling {
+            self.epoch_journals.clear();
+            return;
+        }
+        self.epoch_journals.push_back(EpochJournal {
+            epoch_id,
+            old_values,
+        });
+        if self.epoch_journals.len() > MAX_REVERTIBLE_EPOCHS {
+            self.epoch_journals.pop_front();
+        }
+    }
+
+    fn invalidate_cached(&mut self, key: &OwnedStateKey) {
+        if let Some(cache) = &mut self.read_cache {
+            cache.get_mut().invalidate(key);
+        }
+    }
+
+    /// Create a checkpoint on top of the checkpoint stack.
+    pub fn checkpoint(&mut self) -> CheckpointId {
+        self.checkpoints.push(HashMap::new());
+        self.checkpoints.len() - 1
+    }
+
+    /// Revert all the modifications after the checkpoint `id` was created, and
+    /// remove this checkpoint and all the checkpoints created after it.
+    pub fn revert_to(&mut self, id: CheckpointId) -> Result<()> {
+        if id >= self.checkpoints.len() {
+            bail!(ErrorKind::UnknownCheckpoint(id));
+        }
+        while self.checkpoints.len() > id {
+            let checkpoint = self.checkpoints.pop().unwrap();
+            for (key, value) in checkpoint {
+                self.invalidate_cached(&key);
+                // A key in a checkpoint is always uncommitted.
+                if let Some(uncommitted) = self.uncommitted.get_mut(&key) {
+                    uncommitted.value = value.clone();
+                }
+                match value {
+                    Some(value) => self.storage.set(key, value)?,
+                    None => self.storage.delete(key)?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Keep the modifications after the checkpoint `id` was created, and
+    /// remove this checkpoint and all the checkpoints created after it.
+    pub fn discard_checkpoint(&mut self, id: CheckpointId) {
+        while self.checkpoints.len() > id {
+            let checkpoint = self.checkpoints.pop().unwrap();
+            if let Some(prev) = self.checkpoints.last_mut() {
+                for (key, value) in checkpoint {
+                    prev.entry(key).or_insert(value);
+                }
+            }
+        }
+    }
+
+    /// The state can not be committed or reverted while a checkpoint is open,
+    /// since reverting to the checkpoint later would not undo the commit.
+    fn check_no_checkpoint(&self) -> Result<()> {
+        if !self.checkpoints.is_empty() {
+            bail!(ErrorKind::CheckpointOpen(self.checkpoints.len()));
+        }
+        Ok(())
+    }
+
+    /// Record the modification of `key` to `value`. The current value of the
+    /// key is only read from the backing store if the key is not modified
+    /// since the last commit, and a checkpoint or the epoch journal needs it.
+    fn record_write(&mut self, key: &OwnedStateKey, value: Option<Box<[u8]>>) -> Result<()> {
+        let in_checkpoint = match self.checkpoints.last() {
+            None => true,
+            Some(checkpoint) => checkpoint.contains_key(key),
+        };
+        if let Some(uncommitted) = self.uncommitted.get_mut(key) {
+            let old_value = std::mem::replace(&mut uncommitted.value, value);
+            if !in_checkpoint {
+                self.checkpoints
+                    .last_mut()
+                    .unwrap()
+                    .insert(key.clone(), old_value);
+            }
+            return Ok(());
+        }
+        let journaling = self.epoch_journaling;
+        let old_value = if !in_checkpoint || journaling {
+            Some(self.storage.get(key.clone())?)
+        } else {
+            None
+        };
+        if !in_checkpoint {
+            self.checkpoints
+                .last_mut()
+                .unwrap()
+                .insert(key.clone(), old_value.clone().unwrap());
+        }
+        self.uncommitted.insert(
+            key.clone(),
+            UncommittedValue {
+                old_value: old_value.filter(|_| journaling),
+                value,
+            },
+        );
+        Ok(())
     }
 }
 
 impl<'a> StateDbTrait for StateDb<'a> {
     fn get_raw(&self, key: StateKey) -> Result<Option<Box<[u8]>>> {
-        self.storage.get(key.into_owned()).map_err(Into::into)
+        let key = key.into_owned();
+        let cache = match &self.read_cache {
+            // The uncommitted writes are only in the backing store.
+            Some(cache) if !self.uncommitted.contains_key(&key) => cache,
+            _ => return self.storage.get(key).map_err(Into::into),
+        };
+        if let Some(value) = cache.lock().get(&key) {
+            return Ok(value);
+        }
+        let value = self.storage.get(key.clone())?;
+        cache.lock().insert(key, value.clone());
+        Ok(value)
+    }
+
+    fn contains_key(&self, key: StateKey) -> Result<bool> {
+        self.storage
+            .contains_key(key.into_owned())
+            .map_err(Into::into)
+    }
+
+    fn get_raw_with_proof(&self, key: StateKey) -> Result<(Option<Box<[u8]>>, StateProof)> {
+        self.storage
+            .get_with_proof(key.into_owned())
+            .map_err(Into::into)
+    }
+
+    fn iter_prefix(
+        &self,
+        prefix: &[u8],
+    ) -> Result<Box<dyn Iterator<Item = Result<(OwnedStateKey, Box<[u8]>)>> + '_>> {
+        let iter = self.storage.iter_prefix(prefix)?.map(|(key, value)| {
+            match OwnedStateKey::from_bytes(&key) {
+                Some(key) => Ok((key, value)),
+                None => Err(ErrorKind::MalformedStateKey(key).into()),
+            }
+        });
+        Ok(Box::new(iter))
     }
 
     fn set_raw(
@@ -38,9 +365,26 @@ impl<'a> StateDbTrait for StateDb<'a> {
         value: Box<[u8]>,
         debug_record: Option<&mut ComputeEpochDebugRecord>,
     ) -> Result<()> {
-        self.storage
-            .set(key.into_owned(), value)
-            .map_err(Into::into)
+        let key = key.into_owned();
+        self.record_write(&key, Some(value.clone()))?;
+        self.invalidate_cached(&key);
+        self.storage.set(key, value).map_err(Into::into)
+    }
+
+    fn set_raw_batch(
+        &mut self,
+        entries: Vec<(StateKey, Box<[u8]>)>,
+        debug_record: Option<&mut ComputeEpochDebugRecord>,
+    ) -> Result<()> {
+        let entries: Vec<_> = entries
+            .into_iter()
+            .map(|(key, value)| (key.into_owned(), value))
+            .collect();
+        for (key, value) in &entries {
+            self.record_write(key, Some(value.clone()))?;
+            self.invalidate_cached(key);
+        }
+        self.storage.set_batch(entries).map_err(Into::into)
     }
 
     fn delete(
@@ -48,14 +392,137 @@ impl<'a> StateDbTrait for StateDb<'a> {
         key: StateKey,
         debug_record: Option<&mut ComputeEpochDebugRecord>,
     ) -> Result<()> {
-        self.storage.delete(key.into_owned()).map_err(Into::into)
+        let key = key.into_owned();
+        self.record_write(&key, None)?;
+        self.invalidate_cached(&key);
+        self.storage.delete(key).map_err(Into::into)
     }
 
-    fn commit(
+    fn commit_with_summary(
         &mut self,
         epoch_id: EpochId,
         debug_record: Option<&mut ComputeEpochDebugRecord>,
-    ) -> Result<()> {
-        self.storage.commit(epoch_id).map_err(Into::into)
+    ) -> Result<CommitSummary> {
+        self.check_no_checkpoint()?;
+        if let Some(staged_epoch) = self.staged_epoch {
+            bail!(ErrorKind::StagedCommitPending(staged_epoch));
+        }
+        let mut summary = CommitSummary::default();
+        let mut old_values = Vec::with_capacity(self.uncommitted.len());
+        for (key, uncommitted) in &self.uncommitted {
+            if uncommitted.is_unchanged() {
+                continue;
+            }
+            match &uncommitted.value {
+                Some(value) => {
+                    summary.keys_written += 1;
+                    summary.bytes_written += value.len();
+                },
+                None => summary.keys_deleted += 1,
+            }
+            if let Some(old_value) = &uncommitted.old_value {
+                old_values.push((key.clone(), old_value.clone()));
+            }
+        }
+        self.commit_storage(epoch_id)?;
+        self.uncommitted.clear();
+        self.push_epoch_journal(epoch_id, old_values);
+        Ok(summary)
+    }
+
+    fn commit_staged(&mut self, epoch_id: EpochId) -> Result<StagedCommit> {
+        self.check_no_checkpoint()?;
+        if let Some(staged_epoch) = self.staged_epoch {
+            bail!(ErrorKind::StagedCommitPending(staged_epoch));
+        }
+        if !self.epoch_journaling {
+            bail!(ErrorKind::EpochJournalingDisabled);
+        }
+        let mut delta = Vec::with_capacity(self.uncommitted.len());
+        for (key, uncommitted) in std::mem::take(&mut self.uncommitted) {
+            if uncommitted.is_unchanged() {
+                continue;
+            }
+            // Epoch journaling can not be switched with uncommitted changes,
+            // so all of them have their old values.
+            let UncommittedValue { old_value, value } = uncommitted;
+            let old_value = old_value.expect("journaled since the last commit");
+            self.invalidate_cached(&key);
+            match old_value {
+                Some(old_value) => self.storage.set(key.clone(), old_value)?,
+                None => self.storage.delete(key.clone())?,
+            }
+            delta.push((key, value));
+        }
+        self.staged_epoch = Some(epoch_id);
+        Ok(StagedCommit { epoch_id, delta })
+    }
+
+    fn finalize(&mut self, staged: StagedCommit) -> Result<()> {
+        self.check_no_checkpoint()?;
+        if self.staged_epoch != Some(staged.epoch_id) {
+            bail!(ErrorKind::UnknownStagedCommit(staged.epoch_id));
+        }
+        if !self.uncommitted.is_empty() {
+            bail!(ErrorKind::UncommittedChanges);
+        }
+        let mut old_values = Vec::with_capacity(staged.delta.len());
+        for (key, value) in staged.delta {
+            self.invalidate_cached(&key);
+            if self.epoch_journaling {
+                old_values.push((key.clone(), self.storage.get(key.clone())?));
+            }
+            match value {
+                Some(value) => self.storage.set(key, value)?,
+                None => self.storage.delete(key)?,
+            }
+        }
+        self.commit_storage(staged.epoch_id)?;
+        self.staged_epoch = None;
+        self.push_epoch_journal(staged.epoch_id, old_values);
+        Ok(())
+    }
+
+    fn abandon(&mut self, staged: StagedCommit) {
+        if self.staged_epoch == Some(staged.epoch_id) {
+            self.staged_epoch = None;
+        } else {
+            warn!(
+                "Abandon an unknown staged commit: epoch_id={:?}",
+                staged.epoch_id
+            );
+        }
+    }
+
+    fn revert_epoch(&mut self, epoch_id: EpochId, new_epoch_id: EpochId) -> Result<()> {
+        self.check_no_checkpoint()?;
+        if let Some(staged_epoch) = self.staged_epoch {
+            bail!(ErrorKind::StagedCommitPending(staged_epoch));
+        }
+        if !self.uncommitted.is_empty() {
+            bail!(ErrorKind::UncommittedChanges);
+        }
+        match self.epoch_journals.back() {
+            Some(journal) if journal.epoch_id == epoch_id => {},
+            _ => bail!(ErrorKind::NotRevertibleEpoch(epoch_id)),
+        }
+        if self
+            .epoch_journals
+            .iter()
+            .any(|journal| journal.epoch_id == new_epoch_id)
+        {
+            bail!(ErrorKind::EpochAlreadyCommitted(new_epoch_id));
+        }
+        let journal = self.epoch_journals.pop_back().unwrap();
+        for (key, old_value) in journal.old_values {
+            self.invalidate_cached(&key);
+            match old_value {
+                Some(old_value) => self.storage.set(key, old_value)?,
+                None => self.storage.delete(key)?,
+            }
+        }
+        // The restored values are committed as a new epoch, the backing store
+        // never commits an epoch id twice.
+        self.commit_storage(new_epoch_id)
     }
 }
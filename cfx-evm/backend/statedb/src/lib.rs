@@ -11,6 +11,7 @@ extern crate log;
 
 mod error;
 mod impls;
+mod read_cache;
 mod statedb_ext;
 
 #[cfg(test)]
@@ -18,15 +19,34 @@ mod tests;
 
 pub use self::{
     error::{Error, ErrorKind, Result},
-    impls::StateDb,
-    statedb_ext::{StateDbExt, TOTAL_TOKENS_KEY},
+    impls::{CheckpointId, CommitSummary, FlushMode, StagedCommit, StateDb, MAX_REVERTIBLE_EPOCHS},
+    read_cache::ReadCacheStats,
+    statedb_ext::{StateDbExt, SystemStorageKey, TOTAL_TOKENS_KEY},
 };
 use cfx_internal_common::debug::ComputeEpochDebugRecord;
-use primitives::{EpochId, StateKey};
+pub use cfx_storage::StateProof;
+use primitives::{EpochId, OwnedStateKey, StateKey};
 
 pub trait StateDbTrait {
     fn get_raw(&self, key: StateKey) -> Result<Option<Box<[u8]>>>;
 
+    /// Check whether the key exists without reading its value. Like
+    /// `get_raw`, it reflects the keys set and deleted after the last commit.
+    fn contains_key(&self, key: StateKey) -> Result<bool>;
+
+    /// Get the raw value with a merkle proof for the light clients. It
+    /// returns `cfx_storage::ErrorKind::ProofUnsupported` if the underlying
+    /// storage cannot produce proofs.
+    fn get_raw_with_proof(&self, key: StateKey) -> Result<(Option<Box<[u8]>>, StateProof)>;
+
+    /// Iterate the entries whose encoded keys start with `prefix`, in the
+    /// lexicographic order of the encoded keys. The keys set by `set_raw` are
+    /// included and the keys removed by `delete` are excluded.
+    fn iter_prefix(
+        &self,
+        prefix: &[u8],
+    ) -> Result<Box<dyn Iterator<Item = Result<(OwnedStateKey, Box<[u8]>)>> + '_>>;
+
     fn set_raw(
         &mut self,
         key: StateKey,
@@ -34,6 +54,14 @@ pub trait StateDbTrait {
         debug_record: Option<&mut ComputeEpochDebugRecord>,
     ) -> Result<()>;
 
+    /// Set multiple raw entries at once. It is equivalent to calling
+    /// `set_raw` for each entry in order.
+    fn set_raw_batch(
+        &mut self,
+        entries: Vec<(StateKey, Box<[u8]>)>,
+        debug_record: Option<&mut ComputeEpochDebugRecord>,
+    ) -> Result<()>;
+
     fn delete(
         &mut self,
         key: StateKey,
@@ -44,5 +72,44 @@ pub trait StateDbTrait {
         &mut self,
         epoch_id: EpochId,
         debug_record: Option<&mut ComputeEpochDebugRecord>,
-    ) -> Result<()>;
+    ) -> Result<()> {
+        self.commit_with_summary(epoch_id, debug_record).map(|_| ())
+    }
+
+    /// Commit like `commit`, and summarize the keys written and deleted since
+    /// the last commit. Without epoch journaling, the keys changed back to
+    /// their committed values are counted too.
+    fn commit_with_summary(
+        &mut self,
+        epoch_id: EpochId,
+        debug_record: Option<&mut ComputeEpochDebugRecord>,
+    ) -> Result<CommitSummary>;
+
+    /// The first phase of a two-phase commit. It takes the changes since the
+    /// last commit out of the backing store into a `StagedCommit`, so the
+    /// state reads as the last committed state afterwards. At most one staged
+    /// commit can be pending. It requires epoch journaling, see
+    /// `StateDb::set_epoch_journaling`.
+    fn commit_staged(&mut self, epoch_id: EpochId) -> Result<StagedCommit>;
+
+    /// Write the changes of a staged commit to the backing store and commit
+    /// them. The state must not have other uncommitted changes.
+    fn finalize(&mut self, staged: StagedCommit) -> Result<()>;
+
+    /// Drop the changes of a staged commit.
+    fn abandon(&mut self, staged: StagedCommit);
+
+    /// Revert the writes of the last committed epoch, e.g. on a chain reorg,
+    /// so the state reads as before the epoch. Only the last epoch can be
+    /// reverted, and then the epoch before it, as long as they are still
+    /// journaled with epoch journaling. The state must not have uncommitted
+    /// changes.
+    ///
+    /// The restored values are committed to the backing store as the epoch
+    /// `new_epoch_id`, so the backing store only needs to support ordinary
+    /// commits, not rewinding to an epoch committed before. `new_epoch_id`
+    /// must not have been committed before, and it is rejected with
+    /// `ErrorKind::EpochAlreadyCommitted` if it is a revertible epoch. The new
+    /// epoch itself is not revertible.
+    fn revert_epoch(&mut self, epoch_id: EpochId, new_epoch_id: EpochId) -> Result<()>;
 }
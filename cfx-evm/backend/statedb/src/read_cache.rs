@@ -0,0 +1,100 @@
+use primitives::OwnedStateKey;
+use std::collections::{BTreeMap, HashMap};
+
+/// The hit and miss counters of the read cache of `StateDb`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// The number of the cached keys.
+    pub len: usize,
+}
+
+struct CacheEntry {
+    value: Option<Box<[u8]>>,
+    last_use: u64,
+}
+
+/// The values read from the backing store, used by `StateDb::get_raw`. It
+/// keeps at most `capacity` keys, and the least recently used key is evicted
+/// first. `None` means the key does not exist in the backing store.
+pub(crate) struct ReadCache {
+    capacity: usize,
+    entries: HashMap<OwnedStateKey, CacheEntry>,
+    /// The keys ordered by their last use.
+    by_last_use: BTreeMap<u64, OwnedStateKey>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl ReadCache {
+    pub fn new(capacity: usize) -> Self {
+        ReadCache {
+            capacity,
+            entries: HashMap::new(),
+            by_last_use: BTreeMap::new(),
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn stats(&self) -> ReadCacheStats {
+        ReadCacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            len: self.entries.len(),
+        }
+    }
+
+    /// Get the cached value of the key, and count a hit or a miss. A hit
+    /// counts as a use of the key.
+    pub fn get(&mut self, key: &OwnedStateKey) -> Option<Option<Box<[u8]>>> {
+        self.clock += 1;
+        let clock = self.clock;
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                self.hits += 1;
+                self.by_last_use.remove(&entry.last_use);
+                self.by_last_use.insert(clock, key.clone());
+                entry.last_use = clock;
+                Some(entry.value.clone())
+            },
+            None => {
+                self.misses += 1;
+                None
+            },
+        }
+    }
+
+    /// Cache the value read from the backing store, and evict the least
+    /// recently used key if the cache is full.
+    pub fn insert(&mut self, key: OwnedStateKey, value: Option<Box<[u8]>>) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.clock += 1;
+        let entry = CacheEntry {
+            value,
+            last_use: self.clock,
+        };
+        if let Some(old_entry) = self.entries.insert(key.clone(), entry) {
+            self.by_last_use.remove(&old_entry.last_use);
+        }
+        self.by_last_use.insert(self.clock, key);
+        while self.entries.len() > self.capacity {
+            let (_, evicted) = self
+                .by_last_use
+                .pop_first()
+                .expect("The cache is not empty");
+            self.entries.remove(&evicted);
+        }
+    }
+
+    pub fn invalidate(&mut self, key: &OwnedStateKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.by_last_use.remove(&entry.last_use);
+        }
+    }
+}
@@ -2,16 +2,65 @@
 // Conflux is free software and distributed under GNU General Public License.
 // See http://www.gnu.org/licenses/
 
-use super::Result;
+use super::{ErrorKind, Result};
 use crate::StateDbTrait;
 use cfx_internal_common::debug::ComputeEpochDebugRecord;
-use cfx_parameters::internal_contract_addresses::STORAGE_INTEREST_STAKING_CONTRACT_ADDRESS;
-use cfx_types::{AddressSpaceUtil, AddressWithSpace, H256, U256};
-use primitives::{is_default::IsDefault, Account, CodeInfo, StateKey};
+use cfx_parameters::internal_contract_addresses::{
+    STORAGE_INTEREST_STAKING_CONTRACT_ADDRESS, SYSTEM_STORAGE_ADDRESS,
+};
+use cfx_types::{Address, AddressSpaceUtil, AddressWithSpace, H256, U256};
+use keccak_hash::keccak;
+use primitives::{
+    account::AccountError, is_default::IsDefault, Account, CodeInfo, OwnedStateKey, StateKey,
+    StorageValue,
+};
 use rlp::Rlp;
+use std::any::type_name;
 
 pub const TOTAL_TOKENS_KEY: &'static [u8] = b"total_issued_tokens";
 
+/// The reserved prefix of the parameter keys in the system storage. The keys
+/// written by the `SystemStorage` contract are 32 bytes long, so the longer
+/// parameter keys never collide with them.
+const SYSTEM_PARAM_PREFIX: &'static [u8] = b"system_param";
+
+/// A storage slot reserved for the internal contracts. The slots are only
+/// built by the constructors, which keep each purpose in its own keyspace.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SystemStorageKey {
+    address: AddressWithSpace,
+    key: Vec<u8>,
+}
+
+impl SystemStorageKey {
+    /// The slot of the total issued tokens.
+    pub fn total_tokens() -> Self {
+        SystemStorageKey {
+            address: STORAGE_INTEREST_STAKING_CONTRACT_ADDRESS.with_evm_space(),
+            key: TOTAL_TOKENS_KEY.to_vec(),
+        }
+    }
+
+    /// The slot of a named parameter in the system storage. It is the hash of
+    /// the name under `SYSTEM_PARAM_PREFIX`.
+    pub fn param(name: &str) -> Self {
+        SystemStorageKey {
+            address: SYSTEM_STORAGE_ADDRESS.with_evm_space(),
+            key: [SYSTEM_PARAM_PREFIX, keccak(name.as_bytes()).as_bytes()].concat(),
+        }
+    }
+
+    pub fn address(&self) -> &AddressWithSpace {
+        &self.address
+    }
+
+    pub fn as_state_key(&self) -> StateKey {
+        StateKey::new_storage_key(&self.address, &self.key)
+    }
+}
+
+/// The typed getters return `ErrorKind::Decode` if a stored value is
+/// malformed.
 pub trait StateDbExt: StateDbTrait {
     fn get<T>(&self, key: StateKey) -> Result<Option<T>>
     where
@@ -19,7 +68,10 @@ pub trait StateDbExt: StateDbTrait {
     {
         match self.get_raw(key) {
             Ok(None) => Ok(None),
-            Ok(Some(raw)) => Ok(Some(::rlp::decode::<T>(raw.as_ref())?)),
+            Ok(Some(raw)) => match ::rlp::decode::<T>(raw.as_ref()) {
+                Ok(value) => Ok(Some(value)),
+                Err(_) => bail!(ErrorKind::Decode(key.into_owned(), type_name::<T>())),
+            },
             Err(e) => bail!(e),
         }
     }
@@ -45,24 +97,80 @@ pub trait StateDbExt: StateDbTrait {
     }
 
     fn get_account(&self, address: &AddressWithSpace) -> Result<Option<Account>> {
-        match self.get_raw(StateKey::new_account_key(&address)) {
+        let key = StateKey::new_account_key(&address);
+        match self.get_raw(key) {
             Ok(None) => Ok(None),
-            Ok(Some(raw)) => Ok(Some(Account::new_from_rlp(
-                address.address,
-                &Rlp::new(&raw),
-            )?)),
+            Ok(Some(raw)) => match Account::new_from_rlp(address.address, &Rlp::new(&raw)) {
+                Ok(account) => Ok(Some(account)),
+                Err(AccountError::InvalidRlp(_)) => {
+                    bail!(ErrorKind::Decode(key.into_owned(), type_name::<Account>()))
+                },
+                Err(e) => bail!(e),
+            },
             Err(e) => bail!(e),
         }
     }
 
+    fn get_system<T>(&self, key: &SystemStorageKey) -> Result<Option<T>>
+    where
+        T: ::rlp::Decodable,
+    {
+        self.get::<T>(key.as_state_key())
+    }
+
+    fn set_system<T>(
+        &mut self,
+        key: &SystemStorageKey,
+        value: &T,
+        debug_record: Option<&mut ComputeEpochDebugRecord>,
+    ) -> Result<()>
+    where
+        T: ::rlp::Encodable + IsDefault,
+    {
+        self.set::<T>(key.as_state_key(), value, debug_record)
+    }
+
+    /// Read all the storage slots of a contract in the ethereum space, sorted
+    /// by key. It is a diagnostic tool, e.g. for reconciling the state of an
+    /// internal contract across the spaces. Like `iter_prefix`, it reflects
+    /// the committed state plus the writes pending since the last commit.
+    ///
+    /// The slots written by EVM code hold a `StorageValue`, and the slots
+    /// written by `set_system` hold a plain `U256`. Both are decoded.
+    fn dump_contract_storage(&self, address: &Address) -> Result<Vec<(Vec<u8>, U256)>> {
+        // The encoding of the empty storage key is the prefix of every
+        // storage key of the contract.
+        let prefix: Vec<u8> = OwnedStateKey::StorageKey {
+            address: address.with_evm_space(),
+            storage_key: vec![],
+        }
+        .into();
+        let mut entries = vec![];
+        for entry in self.iter_prefix(&prefix)? {
+            let (key, raw) = entry?;
+            let value = match ::rlp::decode::<StorageValue>(raw.as_ref()) {
+                Ok(value) => value.value,
+                Err(_) => match ::rlp::decode::<U256>(raw.as_ref()) {
+                    Ok(value) => value,
+                    Err(_) => bail!(ErrorKind::Decode(key, "StorageValue or U256")),
+                },
+            };
+            let storage_key = match key {
+                OwnedStateKey::StorageKey { storage_key, .. } => storage_key,
+                key => bail!(ErrorKind::MalformedStateKey(key.into())),
+            };
+            entries.push((storage_key, value));
+        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(entries)
+    }
+
     fn get_code(&self, address: &AddressWithSpace, code_hash: &H256) -> Result<Option<CodeInfo>> {
         self.get::<CodeInfo>(StateKey::new_code_key(&address))
     }
     fn get_total_issued_tokens(&self) -> Result<U256> {
-        let address = STORAGE_INTEREST_STAKING_CONTRACT_ADDRESS.with_evm_space();
-        let total_issued_tokens_key = StateKey::new_storage_key(&address, TOTAL_TOKENS_KEY);
-        let total_issued_tokens_opt = self.get::<U256>(total_issued_tokens_key)?;
-        Ok(total_issued_tokens_opt.unwrap_or_default())
+        let total_issued_tokens = self.get_system::<U256>(&SystemStorageKey::total_tokens())?;
+        Ok(total_issued_tokens.unwrap_or_default())
     }
 
     fn set_total_issued_tokens(
@@ -70,9 +178,11 @@ pub trait StateDbExt: StateDbTrait {
         total_issued_tokens: &U256,
         debug_record: Option<&mut ComputeEpochDebugRecord>,
     ) -> Result<()> {
-        let address = STORAGE_INTEREST_STAKING_CONTRACT_ADDRESS.with_evm_space();
-        let total_issued_tokens_key = StateKey::new_storage_key(&address, TOTAL_TOKENS_KEY);
-        self.set::<U256>(total_issued_tokens_key, total_issued_tokens, debug_record)
+        self.set_system::<U256>(
+            &SystemStorageKey::total_tokens(),
+            total_issued_tokens,
+            debug_record,
+        )
     }
 }
 
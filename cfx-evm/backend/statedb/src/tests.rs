@@ -2,151 +2,134 @@
 // Conflux is free software and distributed under GNU General Public License.
 // See http://www.gnu.org/licenses/
 
-use super::StateDb;
-use cfx_internal_common::StateRootWithAuxInfo;
-use cfx_storage::{utils::access_mode, ErrorKind, MptKeyValue, Result, StorageStateTrait};
+use super::{
+    CommitSummary, ErrorKind, FlushMode, ReadCacheStats, StateDb, StateDbExt, StateDbTrait,
+    SystemStorageKey,
+};
+use cfx_storage::{
+    ErrorKind as StorageErrorKind, InMemoryDb, RawEntryIter, Result, StateProof, StorageTrait,
+};
+use cfx_types::{Address, AddressSpaceUtil, AddressWithSpace, Space, H256, U256};
 use parking_lot::Mutex;
-use primitives::{EpochId, StorageKey, StorageKeyWithSpace, MERKLE_NULL_NODE};
-use std::collections::HashMap;
+use primitives::{EpochId, OwnedStateKey, StateKey};
+use std::{collections::HashMap, sync::Arc};
 
 type StorageValue = Box<[u8]>;
 type RawStorage = HashMap<Vec<u8>, StorageValue>;
 
+/// A storage whose contents and counters are shared by its clones, so a test
+/// can inspect them while a `StateDb` owns the storage.
+#[derive(Clone, Default)]
 struct MockStorage {
-    pub contents: RawStorage,
-    num_reads: Mutex<u64>,
-    num_writes: u64,
+    contents: Arc<Mutex<RawStorage>>,
+    num_reads: Arc<Mutex<u64>>,
+    num_writes: Arc<Mutex<u64>>,
+    commits: Arc<Mutex<Vec<EpochId>>>,
 }
 
 impl MockStorage {
-    #[allow(unused)]
-    fn empty() -> Self {
+    fn with_contents(contents: RawStorage) -> Self {
         MockStorage {
-            contents: Default::default(),
-            num_reads: Mutex::new(0),
-            num_writes: 0,
+            contents: Arc::new(Mutex::new(contents)),
+            ..Default::default()
         }
     }
 
-    fn with_contents(contents: RawStorage) -> Self {
-        MockStorage {
-            contents,
-            num_reads: Mutex::new(0),
-            num_writes: 0,
-        }
+    fn contents(&self) -> RawStorage {
+        self.contents.lock().clone()
     }
 
-    #[allow(unused)]
-    pub fn get_num_reads(&self) -> u64 {
+    fn get_num_reads(&self) -> u64 {
         *self.num_reads.lock()
     }
 
-    #[allow(unused)]
-    pub fn get_num_writes(&self) -> u64 {
-        self.num_writes
+    fn get_num_writes(&self) -> u64 {
+        *self.num_writes.lock()
     }
-}
 
-#[allow(unused)]
-impl StorageStateTrait for MockStorage {
-    fn commit(&mut self, epoch: EpochId) -> Result<StateRootWithAuxInfo> {
-        self.compute_state_root()
+    fn commits(&self) -> Vec<EpochId> {
+        self.commits.lock().clone()
     }
+}
 
-    fn compute_state_root(&mut self) -> Result<StateRootWithAuxInfo> {
-        Ok(StateRootWithAuxInfo::genesis(&MERKLE_NULL_NODE))
-    }
+impl StorageTrait for MockStorage {
+    type StorageKey = Vec<u8>;
 
-    fn delete(&mut self, access_key: StorageKeyWithSpace) -> Result<()> {
-        self.num_writes += 1;
-        let key = access_key.to_key_bytes();
-        self.contents.remove(&key);
-        Ok(())
+    fn get(&self, key: Self::StorageKey) -> Result<Option<Box<[u8]>>> {
+        *self.num_reads.lock() += 1;
+        Ok(self.contents.lock().get(&key).cloned())
     }
 
-    fn delete_all(
-        &mut self,
-        access_key_prefix: StorageKeyWithSpace,
-    ) -> Result<Option<Vec<MptKeyValue>>> {
-        let prefix = access_key_prefix.to_key_bytes();
-
-        let keys_to_delete: Vec<_> = self
+    fn iter_prefix(&self, prefix: &[u8]) -> Result<RawEntryIter> {
+        let mut entries: Vec<_> = self
             .contents
-            .keys()
-            .filter(|k| k.starts_with(&prefix[..]))
-            .cloned()
+            .lock()
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
             .collect();
-
-        let mut deleted_kvs = vec![];
-
-        for k in keys_to_delete {
-            *self.num_reads.get_mut() += 1;
-            let v = self.contents.get(&k).unwrap();
-            deleted_kvs.push((k.clone(), v.clone()));
-
-            self.num_writes += 1;
-            self.contents.remove(&k);
-        }
-
-        Ok(Some(deleted_kvs))
-    }
-
-    fn delete_test_only(&mut self, access_key: StorageKeyWithSpace) -> Result<Option<Box<[u8]>>> {
-        unimplemented!()
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(Box::new(entries.into_iter()))
     }
 
-    fn get(&self, access_key: StorageKeyWithSpace) -> Result<Option<Box<[u8]>>> {
-        *self.num_reads.lock() += 1;
-        let key = access_key.to_key_bytes();
-        Ok(self.contents.get(&key).cloned())
+    fn set(&mut self, access_key: Self::StorageKey, value: Box<[u8]>) -> Result<()> {
+        *self.num_writes.lock() += 1;
+        self.contents.lock().insert(access_key, value);
+        Ok(())
     }
 
-    fn get_state_root(&self) -> Result<StateRootWithAuxInfo> {
-        Err(ErrorKind::Msg("No state root".to_owned()).into())
+    fn delete(&mut self, access_key: Self::StorageKey) -> Result<()> {
+        *self.num_writes.lock() += 1;
+        self.contents.lock().remove(&access_key);
+        Ok(())
     }
 
-    fn set(&mut self, access_key: StorageKeyWithSpace, value: Box<[u8]>) -> Result<()> {
-        self.num_writes += 1;
-        let key = access_key.to_key_bytes();
-        self.contents.insert(key, value);
+    fn commit(&mut self, epoch: EpochId) -> Result<()> {
+        self.commits.lock().push(epoch);
         Ok(())
     }
+}
 
-    fn read_all(
-        &mut self,
-        access_key_prefix: StorageKeyWithSpace,
-    ) -> Result<Option<Vec<MptKeyValue>>> {
-        let prefix = access_key_prefix.to_key_bytes();
+/// A storage which proves each value with the encoded key.
+struct ProvingStorage(MockStorage);
 
-        let keys: Vec<_> = self
-            .contents
-            .keys()
-            .filter(|k| k.starts_with(&prefix[..]))
-            .cloned()
-            .collect();
+impl StorageTrait for ProvingStorage {
+    type StorageKey = Vec<u8>;
+
+    fn get(&self, key: Self::StorageKey) -> Result<Option<Box<[u8]>>> {
+        self.0.get(key)
+    }
 
-        let mut kvs = vec![];
+    fn get_with_proof(&self, key: Self::StorageKey) -> Result<(Option<Box<[u8]>>, StateProof)> {
+        Ok((self.0.get(key.clone())?, StateProof::new(key)))
+    }
 
-        for k in keys {
-            *self.num_reads.get_mut() += 1;
-            let v = self.contents.get(&k).unwrap();
-            kvs.push((k.clone(), v.clone()));
-        }
+    fn set(&mut self, access_key: Self::StorageKey, value: Box<[u8]>) -> Result<()> {
+        self.0.set(access_key, value)
+    }
 
-        Ok(Some(kvs))
+    fn delete(&mut self, access_key: Self::StorageKey) -> Result<()> {
+        self.0.delete(access_key)
+    }
+
+    fn commit(&mut self, epoch: EpochId) -> Result<()> {
+        self.0.commit(epoch)
     }
 }
 
-type StateDbTest = StateDb;
+static ADDRESS: AddressWithSpace = AddressWithSpace {
+    address: Address::repeat_byte(0x11),
+    space: Space::Ethereum,
+};
 
 // convert `key` to storage interface format
-fn storage_key(key: &'static [u8]) -> StorageKeyWithSpace {
-    StorageKey::AccountKey(key).with_native_space()
+fn storage_key(key: &'static [u8]) -> StateKey<'static> {
+    StateKey::new_storage_key(&ADDRESS, key)
 }
 
 // convert `key` to raw storage format
 fn key(key: &'static [u8]) -> Vec<u8> {
-    storage_key(key).to_key_bytes()
+    storage_key(key).into_owned().into()
 }
 
 // convert `value` to raw storage format
@@ -154,35 +137,33 @@ fn value(value: &'static [u8]) -> StorageValue {
     value.into()
 }
 
-fn init_state_db() -> StateDbTest {
+fn epoch(n: u64) -> EpochId {
+    H256::from_low_u64_be(n)
+}
+
+fn init_storage() -> MockStorage {
     let mut contents = RawStorage::new();
     contents.insert(key(b"00"), value(b"v0"));
     contents.insert(key(b"01"), value(b"v0"));
     contents.insert(key(b"11"), value(b"v0"));
     contents.insert(key(b"22"), value(b"v0"));
+    MockStorage::with_contents(contents)
+}
 
-    let storage = MockStorage::with_contents(contents);
-    StateDbTest::new(Box::new(storage))
+fn init_state_db() -> (StateDb<'static>, MockStorage) {
+    let storage = init_storage();
+    (StateDb::new(storage.clone()), storage)
 }
 
-#[allow(unused)]
-fn print_raw(raw: &RawStorage) {
-    let mut keys: Vec<_> = raw.keys().collect();
-    keys.sort();
-
-    for k in keys {
-        let v = &raw[k];
-        println!(
-            "k = {:?}; v = {:?}",
-            std::str::from_utf8(k).unwrap(),
-            std::str::from_utf8(v).unwrap()
-        );
-    }
+fn init_journaled_state_db() -> (StateDb<'static>, MockStorage) {
+    let (mut state_db, storage) = init_state_db();
+    state_db.set_epoch_journaling(true).unwrap();
+    (state_db, storage)
 }
 
 #[test]
 fn test_basic() {
-    let mut state_db = init_state_db();
+    let (mut state_db, storage) = init_state_db();
 
     // (11, v0) --> (11, v1)
     state_db
@@ -193,34 +174,23 @@ fn test_basic() {
     state_db.delete(storage_key(b"22"), None).unwrap();
 
     // delete (00, v0) and (01, v0)
-    state_db
-        .delete_all::<access_mode::Write>(storage_key(b"0"), None)
-        .unwrap();
-
-    state_db.commit(MERKLE_NULL_NODE, None).unwrap();
-    // FIXME(lpl): Enable tests.
-    // let storage = (state_db.get_storage_mut() as &dyn
-    // Any).downcast_ref::<MockStorage>().unwrap(); let contents =
-    // &storage.contents;
-    //
-    // // we expect only one value after commit
-    // let expected: HashMap<_, _> =
-    //     [(key(b"11"), value(b"v1"))].iter().cloned().collect();
-    //
-    // assert_eq!(*contents, expected);
-    //
-    // // we need to read all values touched
-    // assert_eq!(storage.get_num_reads(), 4);
-    //
-    // // we need to write all values modified or removed
-    // assert_eq!(storage.get_num_writes(), 4);
+    state_db.delete(storage_key(b"00"), None).unwrap();
+    state_db.delete(storage_key(b"01"), None).unwrap();
+
+    state_db.commit(epoch(1), None).unwrap();
+
+    // we expect only one value after commit
+    let expected: RawStorage = [(key(b"11"), value(b"v1"))].iter().cloned().collect();
+    assert_eq!(storage.contents(), expected);
+
+    // we need to write all values modified or removed
+    assert_eq!(storage.get_num_writes(), 4);
+    assert_eq!(storage.commits(), vec![epoch(1)]);
 }
 
 #[test]
 fn test_checkpoint() {
-    use super::StateDbCheckpointMethods;
-
-    let mut state_db = init_state_db();
+    let (mut state_db, storage) = init_state_db();
 
     // (11, v0) --> (11, v1)
     state_db
@@ -228,106 +198,689 @@ fn test_checkpoint() {
         .unwrap();
 
     // create checkpoint #0
-    state_db.checkpoint();
+    let checkpoint = state_db.checkpoint();
+    assert_eq!(checkpoint, 0);
 
     // delete (22, v0)
     state_db.delete(storage_key(b"22"), None).unwrap();
 
     // create checkpoint #1
-    state_db.checkpoint();
+    assert_eq!(state_db.checkpoint(), 1);
 
     // delete (00, v0) and (01, v0)
-    state_db
-        .delete_all::<access_mode::Write>(storage_key(b"0"), None)
-        .unwrap();
+    state_db.delete(storage_key(b"00"), None).unwrap();
+    state_db.delete(storage_key(b"01"), None).unwrap();
 
     // discard checkpoint #1
-    state_db.discard_checkpoint();
+    state_db.discard_checkpoint(1);
 
     // create (33, v0)
     state_db
         .set_raw(storage_key(b"33"), value(b"v0"), None)
         .unwrap();
+    assert_eq!(state_db.get_raw(storage_key(b"00")).unwrap(), None);
 
     // revert to checkpoint #0 --> undo deletes
-    state_db.revert_to_checkpoint();
-
-    state_db.commit(MERKLE_NULL_NODE, None).unwrap();
-    // let storage = (state_db.get_storage_mut() as &dyn
-    // Any).downcast_ref::<MockStorage>().unwrap(); let contents =
-    // &storage.contents;
-    //
-    // // only the initial `set` was committed
-    // let expected: HashMap<_, _> = [
-    //     (key(b"00"), value(b"v0")),
-    //     (key(b"01"), value(b"v0")),
-    //     (key(b"11"), value(b"v1")),
-    //     (key(b"22"), value(b"v0")),
-    // ]
-    // .iter()
-    // .cloned()
-    // .collect();
-    //
-    // assert_eq!(*contents, expected);
-    //
-    // // we need to read all values touched
-    // assert_eq!(storage.get_num_reads(), 5);
-    //
-    // // we need to write all values modified or removed
-    // assert_eq!(storage.get_num_writes(), 1);
+    state_db.revert_to(checkpoint).unwrap();
+    assert_eq!(
+        state_db.get_raw(storage_key(b"00")).unwrap(),
+        Some(value(b"v0"))
+    );
+    assert_eq!(state_db.get_raw(storage_key(b"33")).unwrap(), None);
+
+    state_db.commit(epoch(1), None).unwrap();
+
+    // only the initial `set` was committed
+    let expected: RawStorage = [
+        (key(b"00"), value(b"v0")),
+        (key(b"01"), value(b"v0")),
+        (key(b"11"), value(b"v1")),
+        (key(b"22"), value(b"v0")),
+    ]
+    .iter()
+    .cloned()
+    .collect();
+    assert_eq!(storage.contents(), expected);
 }
 
 #[test]
-fn test_checkpoint_evict_memory() {
-    use super::StateDbCheckpointMethods;
-
-    let mut state_db = init_state_db();
-
-    // value is not read yet
-    assert_eq!(state_db.get_from_cache(&key(b"00")), None);
+fn test_checkpoint_nested_revert() {
+    let (mut state_db, _) = init_state_db();
 
     // create checkpoint #0
-    state_db.checkpoint();
+    let checkpoint0 = state_db.checkpoint();
 
     // (00, v0) --> (00, v1) [new value]
     state_db
         .set_raw(storage_key(b"00"), value(b"v1"), None)
         .unwrap();
 
-    // value has been read
+    // create checkpoint #1
+    let checkpoint1 = state_db.checkpoint();
+
+    // (00, v1) --> (00, v0) [back to original value]
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v0"), None)
+        .unwrap();
+
+    // revert to checkpoint #1
+    // (00, v0) --> (00, v1)
+    state_db.revert_to(checkpoint1).unwrap();
     assert_eq!(
-        state_db.get_from_cache(&key(b"00")),
-        Some(value(b"v1").into())
+        state_db.get_raw(storage_key(b"00")).unwrap(),
+        Some(value(b"v1"))
     );
 
-    // create checkpoint #1
+    // checkpoint #1 is removed by the revert
+    assert!(matches!(
+        state_db.revert_to(checkpoint1).unwrap_err().kind(),
+        ErrorKind::UnknownCheckpoint(1)
+    ));
+
+    // revert to checkpoint #0
+    // (00, v1) --> (00, v0)
+    state_db.revert_to(checkpoint0).unwrap();
+    assert_eq!(
+        state_db.get_raw(storage_key(b"00")).unwrap(),
+        Some(value(b"v0"))
+    );
+}
+
+#[test]
+fn test_commit_with_open_checkpoint() {
+    let (mut state_db, storage) = init_state_db();
+
     state_db.checkpoint();
+    state_db
+        .set_raw(storage_key(b"11"), value(b"v1"), None)
+        .unwrap();
+    assert!(matches!(
+        state_db.commit(epoch(1), None).unwrap_err().kind(),
+        ErrorKind::CheckpointOpen(1)
+    ));
+    assert!(storage.commits().is_empty());
+
+    state_db.discard_checkpoint(0);
+    state_db.commit(epoch(1), None).unwrap();
+    assert_eq!(storage.commits(), vec![epoch(1)]);
+}
 
-    // (00, v1) --> (00, v0) [back to original value]
+#[test]
+fn test_get_raw_with_proof() {
+    let (state_db, _) = init_state_db();
+    match state_db
+        .get_raw_with_proof(storage_key(b"00"))
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::Storage(e) => assert!(matches!(e.kind(), StorageErrorKind::ProofUnsupported)),
+        e => panic!("Unexpected error: {}", e),
+    }
+
+    let mut state_db = StateDb::new(ProvingStorage(init_storage()));
+    state_db
+        .set_raw(storage_key(b"33"), value(b"v1"), None)
+        .unwrap();
+    let (raw, proof) = state_db.get_raw_with_proof(storage_key(b"33")).unwrap();
+    assert_eq!(raw, Some(value(b"v1")));
+    assert_eq!(proof.into_bytes(), key(b"33"));
+    let (raw, _) = state_db.get_raw_with_proof(storage_key(b"44")).unwrap();
+    assert_eq!(raw, None);
+}
+
+#[test]
+fn test_set_raw_batch() {
+    let (mut state_db, storage) = init_state_db();
+    let checkpoint = state_db.checkpoint();
+
+    // The entries are applied in order, so the last value of a key wins.
+    state_db
+        .set_raw_batch(
+            vec![
+                (storage_key(b"33"), value(b"v1")),
+                (storage_key(b"00"), value(b"v1")),
+                (storage_key(b"33"), value(b"v2")),
+            ],
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        state_db.get_raw(storage_key(b"33")).unwrap(),
+        Some(value(b"v2"))
+    );
+    assert_eq!(
+        state_db.get_raw(storage_key(b"00")).unwrap(),
+        Some(value(b"v1"))
+    );
+
+    // The batch is journaled like `set_raw`.
+    state_db.revert_to(checkpoint).unwrap();
+    assert_eq!(storage.contents(), init_storage().contents());
+
+    state_db
+        .set_raw_batch(vec![(storage_key(b"33"), value(b"v1"))], None)
+        .unwrap();
+    state_db.commit(epoch(1), None).unwrap();
+    assert_eq!(storage.contents().get(&key(b"33")), Some(&value(b"v1")));
+}
+
+#[test]
+fn test_iter_prefix() {
+    let (mut state_db, storage) = init_state_db();
+    let entries = |state_db: &StateDb, prefix: &[u8]| -> Vec<(OwnedStateKey, Box<[u8]>)> {
+        state_db
+            .iter_prefix(prefix)
+            .unwrap()
+            .collect::<super::Result<_>>()
+            .unwrap()
+    };
+    let entry =
+        |key: &'static [u8], value: &'static [u8]| (storage_key(key).into_owned(), value.into());
+
+    // The pending writes and deletes are visible before the commit.
+    state_db.delete(storage_key(b"00"), None).unwrap();
+    state_db
+        .set_raw(storage_key(b"33"), value(b"v1"), None)
+        .unwrap();
+    assert_eq!(entries(&state_db, &key(b"0")), vec![entry(b"01", b"v0")]);
+    assert_eq!(
+        entries(&state_db, &key(b"")),
+        vec![
+            entry(b"01", b"v0"),
+            entry(b"11", b"v0"),
+            entry(b"22", b"v0"),
+            entry(b"33", b"v1"),
+        ]
+    );
+    assert!(entries(&state_db, &key(b"4")).is_empty());
+
+    // A key which is not a state key is reported.
+    let mut malformed = key(b"");
+    malformed.truncate(20);
+    malformed.extend_from_slice(b"malformed");
+    storage
+        .contents
+        .lock()
+        .insert(malformed.clone(), value(b"v0"));
+    let errors: Vec<_> = state_db
+        .iter_prefix(&malformed)
+        .unwrap()
+        .filter_map(|entry| entry.err())
+        .collect();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0].kind(), ErrorKind::MalformedStateKey(key) if *key == malformed));
+
+    let state_db = StateDb::new(ProvingStorage(init_storage()));
+    match state_db.iter_prefix(&key(b"")).err().unwrap().kind() {
+        ErrorKind::Storage(e) => {
+            assert!(matches!(e.kind(), StorageErrorKind::IterationUnsupported))
+        },
+        e => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn test_staged_commit() {
+    let (mut state_db, storage) = init_journaled_state_db();
+
+    state_db
+        .set_raw(storage_key(b"11"), value(b"v1"), None)
+        .unwrap();
+    state_db.delete(storage_key(b"22"), None).unwrap();
+    // Writing the old value back is not a change.
     state_db
         .set_raw(storage_key(b"00"), value(b"v0"), None)
         .unwrap();
 
-    // value stays in state-db
+    // The staged changes are taken out of the backing store.
+    let staged = state_db.commit_staged(epoch(1)).unwrap();
+    assert_eq!(*staged.epoch_id(), epoch(1));
+    let mut delta = staged.delta().to_vec();
+    delta.sort_by_key(|(key, _)| Vec::<u8>::from(key.clone()));
+    assert_eq!(
+        delta,
+        vec![
+            (storage_key(b"11").into_owned(), Some(value(b"v1"))),
+            (storage_key(b"22").into_owned(), None),
+        ]
+    );
+    assert_eq!(storage.contents(), init_storage().contents());
+    assert!(storage.commits().is_empty());
+    assert!(matches!(
+        state_db.commit_staged(epoch(2)).unwrap_err().kind(),
+        ErrorKind::StagedCommitPending(epoch_id) if *epoch_id == epoch(1)
+    ));
+
+    state_db.finalize(staged).unwrap();
     assert_eq!(
-        state_db.get_from_cache(&key(b"00")),
-        Some(value(b"v0").into())
+        state_db.get_raw(storage_key(b"11")).unwrap(),
+        Some(value(b"v1"))
     );
+    assert_eq!(state_db.get_raw(storage_key(b"22")).unwrap(), None);
+    assert_eq!(storage.commits(), vec![epoch(1)]);
 
-    // revert to checkpoint #1
-    // (00, v0) --> (00, v1)
-    state_db.revert_to_checkpoint();
+    // An abandoned commit leaves the committed state.
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v1"), None)
+        .unwrap();
+    let staged = state_db.commit_staged(epoch(2)).unwrap();
+    state_db.abandon(staged);
+    assert_eq!(
+        state_db.get_raw(storage_key(b"00")).unwrap(),
+        Some(value(b"v0"))
+    );
+
+    // A staged commit can not be finalized with other uncommitted changes.
+    let staged = state_db.commit_staged(epoch(3)).unwrap();
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v1"), None)
+        .unwrap();
+    assert!(matches!(
+        state_db.finalize(staged).unwrap_err().kind(),
+        ErrorKind::UncommittedChanges
+    ));
+}
 
-    // value stays in state-db
+#[test]
+fn test_staged_commit_errors() {
+    let (mut state_db, _) = init_journaled_state_db();
+    let (mut other_state_db, _) = init_journaled_state_db();
+
+    let staged = other_state_db.commit_staged(epoch(1)).unwrap();
+    assert!(matches!(
+        state_db.finalize(staged).unwrap_err().kind(),
+        ErrorKind::UnknownStagedCommit(epoch_id) if *epoch_id == epoch(1)
+    ));
+
+    state_db.checkpoint();
+    assert!(matches!(
+        state_db.commit_staged(epoch(1)).unwrap_err().kind(),
+        ErrorKind::CheckpointOpen(1)
+    ));
+    state_db.discard_checkpoint(0);
+    let staged = state_db.commit_staged(epoch(1)).unwrap();
+    state_db.checkpoint();
+    assert!(matches!(
+        state_db.finalize(staged).unwrap_err().kind(),
+        ErrorKind::CheckpointOpen(1)
+    ));
+
+    let (mut state_db, _) = init_state_db();
+    assert!(matches!(
+        state_db.commit_staged(epoch(1)).unwrap_err().kind(),
+        ErrorKind::EpochJournalingDisabled
+    ));
+}
+
+#[test]
+fn test_contains_key() {
+    let (mut state_db, _) = init_state_db();
+    let checkpoint = state_db.checkpoint();
+
+    assert!(state_db.contains_key(storage_key(b"00")).unwrap());
+    assert!(!state_db.contains_key(storage_key(b"33")).unwrap());
+
+    // The uncommitted writes and deletes are reflected.
+    state_db.delete(storage_key(b"00"), None).unwrap();
+    state_db
+        .set_raw(storage_key(b"33"), value(b"v0"), None)
+        .unwrap();
+    assert!(!state_db.contains_key(storage_key(b"00")).unwrap());
+    assert!(state_db.contains_key(storage_key(b"33")).unwrap());
+
+    state_db.revert_to(checkpoint).unwrap();
+    assert!(state_db.contains_key(storage_key(b"00")).unwrap());
+    assert!(!state_db.contains_key(storage_key(b"33")).unwrap());
+
+    // The storages overriding `contains_key` behave the same.
+    let mut state_db = StateDb::new(InMemoryDb::new());
+    assert!(!state_db.contains_key(storage_key(b"00")).unwrap());
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v0"), None)
+        .unwrap();
+    assert!(state_db.contains_key(storage_key(b"00")).unwrap());
+}
+
+#[test]
+fn test_commit_summary() {
+    let (mut state_db, _) = init_journaled_state_db();
+
+    state_db
+        .set_raw(storage_key(b"11"), value(b"v1"), None)
+        .unwrap();
+    state_db
+        .set_raw(storage_key(b"33"), value(b"v33"), None)
+        .unwrap();
+    state_db.delete(storage_key(b"22"), None).unwrap();
+    // The keys changed back to their committed values are not counted.
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v1"), None)
+        .unwrap();
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v0"), None)
+        .unwrap();
+    state_db
+        .set_raw(storage_key(b"44"), value(b"v0"), None)
+        .unwrap();
+    state_db.delete(storage_key(b"44"), None).unwrap();
+
+    assert_eq!(
+        state_db.commit_with_summary(epoch(1), None).unwrap(),
+        CommitSummary {
+            keys_written: 2,
+            keys_deleted: 1,
+            bytes_written: 5,
+        }
+    );
     assert_eq!(
-        state_db.get_from_cache(&key(b"00")),
-        Some(value(b"v1").into())
+        state_db.commit_with_summary(epoch(2), None).unwrap(),
+        CommitSummary::default()
     );
 
-    // revert to checkpoint #0
-    // (00, v0) --> None
-    state_db.revert_to_checkpoint();
+    // Without epoch journaling, the keys changed back are counted too.
+    let (mut state_db, _) = init_state_db();
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v1"), None)
+        .unwrap();
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v0"), None)
+        .unwrap();
+    assert_eq!(
+        state_db.commit_with_summary(epoch(1), None).unwrap(),
+        CommitSummary {
+            keys_written: 1,
+            keys_deleted: 0,
+            bytes_written: 2,
+        }
+    );
+}
+
+#[test]
+fn test_write_reads() {
+    // Without a checkpoint or epoch journaling, the writes and the commit do
+    // not read the backing store.
+    let (mut state_db, storage) = init_state_db();
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v1"), None)
+        .unwrap();
+    state_db.delete(storage_key(b"11"), None).unwrap();
+    state_db.commit(epoch(1), None).unwrap();
+    assert_eq!(storage.get_num_reads(), 0);
 
-    // value is removed from state-db
-    assert_eq!(state_db.get_from_cache(&key(b"00")), None);
+    // A checkpoint reads the value of a key before its first write since the
+    // last commit, and reuses the written value afterwards.
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v2"), None)
+        .unwrap();
+    let checkpoint = state_db.checkpoint();
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v3"), None)
+        .unwrap();
+    state_db
+        .set_raw(storage_key(b"22"), value(b"v1"), None)
+        .unwrap();
+    state_db
+        .set_raw(storage_key(b"22"), value(b"v2"), None)
+        .unwrap();
+    assert_eq!(storage.get_num_reads(), 1);
+    state_db.revert_to(checkpoint).unwrap();
+    assert_eq!(
+        state_db.get_raw(storage_key(b"00")).unwrap(),
+        Some(value(b"v2"))
+    );
+    assert_eq!(
+        state_db.get_raw(storage_key(b"22")).unwrap(),
+        Some(value(b"v0"))
+    );
+    let num_reads = storage.get_num_reads();
+    state_db.commit(epoch(2), None).unwrap();
+    assert_eq!(storage.get_num_reads(), num_reads);
+
+    // Epoch journaling reads the value of a key before its first write since
+    // the last commit.
+    state_db.set_epoch_journaling(true).unwrap();
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v4"), None)
+        .unwrap();
+    assert!(matches!(
+        state_db.set_epoch_journaling(false).unwrap_err().kind(),
+        ErrorKind::UncommittedChanges
+    ));
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v5"), None)
+        .unwrap();
+    assert_eq!(storage.get_num_reads(), num_reads + 1);
+    state_db.commit(epoch(3), None).unwrap();
+    assert_eq!(storage.get_num_reads(), num_reads + 1);
+}
+
+#[test]
+fn test_dump_contract_storage() {
+    let mut state_db = StateDb::new(MockStorage::default());
+    let contract = Address::from_low_u64_be(1);
+    let other_contract = Address::from_low_u64_be(2);
+    let (address, other_address) = (contract.with_evm_space(), other_contract.with_evm_space());
+
+    // A slot written by EVM code and a slot written like `set_system`.
+    state_db
+        .set(
+            StateKey::new_storage_key(&address, b"b"),
+            &primitives::StorageValue { value: 2.into() },
+            None,
+        )
+        .unwrap();
+    state_db
+        .set(
+            StateKey::new_storage_key(&address, b"a"),
+            &U256::from(1),
+            None,
+        )
+        .unwrap();
+    // Neither the account nor the slots of other contracts are dumped.
+    state_db
+        .set_raw(StateKey::new_account_key(&address), value(b"account"), None)
+        .unwrap();
+    state_db
+        .set(
+            StateKey::new_storage_key(&other_address, b"a"),
+            &U256::from(3),
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(
+        state_db.dump_contract_storage(&contract).unwrap(),
+        vec![
+            (b"a".to_vec(), U256::from(1)),
+            (b"b".to_vec(), U256::from(2))
+        ]
+    );
+    assert!(state_db
+        .dump_contract_storage(&Address::from_low_u64_be(3))
+        .unwrap()
+        .is_empty());
+
+    let key = StateKey::new_storage_key(&address, b"c");
+    state_db.set_raw(key, value(b"\xff"), None).unwrap();
+    assert!(matches!(
+        state_db.dump_contract_storage(&contract).unwrap_err().kind(),
+        ErrorKind::Decode(decode_key, _) if *decode_key == key.into_owned()
+    ));
+}
+
+#[test]
+fn test_read_cache() {
+    let storage = init_storage();
+    let mut state_db = StateDb::with_read_cache(storage.clone(), 2);
+    let stats = |hits, misses, len| Some(ReadCacheStats { hits, misses, len });
+
+    let get = |state_db: &StateDb, key| state_db.get_raw(storage_key(key)).unwrap();
+    assert_eq!(get(&state_db, b"00"), Some(value(b"v0")));
+    assert_eq!(get(&state_db, b"00"), Some(value(b"v0")));
+    assert_eq!(storage.get_num_reads(), 1);
+    assert_eq!(state_db.read_cache_stats(), stats(1, 1, 1));
+
+    // The uncommitted writes win over the cached value, without touching the
+    // cache.
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v1"), None)
+        .unwrap();
+    assert_eq!(get(&state_db, b"00"), Some(value(b"v1")));
+    assert_eq!(state_db.read_cache_stats(), stats(1, 1, 0));
+
+    // After the commit, the written value is read into the cache again.
+    state_db.commit(epoch(1), None).unwrap();
+    assert_eq!(get(&state_db, b"00"), Some(value(b"v1")));
+    assert_eq!(get(&state_db, b"00"), Some(value(b"v1")));
+    assert_eq!(state_db.read_cache_stats(), stats(2, 2, 1));
+
+    // The missing keys are cached too, and the least recently used key is
+    // evicted first.
+    assert_eq!(get(&state_db, b"33"), None);
+    assert_eq!(get(&state_db, b"33"), None);
+    assert_eq!(get(&state_db, b"11"), Some(value(b"v0")));
+    assert_eq!(state_db.read_cache_stats(), stats(3, 4, 2));
+    let num_reads = storage.get_num_reads();
+    assert_eq!(get(&state_db, b"00"), Some(value(b"v1")));
+    assert_eq!(storage.get_num_reads(), num_reads + 1);
+
+    let state_db = StateDb::with_read_cache(init_storage(), 0);
+    assert_eq!(state_db.read_cache_stats(), None);
+}
+
+#[test]
+fn test_deferred_flush() {
+    let (mut state_db, storage) = init_state_db();
+    assert_eq!(state_db.flush_mode(), FlushMode::Immediate);
+    state_db.set_flush_mode(FlushMode::Deferred);
+
+    // The deferred commits are visible to the reads of the same `StateDb`.
+    state_db
+        .set_raw(storage_key(b"11"), value(b"v1"), None)
+        .unwrap();
+    state_db.commit(epoch(1), None).unwrap();
+    state_db.delete(storage_key(b"22"), None).unwrap();
+    state_db.commit(epoch(2), None).unwrap();
+    assert!(storage.commits().is_empty());
+    assert_eq!(
+        state_db.get_raw(storage_key(b"11")).unwrap(),
+        Some(value(b"v1"))
+    );
+    assert_eq!(state_db.get_raw(storage_key(b"22")).unwrap(), None);
+
+    // A flush commits the backing store once for the last deferred commit.
+    state_db.flush().unwrap();
+    assert_eq!(storage.commits(), vec![epoch(2)]);
+    state_db.flush().unwrap();
+    assert_eq!(storage.commits(), vec![epoch(2)]);
+
+    // The next immediate commit flushes the deferred commits with it.
+    state_db.commit(epoch(3), None).unwrap();
+    state_db.set_flush_mode(FlushMode::Immediate);
+    state_db.commit(epoch(4), None).unwrap();
+    assert_eq!(storage.commits(), vec![epoch(2), epoch(4)]);
+    state_db.flush().unwrap();
+    assert_eq!(storage.commits(), vec![epoch(2), epoch(4)]);
+}
+
+#[test]
+fn test_decode_error() {
+    let mut state_db = StateDb::new(MockStorage::default());
+    let key = SystemStorageKey::total_tokens();
+    // The RLP encoding of 1_000_000 is [0x83, 0x0f, 0x42, 0x40].
+    state_db
+        .set_raw(key.as_state_key(), vec![0x83, 0x0f, 0x42].into(), None)
+        .unwrap();
+
+    match state_db.get_total_issued_tokens().unwrap_err().kind() {
+        ErrorKind::Decode(decode_key, expected) => {
+            assert_eq!(*decode_key, key.as_state_key().into_owned());
+            assert_eq!(*expected, std::any::type_name::<U256>());
+        },
+        e => panic!("Unexpected error: {}", e),
+    }
+    assert!(matches!(
+        state_db.get_system::<U256>(&key).unwrap_err().kind(),
+        ErrorKind::Decode(..)
+    ));
+}
+
+#[test]
+fn test_revert_epoch() {
+    let (mut state_db, storage) = init_journaled_state_db();
+
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v1"), None)
+        .unwrap();
+    state_db.commit(epoch(1), None).unwrap();
+    state_db
+        .set_raw(storage_key(b"00"), value(b"v2"), None)
+        .unwrap();
+    state_db
+        .set_raw(storage_key(b"33"), value(b"v0"), None)
+        .unwrap();
+    state_db.commit(epoch(2), None).unwrap();
+
+    // Only the last epoch can be reverted.
+    assert!(matches!(
+        state_db.revert_epoch(epoch(1), epoch(12)).unwrap_err().kind(),
+        ErrorKind::NotRevertibleEpoch(epoch_id) if *epoch_id == epoch(1)
+    ));
+    // The restored values are not committed under a committed epoch id.
+    for n in [1, 2] {
+        assert!(matches!(
+            state_db.revert_epoch(epoch(2), epoch(n)).unwrap_err().kind(),
+            ErrorKind::EpochAlreadyCommitted(epoch_id) if *epoch_id == epoch(n)
+        ));
+    }
+
+    state_db.revert_epoch(epoch(2), epoch(12)).unwrap();
+    assert_eq!(
+        state_db.get_raw(storage_key(b"00")).unwrap(),
+        Some(value(b"v1"))
+    );
+    assert_eq!(state_db.get_raw(storage_key(b"33")).unwrap(), None);
+    assert_eq!(storage.get(key(b"00")).unwrap(), Some(value(b"v1")));
+    assert_eq!(storage.get(key(b"33")).unwrap(), None);
+
+    // The first journaled epoch is reverted as well, and each revert is
+    // committed under its new epoch id.
+    state_db.revert_epoch(epoch(1), epoch(11)).unwrap();
+    assert_eq!(
+        state_db.get_raw(storage_key(b"00")).unwrap(),
+        Some(value(b"v0"))
+    );
+    assert_eq!(
+        storage.commits(),
+        vec![epoch(1), epoch(2), epoch(12), epoch(11)]
+    );
+    assert!(state_db.revert_epoch(epoch(1), epoch(13)).is_err());
+
+    // An epoch can not be reverted while a checkpoint is open.
+    state_db.commit(epoch(3), None).unwrap();
+    state_db.checkpoint();
+    assert!(matches!(
+        state_db
+            .revert_epoch(epoch(3), epoch(13))
+            .unwrap_err()
+            .kind(),
+        ErrorKind::CheckpointOpen(1)
+    ));
+    state_db.discard_checkpoint(0);
+    state_db.revert_epoch(epoch(3), epoch(13)).unwrap();
+
+    // An epoch committed without epoch journaling can not be reverted, and
+    // neither can the epochs before it.
+    state_db.commit(epoch(4), None).unwrap();
+    state_db.set_epoch_journaling(false).unwrap();
+    state_db.commit(epoch(5), None).unwrap();
+    for n in [5, 4] {
+        assert!(matches!(
+            state_db
+                .revert_epoch(epoch(n), epoch(15))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::NotRevertibleEpoch(_)
+        ));
+    }
 }
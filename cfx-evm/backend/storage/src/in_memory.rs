@@ -1,4 +1,4 @@
-use crate::StorageTrait;
+use crate::{RawEntryIter, StorageTrait};
 use std::{collections::HashMap, sync::RwLock};
 
 type Bytes = Vec<u8>;
@@ -20,11 +20,33 @@ impl StorageTrait for InMemoryDb {
         Ok(self.inner.read().unwrap().get(&key).cloned())
     }
 
+    fn contains_key(&self, key: Self::StorageKey) -> crate::Result<bool> {
+        Ok(self.inner.read().unwrap().contains_key(&key))
+    }
+
+    fn iter_prefix(&self, prefix: &[u8]) -> crate::Result<RawEntryIter> {
+        let mut entries: Vec<_> = self
+            .inner
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(Box::new(entries.into_iter()))
+    }
+
     fn set(&mut self, access_key: Self::StorageKey, value: Box<[u8]>) -> crate::Result<()> {
         self.inner.get_mut().unwrap().insert(access_key, value);
         Ok(())
     }
 
+    fn set_batch(&mut self, entries: Vec<(Self::StorageKey, Box<[u8]>)>) -> crate::Result<()> {
+        self.inner.get_mut().unwrap().extend(entries);
+        Ok(())
+    }
+
     fn delete(&mut self, access_key: Self::StorageKey) -> crate::Result<()> {
         self.inner.get_mut().unwrap().remove(&access_key);
         Ok(())
@@ -5,8 +5,10 @@ use std::marker::PhantomData;
 extern crate error_chain;
 
 mod in_memory;
+mod proof;
 
 pub use in_memory::InMemoryDb;
+pub use proof::StateProof;
 
 error_chain! {
     links {
@@ -16,15 +18,50 @@ error_chain! {
     }
 
     errors {
+        ProofUnsupported {
+            description("state proof is not supported by the storage")
+            display("state proof is not supported by the storage")
+        }
+        IterationUnsupported {
+            description("prefix iteration is not supported by the storage")
+            display("prefix iteration is not supported by the storage")
+        }
     }
 }
 
+/// The iterator of the encoded keys and the values.
+pub type RawEntryIter<'a> = Box<dyn Iterator<Item = (Vec<u8>, Box<[u8]>)> + 'a>;
+
 pub trait StorageTrait {
     type StorageKey;
 
     // Actions.
     fn get(&self, key: Self::StorageKey) -> Result<Option<Box<[u8]>>>;
+    /// Check whether the key exists. The storages should override it if they
+    /// can check the existence without copying the value.
+    fn contains_key(&self, key: Self::StorageKey) -> Result<bool> {
+        Ok(self.get(key)?.is_some())
+    }
+    /// Get the value together with a merkle proof of it. The storages which
+    /// cannot produce proofs return `ErrorKind::ProofUnsupported`.
+    fn get_with_proof(&self, _key: Self::StorageKey) -> Result<(Option<Box<[u8]>>, StateProof)> {
+        bail!(ErrorKind::ProofUnsupported)
+    }
+    /// Iterate the entries whose encoded keys start with `prefix`, in the
+    /// lexicographic order of the encoded keys. The storages which cannot
+    /// enumerate keys return `ErrorKind::IterationUnsupported`.
+    fn iter_prefix(&self, _prefix: &[u8]) -> Result<RawEntryIter> {
+        bail!(ErrorKind::IterationUnsupported)
+    }
     fn set(&mut self, access_key: Self::StorageKey, value: Box<[u8]>) -> Result<()>;
+    /// Set multiple entries at once. The storages should override it if they
+    /// can apply the entries in a single write.
+    fn set_batch(&mut self, entries: Vec<(Self::StorageKey, Box<[u8]>)>) -> Result<()> {
+        for (access_key, value) in entries {
+            self.set(access_key, value)?;
+        }
+        Ok(())
+    }
     fn delete(&mut self, access_key: Self::StorageKey) -> Result<()>;
     fn commit(&mut self, epoch: EpochId) -> Result<()>;
 }
@@ -47,10 +84,31 @@ where
         )
     }
 
+    fn contains_key(&self, key: Self::StorageKey) -> Result<bool> {
+        self.inner.contains_key(key.into())
+    }
+
+    fn get_with_proof(&self, key: Self::StorageKey) -> Result<(Option<Box<[u8]>>, StateProof)> {
+        self.inner.get_with_proof(key.into())
+    }
+
+    fn iter_prefix(&self, prefix: &[u8]) -> Result<RawEntryIter> {
+        self.inner.iter_prefix(prefix)
+    }
+
     fn set(&mut self, access_key: Self::StorageKey, value: Box<[u8]>) -> Result<()> {
         self.inner.set(access_key.into(), value)
     }
 
+    fn set_batch(&mut self, entries: Vec<(Self::StorageKey, Box<[u8]>)>) -> Result<()> {
+        self.inner.set_batch(
+            entries
+                .into_iter()
+                .map(|(access_key, value)| (access_key.into(), value))
+                .collect(),
+        )
+    }
+
     fn delete(&mut self, access_key: Self::StorageKey) -> Result<()> {
         self.inner.delete(access_key.into())
     }
@@ -0,0 +1,19 @@
+/// A merkle proof of a state entry. The encoding of the proof is specified by
+/// the storage which produces it, so it is kept as opaque bytes and can be
+/// passed across the cross-space boundary directly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateProof(Vec<u8>);
+
+impl StateProof {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        StateProof(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2019 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use cfx_evm::{
+    new_machine_with_builtin, CrossVMAction, CrossVMParams, CrossVMParamsBuilder, Env, State,
+    StateDb, TXExecutor, VmFactory,
+};
+use cfx_storage::InMemoryDb;
+use cfx_types::{Address, U256};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const CALLS: usize = 16;
+
+fn cross_vm_params(action: CrossVMAction, receiver: Address) -> CrossVMParams {
+    CrossVMParamsBuilder::new(action, receiver, U256::from(1_000_000))
+        .caller_info("0x1::bench::Bench".to_string())
+        .raw_calldata(vec![0x12, 0x34, 0x56, 0x78])
+        .build()
+        .unwrap()
+}
+
+fn code_cache_benchmark(c: &mut Criterion) {
+    let machine = new_machine_with_builtin(Default::default(), VmFactory::new(1024 * 32));
+    let env = Env::default();
+    let spec = machine.spec(env.number);
+    let mut state = State::new(StateDb::new(InMemoryDb::new())).unwrap();
+
+    // Runtime code echoing the calldata:
+    // CALLDATASIZE PUSH1 0x00 PUSH1 0x00 CALLDATACOPY CALLDATASIZE PUSH1 0x00
+    // RETURN
+    let runtime_code = vec![0x36, 0x60, 0x00, 0x60, 0x00, 0x37, 0x36, 0x60, 0x00, 0xf3];
+    // PUSH10 <runtime_code> PUSH1 0x00 MSTORE PUSH1 0x0a PUSH1 0x16 RETURN
+    let mut init_code = vec![0x69];
+    init_code.extend_from_slice(&runtime_code);
+    init_code.extend_from_slice(&[0x60, 0x00, 0x52, 0x60, 0x0a, 0x60, 0x16, 0xf3]);
+    let contract = TXExecutor::new(&mut state, &env, &machine, &spec)
+        .cross_vm_call(cross_vm_params(
+            CrossVMAction::Create(init_code),
+            Address::zero(),
+        ))
+        .unwrap()
+        .created_address
+        .unwrap();
+
+    // Every call is made by a new executor, so the code is looked up in the
+    // state each time.
+    c.bench_function("Cross-space calls with cold code cache", |b| {
+        b.iter(|| {
+            for _ in 0..CALLS {
+                TXExecutor::new(&mut state, &env, &machine, &spec)
+                    .cross_vm_call(cross_vm_params(CrossVMAction::Call, contract))
+                    .unwrap();
+            }
+        });
+    });
+
+    // All the calls are made by the same executor, so the code is looked up
+    // in the state only once.
+    c.bench_function("Cross-space calls with warm code cache", |b| {
+        b.iter(|| {
+            let mut executor = TXExecutor::new(&mut state, &env, &machine, &spec);
+            for _ in 0..CALLS {
+                executor
+                    .cross_vm_call(cross_vm_params(CrossVMAction::Call, contract))
+                    .unwrap();
+            }
+        });
+    });
+}
+
+criterion_group!(benches, code_cache_benchmark);
+criterion_main!(benches);
@@ -455,6 +455,14 @@ impl<'a, 'b> ContextTrait for Context<'a, 'b> {
         self.local_part.depth
     }
 
+    fn count_step(&mut self) -> vm::Result<()> {
+        if self.callstack.count_step() {
+            Ok(())
+        } else {
+            Err(vm::Error::StepLimitReached)
+        }
+    }
+
     fn trace_next_instruction(&mut self, _pc: usize, _instruction: u8, _current_gas: U256) -> bool {
         // TODO
         false
@@ -20,6 +20,9 @@ pub struct FrameStack<'a> {
     tx_substate: Substate,
     observer: Observer,
     base_gas_required: u64,
+    /// The limit of the depth of a frame plus the number of cross-space calls
+    /// leading to this frame stack.
+    max_depth: usize,
 }
 
 pub struct FrameStackOutput {
@@ -50,6 +53,7 @@ impl<'a> FrameStack<'a> {
         top_substate: Substate,
         observer: Observer,
         base_gas_required: u64,
+        max_depth: usize,
     ) -> Self {
         FrameStack {
             state,
@@ -58,13 +62,35 @@ impl<'a> FrameStack<'a> {
             tx_substate: top_substate,
             observer,
             base_gas_required,
+            max_depth,
         }
     }
 
+    /// Set the number of cross-space calls that leads to this frame stack.
+    pub fn with_cross_space_depth(mut self, cross_space_depth: usize) -> Self {
+        self.callstack = FrameStackInfo::with_cross_space_depth(cross_space_depth);
+        self
+    }
+
+    /// Limit the number of instructions executed by all the frames,
+    /// independent of the gas.
+    pub fn with_max_steps(mut self, max_steps: Option<u64>) -> Self {
+        self.callstack.set_max_steps(max_steps);
+        self
+    }
+
+    /// Whether a frame at `depth` of this frame stack exceeds the depth limit.
+    fn exceeds_max_depth(&self, depth: usize) -> bool {
+        self.callstack.cross_space_depth() + depth > self.max_depth
+    }
+
     /// Execute the top call-create executive. This function handles resume
     /// traps and sub-level tracing. The caller is expected to handle
     /// current-level tracing.
     pub fn exec(mut self, top_frame: CallCreateFrame<'a>) -> DbResult<FrameStackOutput> {
+        if self.exceeds_max_depth(0) {
+            return Ok(self.process_return(Err(vm::Error::OutOfDepth)));
+        }
         let last_res = top_frame.exec(
             self.state,
             &mut self.callstack,
@@ -112,13 +138,24 @@ impl<'a> FrameStack<'a> {
                 },
                 TrapResult::SubCallCreate(trap_err) => {
                     let (callee, caller) = CallCreateFrame::from_trap_error(trap_err);
-                    self.frame_stack.push(caller);
+                    // The callee is at the depth of the number of its
+                    // ancestors.
+                    if self.exceeds_max_depth(self.frame_stack.len() + 1) {
+                        caller.resume(
+                            Err(vm::Error::OutOfDepth),
+                            self.state,
+                            &mut self.callstack,
+                            &mut *self.observer.as_vm_observe(),
+                        )?
+                    } else {
+                        self.frame_stack.push(caller);
 
-                    callee.exec(
-                        self.state,
-                        &mut self.callstack,
-                        &mut *self.observer.as_vm_observe(),
-                    )?
+                        callee.exec(
+                            self.state,
+                            &mut self.callstack,
+                            &mut *self.observer.as_vm_observe(),
+                        )?
+                    }
                 },
             }
         }
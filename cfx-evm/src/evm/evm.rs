@@ -37,6 +37,13 @@ pub struct FinalizationResult {
     pub return_data: ReturnData,
 }
 
+impl FinalizationResult {
+    /// Whether the execution succeeded, i.e. its state changes are applied.
+    pub fn is_success(&self) -> bool {
+        self.apply_state
+    }
+}
+
 /// Types that can be "finalized" using an EVM.
 ///
 /// In practice, this is just used to define an inherent impl on
@@ -51,7 +51,7 @@ use crate::{
     },
 };
 use bit_set::BitSet;
-use cfx_types::{Address, BigEndianHash, Space, H256, U256, U512};
+use cfx_types::{Address, AddressSpaceUtil, BigEndianHash, Space, H256, U256, U512};
 use std::{cmp, convert::TryFrom, marker::PhantomData, mem, sync::Arc};
 
 const GASOMETER_PROOF: &str = "If gasometer is None, Err is immediately returned in step; this function is only called by step; qed";
@@ -389,6 +389,9 @@ impl<Cost: CostType> Interpreter<Cost> {
         let result = match self.resume_result.take() {
             Some(result) => result,
             None => {
+                if let Err(e) = context.count_step() {
+                    return InterpreterResult::Done(Err(e));
+                }
                 let opcode = self.reader.code[self.reader.position];
                 let instruction = Instruction::from_u8(opcode);
                 self.reader.position += 1;
@@ -1006,6 +1009,7 @@ impl<Cost: CostType> Interpreter<Cost> {
                 let mut key = vec![0; 32];
                 self.stack.pop_back().to_big_endian(key.as_mut());
                 let word = context.storage_at(&key)?;
+                tracer.trace_storage_read(&self.params.address.with_space(self.params.space), &key);
                 self.stack.push(word);
             },
             instructions::SSTORE => {
@@ -0,0 +1,70 @@
+use cfx_types::{Address, H256, U256};
+use solidity_abi::ABIListWriter;
+
+/// A typed argument of a cross-space call. A list of them is ABI-encoded as
+/// the arguments of the called EVM function, so the Move side does not encode
+/// each argument by itself (see `CrossVMParams::typed_args`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum CrossVMArg {
+    Uint256(U256),
+    Address(Address),
+    Bool(bool),
+    Bytes32(H256),
+    Bytes(Vec<u8>),
+    String(String),
+    Uint256Array(Vec<U256>),
+    AddressArray(Vec<Address>),
+    BytesArray(Vec<Vec<u8>>),
+    StringArray(Vec<String>),
+}
+
+impl CrossVMArg {
+    /// The solidity type of the argument in the function signature.
+    pub fn solidity_type(&self) -> &'static str {
+        match self {
+            CrossVMArg::Uint256(_) => "uint256",
+            CrossVMArg::Address(_) => "address",
+            CrossVMArg::Bool(_) => "bool",
+            CrossVMArg::Bytes32(_) => "bytes32",
+            CrossVMArg::Bytes(_) => "bytes",
+            CrossVMArg::String(_) => "string",
+            CrossVMArg::Uint256Array(_) => "uint256[]",
+            CrossVMArg::AddressArray(_) => "address[]",
+            CrossVMArg::BytesArray(_) => "bytes[]",
+            CrossVMArg::StringArray(_) => "string[]",
+        }
+    }
+
+    fn write_down(&self, writer: &mut ABIListWriter) {
+        match self {
+            CrossVMArg::Uint256(value) => writer.write_down(value),
+            CrossVMArg::Address(value) => writer.write_down(value),
+            CrossVMArg::Bool(value) => writer.write_down(value),
+            CrossVMArg::Bytes32(value) => writer.write_down(value),
+            CrossVMArg::Bytes(value) => writer.write_down(value),
+            CrossVMArg::String(value) => writer.write_down(value),
+            CrossVMArg::Uint256Array(value) => writer.write_down(value),
+            CrossVMArg::AddressArray(value) => writer.write_down(value),
+            CrossVMArg::BytesArray(value) => writer.write_down(value),
+            CrossVMArg::StringArray(value) => writer.write_down(value),
+        }
+    }
+}
+
+/// The signature of `function_name` taking the arguments, e.g.
+/// `transfer(address,uint256)`.
+pub fn cross_vm_function_signature(function_name: &str, args: &[CrossVMArg]) -> String {
+    let types: Vec<&str> = args.iter().map(CrossVMArg::solidity_type).collect();
+    format!("{}({})", function_name, types.join(","))
+}
+
+/// Encodes the arguments as an ABI tuple, i.e. the calldata without the
+/// function selector.
+pub fn encode_cross_vm_args(args: &[CrossVMArg]) -> Vec<u8> {
+    // Every supported type takes exactly one 32-byte head slot.
+    let mut writer = ABIListWriter::with_heads_length(args.len() * 32);
+    for arg in args {
+        arg.write_down(&mut writer);
+    }
+    writer.into_linked_bytes().to_vec()
+}
@@ -98,6 +98,7 @@ impl<'a> TXExecutor<'a> {
             sender_pay_executed
         );
         self.state.revert_to_checkpoint();
+        self.code_cache.clear();
 
         let mut executed = sender_pay_executed;
 
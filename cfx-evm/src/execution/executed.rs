@@ -2,12 +2,15 @@
 // Conflux is free software and distributed under GNU General Public License.
 // See http://www.gnu.org/licenses/
 
-use crate::{bytes::Bytes, vm};
-use cfx_types::{AddressWithSpace, U256, U512};
-use primitives::LogEntry;
-use solidity_abi::{ABIDecodable, ABIDecodeError};
+use crate::{bytes::Bytes, evm::FinalizationResult, vm};
+use cfx_types::{AddressWithSpace, H256, U256, U512};
+use primitives::{Action, LogEntry};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use solidity_abi::{ABIDecodable, ABIDecodeError, ABIEncodable};
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Executed {
     /// Gas used during execution of transaction.
     pub gas_used: U256,
@@ -28,15 +31,25 @@ pub struct Executed {
     ///
     /// B creation ends first, and it will be the first element of the vector.
     pub contracts_created: Vec<AddressWithSpace>,
+    /// Whether the top-level action of the transaction is a contract
+    /// creation.
+    pub is_create: bool,
     /// Transaction output.
     pub output: Bytes,
     /// The trace of this transaction.
     pub trace: Vec<ExecTrace>,
     /// Only for the virtual call, an accurate gas estimation for gas usage,
     pub estimated_gas_limit: Option<U256>,
+    /// The hash of the transaction. It is `None` if the transaction does not
+    /// provide its hash.
+    pub tx_hash: Option<H256>,
+    /// The remaining balances of the suicided contracts, which are burnt when
+    /// the contracts are removed at the end of the transaction.
+    pub tokens_burned: U256,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ToRepackError {
     /// Returned when transaction nonce does not match state nonce.
     InvalidNonce {
@@ -48,17 +61,37 @@ pub enum ToRepackError {
 
     /// Returned when a non-sponsored transaction's sender does not exist yet.
     SenderDoesNotExist,
+
+    /// Returned when the transaction gas limit exceeds the gas remaining in
+    /// the block.
+    BlockGasLimitExceeded {
+        /// Gas remaining in the block.
+        limit: U256,
+        /// Transaction gas limit.
+        got: U256,
+    },
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TxDropError {
     /// The account nonce in world-state is larger than tx nonce
     OldNonce(U256, U256),
     ///
     NotEnoughBaseGas { expected: u64, actual: u64 },
+    /// The max fee per gas of an EIP-1559 transaction is lower than the block
+    /// base fee.
+    FeeCapTooLow { base_fee: U256, max_fee: U256 },
+    /// The init code of a contract creation is larger than
+    /// `Spec::init_code_data_limit`.
+    InitCodeTooLarge { limit: usize, actual: usize },
+    /// The calldata of a cross-space call is larger than
+    /// `Spec::max_cross_space_calldata_size`.
+    CrossSpaceCalldataTooLarge { limit: usize, actual: usize },
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ExecutionError {
     /// Returned when cost of transaction (value + gas_price * gas) exceeds
     /// current sender balance.
@@ -70,15 +103,66 @@ pub enum ExecutionError {
         /// Actual gas cost. This should be min(gas_fee, balance).
         actual_gas_cost: U256,
     },
-    VmError(vm::Error),
+    /// The transaction ran out of gas. Unlike the other VM errors, it may
+    /// succeed if it is resent with a larger gas limit.
+    OutOfGas {
+        /// The gas limit of the transaction.
+        gas_limit: U256,
+    },
+    VmError(
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "serialize_vm_error",
+                deserialize_with = "deserialize_vm_error"
+            )
+        )]
+        vm::Error,
+    ),
+}
+
+/// `vm::Error` contains the database error, so it is serialized as its
+/// display string.
+#[cfg(feature = "serde")]
+fn serialize_vm_error<S: Serializer>(error: &vm::Error, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(error)
+}
+
+/// The errors without fields are restored from their display strings as they
+/// are. The other errors are restored as `vm::Error::Message`, which is
+/// serialized to the same string again.
+#[cfg(feature = "serde")]
+fn deserialize_vm_error<'de, D: Deserializer<'de>>(deserializer: D) -> Result<vm::Error, D::Error> {
+    use vm::Error::*;
+    let message = String::deserialize(deserializer)?;
+    let error = [
+        OutOfGas,
+        InvalidSubEntry,
+        ExceedStorageLimit,
+        MutableCallInStaticContext,
+        OutOfBounds,
+        Reverted,
+        CrossSpaceReentrancyLimit,
+        OutOfDepth,
+        StepLimitReached,
+        CrossSpaceDelegateCallWithValue,
+        MissingCrossSpaceCalldata,
+    ]
+    .into_iter()
+    .find(|error| error.to_string() == message);
+    Ok(error.unwrap_or(Message(message)))
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ExecutionOutcome {
     NotExecutedDrop(TxDropError),
     NotExecutedToReconsiderPacking(ToRepackError),
     ExecutionErrorBumpNonce(ExecutionError, Executed),
     Finished(Executed),
+    /// The transaction with this hash has been executed successfully before.
+    /// It is only reported by an executor with a `ReceiptCache`.
+    AlreadyExecuted(H256),
 }
 
 impl ExecutionOutcome {
@@ -88,9 +172,74 @@ impl ExecutionOutcome {
             _ => None,
         }
     }
+
+    /// Whether the transaction is executed successfully. Unlike
+    /// `successfully_executed`, it does not consume the outcome.
+    pub fn succeeded(&self) -> bool {
+        matches!(self, ExecutionOutcome::Finished(_))
+    }
+
+    /// A stable numeric code of the outcome for the callers which can not
+    /// access the Rust type, e.g., the Move adapter. The codes must not be
+    /// changed once released.
+    ///
+    /// | Code | Outcome                                          |
+    /// |------|--------------------------------------------------|
+    /// | 0    | `Finished`                                       |
+    /// | 1    | `TxDropError::OldNonce`                          |
+    /// | 2    | `ToRepackError::InvalidNonce`                    |
+    /// | 3    | `ToRepackError::SenderDoesNotExist`              |
+    /// | 4    | `TxDropError::NotEnoughBaseGas`                  |
+    /// | 5    | `ExecutionError::NotEnoughCash`                  |
+    /// | 6    | `ExecutionError::VmError`, except for `Reverted` |
+    /// | 7    | `ExecutionError::VmError(vm::Error::Reverted)`   |
+    /// | 8    | `TxDropError::FeeCapTooLow`                      |
+    /// | 9    | `TxDropError::InitCodeTooLarge`                  |
+    /// | 10   | `TxDropError::CrossSpaceCalldataTooLarge`        |
+    /// | 11   | `ToRepackError::BlockGasLimitExceeded`           |
+    /// | 12   | `ExecutionError::OutOfGas`                       |
+    /// | 13   | `AlreadyExecuted`                                |
+    pub fn error_code(&self) -> u32 {
+        use self::{ExecutionError::*, ToRepackError::*, TxDropError::*};
+        match self {
+            ExecutionOutcome::Finished(_) => 0,
+            ExecutionOutcome::NotExecutedDrop(OldNonce(..)) => 1,
+            ExecutionOutcome::NotExecutedToReconsiderPacking(InvalidNonce { .. }) => 2,
+            ExecutionOutcome::NotExecutedToReconsiderPacking(SenderDoesNotExist) => 3,
+            ExecutionOutcome::NotExecutedDrop(NotEnoughBaseGas { .. }) => 4,
+            ExecutionOutcome::ExecutionErrorBumpNonce(NotEnoughCash { .. }, _) => 5,
+            ExecutionOutcome::ExecutionErrorBumpNonce(VmError(vm::Error::Reverted), _) => 7,
+            ExecutionOutcome::ExecutionErrorBumpNonce(VmError(_), _) => 6,
+            ExecutionOutcome::NotExecutedDrop(FeeCapTooLow { .. }) => 8,
+            ExecutionOutcome::NotExecutedDrop(InitCodeTooLarge { .. }) => 9,
+            ExecutionOutcome::NotExecutedDrop(CrossSpaceCalldataTooLarge { .. }) => 10,
+            ExecutionOutcome::NotExecutedToReconsiderPacking(BlockGasLimitExceeded { .. }) => 11,
+            ExecutionOutcome::ExecutionErrorBumpNonce(OutOfGas { .. }, _) => 12,
+            ExecutionOutcome::AlreadyExecuted(_) => 13,
+        }
+    }
 }
 
 impl Executed {
+    /// The address of the contract created by the top-level action of the
+    /// transaction. The contracts created internally, e.g., by a call or by
+    /// the constructor, are not considered. Since the top-level creation
+    /// ends last, it is the last entry of `contracts_created`.
+    pub fn created_address(&self) -> Option<AddressWithSpace> {
+        if self.is_create {
+            self.contracts_created.last().cloned()
+        } else {
+            None
+        }
+    }
+
+    /// The gas limit recommended for the transaction, like the result of
+    /// `eth_estimateGas`. It is `estimated_gas_limit` if the estimation is
+    /// requested, otherwise `gas_used`.
+    pub fn recommended_gas_limit(&self) -> U256 {
+        self.estimated_gas_limit.unwrap_or(self.gas_used)
+    }
+
     pub fn not_enough_balance_fee_charged(
         tx: &impl TransactionInfo,
         fee: &U256,
@@ -108,9 +257,12 @@ impl Executed {
             fee: fee.clone(),
             logs: vec![],
             contracts_created: vec![],
+            is_create: *tx.action() == Action::Create,
             output: Default::default(),
             trace,
             estimated_gas_limit: None,
+            tx_hash: tx.hash(),
+            tokens_burned: U256::zero(),
         }
     }
 
@@ -125,16 +277,78 @@ impl Executed {
             fee: tx.gas().saturating_mul(*tx.gas_price()),
             logs: vec![],
             contracts_created: vec![],
+            is_create: *tx.action() == Action::Create,
 
             output: Default::default(),
             trace,
             estimated_gas_limit: None,
+            tx_hash: tx.hash(),
+            tokens_burned: U256::zero(),
+        }
+    }
+}
+
+/// The function selector of `MoveAbort(bytes32,string,uint64)`, which
+/// prefixes the revert data of a call into the Move VM aborted on the Move
+/// side.
+pub const MOVE_ABORT_SIG: [u8; 4] = [89, 161, 110, 5];
+
+/// The abort code and location of a Move abort returned to an EVM caller.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MoveAbortInfo {
+    /// The address the aborted module is published at.
+    pub address: H256,
+    /// The name of the aborted module.
+    pub module: String,
+    pub code: u64,
+}
+
+pub fn move_abort_encode(info: &MoveAbortInfo) -> Vec<u8> {
+    let data = (info.address, info.module.clone(), info.code).abi_encode();
+    [&MOVE_ABORT_SIG[..], &data].concat()
+}
+
+/// Decode the revert data of a Move abort, or return `None` if the data is
+/// not in the `MoveAbort(bytes32,string,uint64)` format.
+pub fn move_abort_decode(output: &[u8]) -> Option<MoveAbortInfo> {
+    if output.len() < 4 || output[..4] != MOVE_ABORT_SIG {
+        return None;
+    }
+    let (address, module, code) = <(H256, String, u64)>::abi_decode(&output[4..]).ok()?;
+    Some(MoveAbortInfo {
+        address,
+        module,
+        code,
+    })
+}
+
+impl FinalizationResult {
+    /// The decoded revert reason if the execution is reverted. It is defined
+    /// next to `revert_reason_decode`, so `evm` does not depend on
+    /// `execution`.
+    pub fn decoded_error(&self) -> Option<String> {
+        if self.is_success() {
+            None
+        } else {
+            Some(revert_reason_decode(&self.return_data))
         }
     }
 }
 
 pub fn revert_reason_decode(output: &[u8]) -> String {
     const MAX_LENGTH: usize = 50;
+    revert_reason_decode_with_limit(output, MAX_LENGTH)
+}
+
+/// Decode the revert reason, and truncate it to at most `max_len` bytes if it
+/// is not shorter than `max_len`. The truncation never splits a character.
+pub fn revert_reason_decode_with_limit(output: &[u8], max_len: usize) -> String {
+    if let Some(info) = move_abort_decode(output) {
+        return format!(
+            "Move abort {} in {:?}::{}",
+            info.code, info.address, info.module
+        );
+    }
     let decode_result = if output.len() < 4 {
         Err(ABIDecodeError("Uncompleted Signature"))
     } else {
@@ -147,10 +361,15 @@ pub fn revert_reason_decode(output: &[u8]) -> String {
     };
     match decode_result {
         Ok(str) => {
-            if str.len() < MAX_LENGTH {
+            if str.len() < max_len {
                 str
             } else {
-                format!("{}...", str[..MAX_LENGTH].to_string())
+                // Index 0 is always a char boundary.
+                let end = (0..=max_len)
+                    .rev()
+                    .find(|i| str.is_char_boundary(*i))
+                    .unwrap();
+                format!("{}...", &str[..end])
             }
         },
         Err(_) => format!("0x{}", hex::encode(output)),
@@ -173,3 +392,131 @@ fn test_decode_result() {
         revert_reason_decode(&input_hex.from_hex().unwrap())
     );
 }
+
+#[test]
+fn test_decode_result_with_limit() {
+    use solidity_abi::ABIEncodable;
+
+    let encode =
+        |reason: &str| [&[8, 195, 121, 160][..], &reason.to_string().abi_encode()].concat();
+    let reason = format!("{}\u{1F600}tail", "a".repeat(48));
+    assert_eq!(
+        format!("{}...", "a".repeat(48)),
+        revert_reason_decode_with_limit(&encode(&reason), 50)
+    );
+    assert_eq!(
+        format!("{}\u{1F600}...", "a".repeat(48)),
+        revert_reason_decode_with_limit(&encode(&reason), 52)
+    );
+    assert_eq!(
+        reason,
+        revert_reason_decode_with_limit(&encode(&reason), 100)
+    );
+}
+
+#[test]
+fn test_decode_result_truncation() {
+    use solidity_abi::ABIEncodable;
+
+    // The 50th byte is inside the 3-byte character.
+    let reason = format!("{}\u{4E2D}", "a".repeat(49));
+    let output = [&[8, 195, 121, 160][..], &reason.abi_encode()].concat();
+    assert_eq!(
+        format!("{}...", "a".repeat(49)),
+        revert_reason_decode(&output)
+    );
+}
+
+#[test]
+fn test_move_abort_decode() {
+    assert_eq!(
+        &MOVE_ABORT_SIG[..],
+        &keccak_hash::keccak("MoveAbort(bytes32,string,uint64)")[..4]
+    );
+
+    let info = MoveAbortInfo {
+        address: H256::from_low_u64_be(1),
+        module: "coin".to_string(),
+        code: 65542,
+    };
+    let output = move_abort_encode(&info);
+    assert_eq!(move_abort_decode(&output), Some(info));
+    assert_eq!(
+        revert_reason_decode(&output),
+        format!("Move abort 65542 in {:?}::coin", H256::from_low_u64_be(1))
+    );
+
+    let reason = [&[8, 195, 121, 160][..], &"coin".to_string().abi_encode()].concat();
+    assert_eq!(move_abort_decode(&reason), None);
+}
+
+#[test]
+fn test_error_code() {
+    let executed = || Executed {
+        gas_used: U256::zero(),
+        fee: U256::zero(),
+        gas_charged: U256::zero(),
+        logs: vec![],
+        contracts_created: vec![],
+        is_create: false,
+        output: Default::default(),
+        trace: vec![],
+        estimated_gas_limit: None,
+        tx_hash: None,
+        tokens_burned: U256::zero(),
+    };
+    let outcomes = vec![
+        ExecutionOutcome::Finished(executed()),
+        ExecutionOutcome::NotExecutedDrop(TxDropError::OldNonce(1.into(), 0.into())),
+        ExecutionOutcome::NotExecutedToReconsiderPacking(ToRepackError::InvalidNonce {
+            expected: 0.into(),
+            got: 1.into(),
+        }),
+        ExecutionOutcome::NotExecutedToReconsiderPacking(ToRepackError::SenderDoesNotExist),
+        ExecutionOutcome::NotExecutedDrop(TxDropError::NotEnoughBaseGas {
+            expected: 21000,
+            actual: 0,
+        }),
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::NotEnoughCash {
+                required: 1.into(),
+                got: 0.into(),
+                actual_gas_cost: 0.into(),
+            },
+            executed(),
+        ),
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::VmError(vm::Error::BadJumpDestination { destination: 0 }),
+            executed(),
+        ),
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::VmError(vm::Error::Reverted),
+            executed(),
+        ),
+        ExecutionOutcome::NotExecutedDrop(TxDropError::FeeCapTooLow {
+            base_fee: 2.into(),
+            max_fee: 1.into(),
+        }),
+        ExecutionOutcome::NotExecutedDrop(TxDropError::InitCodeTooLarge {
+            limit: 49152,
+            actual: 49153,
+        }),
+        ExecutionOutcome::NotExecutedDrop(TxDropError::CrossSpaceCalldataTooLarge {
+            limit: 131072,
+            actual: 131073,
+        }),
+        ExecutionOutcome::NotExecutedToReconsiderPacking(ToRepackError::BlockGasLimitExceeded {
+            limit: 21000.into(),
+            got: 21001.into(),
+        }),
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::OutOfGas {
+                gas_limit: 21000.into(),
+            },
+            executed(),
+        ),
+        ExecutionOutcome::AlreadyExecuted(H256::zero()),
+    ];
+    let codes: Vec<u32> = outcomes.iter().map(ExecutionOutcome::error_code).collect();
+    assert_eq!(codes, (0..14).collect::<Vec<u32>>());
+}
@@ -1,25 +1,32 @@
 use super::{
+    cross_vm_args::{cross_vm_function_signature, encode_cross_vm_args, CrossVMArg},
     executed::{Executed, ExecutionError, ExecutionOutcome, ToRepackError, TxDropError},
+    receipt_cache::ReceiptCache,
     transaction_info::TransactionInfo,
-    TransactOptions,
+    EnvOverride, TraceLevel, TransactCheckSettings, TransactOptions,
 };
 use crate::{
     call_create_frame::{contract_address, CallCreateFrame, FrameStack, FrameStackOutput},
     evm::FinalizationResult,
+    internal_contract::{CrossVMCallEvent, SolidityEventTrait},
     machine::Machine,
     observer::{AddressPocket, MultiObservers, StateTracer},
     state::{cleanup_mode, Substate},
-    vm::{self, ActionParams, ActionValue, CallType, CreateContractAddress, CreateType, Env, Spec},
+    vm::{
+        self, ActionParams, ActionValue, CallType, CreateContractAddress, CreateType, Env,
+        RefundCap, Spec,
+    },
     vm_factory::VmFactory,
 };
 use cfx_parameters::internal_contract_addresses::CROSS_SPACE_CONTRACT_ADDRESS;
-use cfx_state::StateTrait;
+use cfx_state::{CleanupMode, StateTrait};
 use cfx_statedb::Result as DbResult;
-use cfx_types::{Address, AddressSpaceUtil, AddressWithSpace, Space, U256, U512};
-use primitives::transaction::Action;
-use solidity_abi::ABIEncodable;
+use cfx_types::{Address, AddressSpaceUtil, AddressWithSpace, Space, H256, U256, U512};
+use parking_lot::Mutex;
+use primitives::{transaction::Action, CrossSpaceReceipt, LogEntry};
+use solidity_abi::{abi_decode_values, ABIDecodeError, ABIEncodable, ABIValue};
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     convert::{TryFrom, TryInto},
     sync::Arc,
 };
@@ -31,10 +38,30 @@ pub struct TXExecutor<'a> {
     machine: &'a Machine,
     factory: VmFactory,
     pub(super) spec: &'a Spec,
+    /// The code and the code hash of the accounts called by this executor.
+    /// An entry is removed once the contract is created or removed, or the
+    /// state is reverted to a checkpoint.
+    pub(super) code_cache: HashMap<AddressWithSpace, (Option<Arc<Vec<u8>>>, Option<H256>)>,
+    /// The transactions executed successfully before, shared by the
+    /// executors of a node.
+    receipt_cache: Option<Arc<Mutex<ReceiptCache>>>,
+    /// The number of EVM-Move transitions on the call chain leading to this
+    /// executor. It is 0 for the executor of a Move transaction, and set by
+    /// the Move VM for the executor of a Move function called from EVM.
+    cross_space_depth: usize,
+    /// The callers each sponsor has authorized to pay the gas of their
+    /// cross-space calls, keyed by the sponsor. A caller is identified by
+    /// `CrossVMParams::mapped_sender`.
+    sponsored_callers: HashMap<Address, HashSet<Address>>,
 }
 
-pub fn gas_required_for(is_create: bool, data: &[u8], spec: &Spec) -> u64 {
-    data.iter().fold(
+pub fn gas_required_for(
+    is_create: bool,
+    data: &[u8],
+    access_list: &[(Address, Vec<H256>)],
+    spec: &Spec,
+) -> u64 {
+    let data_gas = data.iter().fold(
         (if is_create {
             spec.tx_create_gas
         } else {
@@ -46,6 +73,55 @@ pub fn gas_required_for(is_create: bool, data: &[u8], spec: &Spec) -> u64 {
                 _ => spec.tx_data_non_zero_gas,
             }) as u64
         },
+    );
+    access_list.iter().fold(data_gas, |g, (_, keys)| {
+        g + spec.access_list_address_cost as u64
+            + keys.len() as u64 * spec.access_list_storage_key_cost as u64
+    })
+}
+
+/// The EVM address representing a Move account in cross-space calls. It is
+/// the last 20 bytes of the keccak hash of the Move account address, the same
+/// way an EVM address is derived from a public key.
+pub fn mapped_sender_address(move_account: &[u8]) -> Address {
+    Address::from(keccak_hash::keccak(move_account))
+}
+
+/// The prefix of the storage keys of the cross-space contract holding the
+/// nonces of the mapped senders. The keys are longer than the 32-byte storage
+/// slots of the contract, so the namespace never collides with them.
+const MAPPED_SENDER_NONCE_PREFIX: &[u8] = b"mapped_sender_nonce";
+
+/// The storage key of the nonce of a mapped sender: `MAPPED_SENDER_NONCE_PREFIX`
+/// followed by the 20 bytes of the mapped address.
+fn mapped_sender_nonce_key(mapped_sender: &Address) -> Vec<u8> {
+    [MAPPED_SENDER_NONCE_PREFIX, mapped_sender.as_bytes()].concat()
+}
+
+/// Splits the gas limit of an executed transaction into the charged and the
+/// refunded parts. Returns `(gas_charged, fees_value, refund_value)`.
+///
+/// The refunded gas is `gas_left` capped by `spec.refund_cap` and
+/// `spec.max_refund_quotient`. With the Conflux rule, `gas_left` should be
+/// smaller than 1/4 of `gas_limit`, otherwise 3/4 of `gas_limit` is charged.
+pub(super) fn compute_gas_settlement(
+    gas_limit: U256,
+    gas_left: U256,
+    gas_price: U256,
+    spec: &Spec,
+) -> (U256, U256, U256) {
+    let gas_used = gas_limit - gas_left;
+    let cap_base = match spec.refund_cap {
+        RefundCap::GasLimit => gas_limit,
+        RefundCap::GasUsed => gas_used,
+    };
+    let max_refund = cap_base / U256::from(spec.max_refund_quotient.max(1));
+    let gas_refunded = gas_left.min(max_refund);
+    let gas_charged = gas_limit - gas_refunded;
+    (
+        gas_charged,
+        gas_charged.saturating_mul(gas_price),
+        gas_refunded.saturating_mul(gas_price),
     )
 }
 
@@ -57,8 +133,19 @@ enum PreCheckResult<'a> {
     Fail(ExecutionOutcome),
 }
 
-#[derive(Debug)]
+/// The kind of EVM action triggered by a cross-space call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CrossVMAction {
+    /// Message call to `CrossVMParams::receiver`.
+    Call,
+    /// Deploy a new contract with the given init code. The cross-space
+    /// contract is the sender, and `CrossVMParams::receiver` is ignored.
+    Create(Vec<u8>),
+}
+
+#[derive(Debug, Clone)]
 pub struct CrossVMParams {
+    pub action: CrossVMAction,
     pub receiver: Address,
     pub function_name: String,
     pub gas: U256,
@@ -66,22 +153,383 @@ pub struct CrossVMParams {
     pub value: U256,
     pub evm_params: Vec<Vec<u8>>,
     pub caller_info: String,
+    /// If set, it is used as the `original_sender` (`tx.origin`) of the call
+    /// instead of the zero address. It should be derived by
+    /// `mapped_sender_address`.
+    ///
+    /// A contract created with a mapped sender gets its address from the
+    /// mapped sender and its nonce in the cross-space namespace (see
+    /// `TXExecutor::mapped_sender_nonce`), instead of from the cross-space
+    /// contract. The EVM account of the mapped sender is not touched.
+    pub mapped_sender: Option<Address>,
+    /// Solidity types of the expected return values, e.g. `["uint256",
+    /// "address"]`. The return data is not decoded if it is empty.
+    pub return_types: Vec<String>,
+    /// The gas stipend for a call carrying value, like the 2300-gas stipend of
+    /// a value transfer in EVM. If `value` is nonzero, the stipend is added on
+    /// top of `gas`, so the receiver gets at least this amount of gas.
+    pub min_stipend: Option<U256>,
+    /// If set, `CrossVMAction::Create` deploys the contract at the CREATE2
+    /// address derived from this salt, instead of the address derived from the
+    /// nonce of the cross-space contract.
+    pub salt: Option<H256>,
+    /// If set, it is used verbatim as the calldata of `CrossVMAction::Call`,
+    /// so the Move side can call an EVM function of any signature with
+    /// pre-encoded calldata. It takes precedence over `function_name`,
+    /// `caller_info` and `evm_params`, which are ignored in this case.
+    pub raw_calldata: Option<Vec<u8>>,
+    /// If set, `CrossVMAction::Call` executes the code at this address in the
+    /// context (storage and balance) of `receiver`, like a proxy contract
+    /// forwarding the call to its logic contract.
+    pub code_address: Option<Address>,
+    /// The call type of `CrossVMAction::Call`, either `CallType::Call` or
+    /// `CallType::DelegateCall`. A delegate call runs in the context of
+    /// `receiver` without transferring value, so `value` must be zero.
+    pub call_type: CallType,
+    /// If set, `CrossVMAction::Call` fails with
+    /// `vm::Error::CrossSpaceTargetNotContract` when the executed code (at
+    /// `code_address` or `receiver`) is empty, instead of succeeding without
+    /// output.
+    pub require_contract: bool,
+    /// If set, the call fails with `vm::Error::StepLimitReached` once the
+    /// EVM frames of the call have executed this many instructions, even if
+    /// gas is left. It bounds the latency of a call independent of the gas.
+    pub max_steps: Option<u64>,
+    /// The space of `receiver`, `code_address` and the created contract. Only
+    /// `Space::Ethereum` is supported for now, the other spaces fail with
+    /// `vm::Error::UnsupportedCrossSpaceTarget`.
+    pub target_space: Space,
+    /// If set, the gas of the call is paid from the EVM-space balance of the
+    /// sponsor, e.g. when a Move-side sponsor covers the call for the caller.
+    /// `gas * gas_price` is charged before the call, and the unused gas
+    /// (excluding the stipend) is refunded afterward. The call is rejected
+    /// with `vm::Error::NotEnoughSponsorBalance` if the sponsor can not
+    /// afford it, or with `vm::Error::UnauthorizedSponsor` if the sponsor has
+    /// not authorized `mapped_sender` by `TXExecutor::authorize_sponsor`.
+    /// The used gas is burnt and credited to the block author like the fee
+    /// of a transaction.
+    pub sponsor: Option<Address>,
+    /// If set, `CrossVMAction::Call` calls `function_name` with these
+    /// arguments, i.e. the selector is derived from
+    /// `cross_vm_function_signature` and the arguments are encoded by
+    /// `encode_cross_vm_args`. `caller_info` and `evm_params` are ignored in
+    /// this case. `raw_calldata` still takes precedence.
+    pub typed_args: Option<Vec<CrossVMArg>>,
 }
 
 pub struct CrossVMReturn {
     pub substate: Substate,
     pub result: vm::Result<FinalizationResult>,
+    /// The return data decoded by `CrossVMParams::return_types`. It is `None`
+    /// if no return types are declared or the call does not succeed. The raw
+    /// bytes are still available in `result`.
+    pub decoded_output: Option<Result<Vec<ABIValue>, ABIDecodeError>>,
+    /// The decoded revert reason if the call is reverted by the EVM.
+    pub revert_reason: Option<String>,
+    pub gas_used: U256,
+    pub gas_left: U256,
+    /// The address of the deployed contract for a successful
+    /// `CrossVMAction::Create`.
+    pub created_address: Option<Address>,
+    /// Addresses of contracts created during the call, ordered from earliest
+    /// creation. See `Executed::contracts_created`.
+    pub contracts_created: Vec<AddressWithSpace>,
+    /// The logs emitted during the call, including the `CrossVMCallEvent` of
+    /// the call itself. They are copied from `substate`.
+    pub logs: Vec<LogEntry>,
+    /// The receipt of the call to be persisted by the storage layer. It is
+    /// set by `TXExecutor::cross_vm_call`, including for a rejected call.
+    pub receipt: Option<CrossSpaceReceipt>,
+}
+
+impl CrossVMReturn {
+    /// Assembles the return of a cross-space call from its substate and
+    /// result, e.g. to fabricate a return in the tests of the Move side.
+    /// `revert_reason`, `gas_left`, `contracts_created` and `logs` are derived
+    /// from them, and `gas_used` is zero since the gas of the call is unknown.
+    pub fn from_parts(substate: Substate, result: vm::Result<FinalizationResult>) -> Self {
+        let revert_reason = result
+            .as_ref()
+            .ok()
+            .and_then(FinalizationResult::decoded_error);
+        let gas_left = result.as_ref().map_or(U256::zero(), |r| r.gas_left);
+        CrossVMReturn {
+            contracts_created: substate.contracts_created.to_vec(),
+            logs: substate.logs.to_vec(),
+            substate,
+            result,
+            decoded_output: None,
+            revert_reason,
+            gas_used: U256::zero(),
+            gas_left,
+            created_address: None,
+            receipt: None,
+        }
+    }
+
+    /// The return of a cross-space call rejected before any frame is
+    /// executed. All the gas is consumed.
+    fn rejected(error: vm::Error, gas: U256) -> Self {
+        CrossVMReturn {
+            gas_used: gas,
+            ..Self::from_parts(Substate::new(), Err(error))
+        }
+    }
+}
+
+/// The contracts created by a batch of cross-space calls, in the order
+/// documented on `TXExecutor::cross_vm_call_batch`.
+pub fn batch_contracts_created(returns: &[CrossVMReturn]) -> Vec<AddressWithSpace> {
+    returns
+        .iter()
+        .flat_map(|output| output.contracts_created.iter().cloned())
+        .collect()
+}
+
+/// Checks that a non-empty function name of a cross-space call is a legal
+/// Solidity identifier (`[A-Za-z_][A-Za-z0-9_]*`). An empty name is allowed as
+/// it denotes a plain transfer.
+pub(super) fn validate_function_name(name: &str) -> vm::Result<()> {
+    let mut chars = name.chars();
+    let valid = match chars.next() {
+        None => true,
+        Some(first) => {
+            (first.is_ascii_alphabetic() || first == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        },
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(vm::Error::InvalidFunctionName(name.to_string()))
+    }
+}
+
+/// Converts the outcome of a failed cross-space call preprocessing to the
+/// error reported to the Move space.
+fn pre_check_error(outcome: ExecutionOutcome) -> vm::Error {
+    match outcome {
+        ExecutionOutcome::NotExecutedDrop(TxDropError::InitCodeTooLarge { limit, actual }) => {
+            vm::Error::InitCodeTooLarge { limit, actual }
+        },
+        ExecutionOutcome::NotExecutedDrop(TxDropError::CrossSpaceCalldataTooLarge {
+            limit,
+            actual,
+        }) => vm::Error::CrossSpaceCalldataTooLarge { limit, actual },
+        ExecutionOutcome::NotExecutedDrop(TxDropError::NotEnoughBaseGas { .. }) => {
+            vm::Error::OutOfGas
+        },
+        ExecutionOutcome::ExecutionErrorBumpNonce(ExecutionError::VmError(error), _) => error,
+        outcome => vm::Error::InternalContract(format!("Pre-check failed: {:?}", outcome)),
+    }
 }
 
 impl CrossVMParams {
+    /// Checks the parameters before the selector and the calldata are
+    /// computed. The function name is not checked if `raw_calldata` is set.
+    fn validate(&self) -> vm::Result<()> {
+        if self.target_space != Space::Ethereum {
+            return Err(vm::Error::UnsupportedCrossSpaceTarget(self.target_space));
+        }
+        if self.action != CrossVMAction::Call {
+            return Ok(());
+        }
+        match self.call_type {
+            CallType::Call => {},
+            CallType::DelegateCall if !self.value.is_zero() => {
+                return Err(vm::Error::CrossSpaceDelegateCallWithValue);
+            },
+            CallType::DelegateCall => {},
+            call_type => return Err(vm::Error::UnsupportedCrossSpaceCallType(call_type)),
+        }
+        if self.raw_calldata.is_some() {
+            return Ok(());
+        }
+        validate_function_name(&self.function_name)
+    }
+
     fn data(&self) -> Vec<u8> {
+        if let Some(raw_calldata) = &self.raw_calldata {
+            return raw_calldata.clone();
+        }
         if self.function_name.is_empty() {
             return vec![];
         }
-        let func_sig = format!("{}(string,bytes[])", self.function_name);
-        let func_bytes = keccak_hash::keccak(func_sig);
-        let abi_encode = (self.caller_info.clone(), self.evm_params.clone()).abi_encode();
-        [&func_bytes[..4], &abi_encode[..]].concat()
+        let abi_encode = match &self.typed_args {
+            Some(args) => encode_cross_vm_args(args),
+            None => (self.caller_info.clone(), self.evm_params.clone()).abi_encode(),
+        };
+        [&self.selector()[..], &abi_encode[..]].concat()
+    }
+
+    /// The gas stipend granted to this call in addition to `gas`.
+    fn stipend(&self) -> U256 {
+        match self.min_stipend {
+            Some(stipend) if !self.value.is_zero() => stipend,
+            _ => U256::zero(),
+        }
+    }
+
+    /// The gas limit of the top frame, including the stipend.
+    fn frame_gas(&self) -> U256 {
+        self.gas.saturating_add(self.stipend())
+    }
+
+    /// The function selector of the call, or zeros for a plain transfer or a
+    /// contract creation.
+    fn selector(&self) -> [u8; 4] {
+        if self.action != CrossVMAction::Call {
+            return [0u8; 4];
+        }
+        let mut selector = [0u8; 4];
+        if let Some(raw_calldata) = &self.raw_calldata {
+            let len = raw_calldata.len().min(4);
+            selector[..len].copy_from_slice(&raw_calldata[..len]);
+            return selector;
+        }
+        if self.function_name.is_empty() {
+            return selector;
+        }
+        let func_sig = match &self.typed_args {
+            Some(args) => cross_vm_function_signature(&self.function_name, args),
+            None => format!("{}(string,bytes[])", self.function_name),
+        };
+        selector.copy_from_slice(&keccak_hash::keccak(func_sig)[..4]);
+        selector
+    }
+}
+
+/// Builds `CrossVMParams` with fluent setters. Every field not set has a
+/// default: no value, no EVM params, an empty caller info and a plain
+/// `CallType::Call` without any of the optional settings.
+pub struct CrossVMParamsBuilder {
+    params: CrossVMParams,
+}
+
+impl CrossVMParamsBuilder {
+    pub fn new(action: CrossVMAction, receiver: Address, gas: U256) -> Self {
+        CrossVMParamsBuilder {
+            params: CrossVMParams {
+                action,
+                receiver,
+                function_name: String::new(),
+                gas,
+                gas_price: U256::zero(),
+                value: U256::zero(),
+                evm_params: vec![],
+                caller_info: String::new(),
+                mapped_sender: None,
+                return_types: vec![],
+                min_stipend: None,
+                salt: None,
+                raw_calldata: None,
+                code_address: None,
+                call_type: CallType::Call,
+                require_contract: false,
+                max_steps: None,
+                target_space: Space::Ethereum,
+                sponsor: None,
+                typed_args: None,
+            },
+        }
+    }
+
+    pub fn function_name(mut self, function_name: String) -> Self {
+        self.params.function_name = function_name;
+        self
+    }
+
+    pub fn gas_price(mut self, gas_price: U256) -> Self {
+        self.params.gas_price = gas_price;
+        self
+    }
+
+    pub fn value(mut self, value: U256) -> Self {
+        self.params.value = value;
+        self
+    }
+
+    pub fn evm_params(mut self, evm_params: Vec<Vec<u8>>) -> Self {
+        self.params.evm_params = evm_params;
+        self
+    }
+
+    pub fn caller_info(mut self, caller_info: String) -> Self {
+        self.params.caller_info = caller_info;
+        self
+    }
+
+    pub fn mapped_sender(mut self, mapped_sender: Address) -> Self {
+        self.params.mapped_sender = Some(mapped_sender);
+        self
+    }
+
+    pub fn return_types(mut self, return_types: Vec<String>) -> Self {
+        self.params.return_types = return_types;
+        self
+    }
+
+    pub fn min_stipend(mut self, min_stipend: U256) -> Self {
+        self.params.min_stipend = Some(min_stipend);
+        self
+    }
+
+    pub fn salt(mut self, salt: H256) -> Self {
+        self.params.salt = Some(salt);
+        self
+    }
+
+    pub fn raw_calldata(mut self, raw_calldata: Vec<u8>) -> Self {
+        self.params.raw_calldata = Some(raw_calldata);
+        self
+    }
+
+    pub fn code_address(mut self, code_address: Address) -> Self {
+        self.params.code_address = Some(code_address);
+        self
+    }
+
+    pub fn call_type(mut self, call_type: CallType) -> Self {
+        self.params.call_type = call_type;
+        self
+    }
+
+    pub fn require_contract(mut self, require_contract: bool) -> Self {
+        self.params.require_contract = require_contract;
+        self
+    }
+
+    pub fn max_steps(mut self, max_steps: u64) -> Self {
+        self.params.max_steps = Some(max_steps);
+        self
+    }
+
+    pub fn target_space(mut self, target_space: Space) -> Self {
+        self.params.target_space = target_space;
+        self
+    }
+
+    pub fn sponsor(mut self, sponsor: Address) -> Self {
+        self.params.sponsor = Some(sponsor);
+        self
+    }
+
+    pub fn typed_args(mut self, typed_args: Vec<CrossVMArg>) -> Self {
+        self.params.typed_args = Some(typed_args);
+        self
+    }
+
+    /// A `CrossVMAction::Call` must set either `function_name` or
+    /// `raw_calldata`. A plain value transfer sets an empty `raw_calldata`.
+    pub fn build(self) -> vm::Result<CrossVMParams> {
+        let params = self.params;
+        if params.action == CrossVMAction::Call
+            && params.function_name.is_empty()
+            && params.raw_calldata.is_none()
+        {
+            return Err(vm::Error::MissingCrossSpaceCalldata);
+        }
+        Ok(params)
     }
 }
 
@@ -99,6 +547,124 @@ impl<'a> TXExecutor<'a> {
             machine,
             factory: machine.vm_factory(),
             spec,
+            code_cache: HashMap::new(),
+            receipt_cache: None,
+            cross_space_depth: 0,
+            sponsored_callers: HashMap::new(),
+        }
+    }
+
+    /// Makes `transact` return `ExecutionOutcome::AlreadyExecuted` for a
+    /// transaction recorded in `receipt_cache`, instead of rejecting it by
+    /// the nonce. A transaction with a hash is recorded once it is executed
+    /// successfully, unless it is only an estimation.
+    pub fn with_receipt_cache(mut self, receipt_cache: Arc<Mutex<ReceiptCache>>) -> Self {
+        self.receipt_cache = Some(receipt_cache);
+        self
+    }
+
+    /// Sets the number of EVM-Move transitions on the call chain leading to
+    /// the cross-space calls of this executor. A cross-space call is rejected
+    /// with `vm::Error::CrossSpaceReentrancyLimit` once it reaches
+    /// `Spec::max_cross_space_depth`.
+    pub fn set_cross_space_depth(&mut self, cross_space_depth: usize) {
+        self.cross_space_depth = cross_space_depth;
+    }
+
+    /// Lets `sponsor` pay the gas of the cross-space calls whose
+    /// `mapped_sender` is `caller`. The embedder is expected to authorize a
+    /// pair only after verifying the consent of the sponsor, e.g. its
+    /// signature.
+    pub fn authorize_sponsor(&mut self, sponsor: Address, caller: Address) {
+        self.sponsored_callers
+            .entry(sponsor)
+            .or_default()
+            .insert(caller);
+    }
+
+    fn is_authorized_sponsor(&self, sponsor: &Address, caller: Option<Address>) -> bool {
+        match (caller, self.sponsored_callers.get(sponsor)) {
+            (Some(caller), Some(callers)) => callers.contains(&caller),
+            _ => false,
+        }
+    }
+
+    /// The code and the code hash of an account, read from the state if they
+    /// are not in `code_cache`.
+    fn cached_code(
+        &mut self,
+        address: &AddressWithSpace,
+    ) -> DbResult<(Option<Arc<Vec<u8>>>, Option<H256>)> {
+        if let Some(entry) = self.code_cache.get(address) {
+            return Ok(entry.clone());
+        }
+        let entry = (self.state.code(address)?, self.state.code_hash(address)?);
+        self.code_cache.insert(*address, entry.clone());
+        Ok(entry)
+    }
+
+    /// The nonce of a Move account in the cross-space nonce namespace. Each
+    /// contract created by a cross-space call with the mapped sender of the
+    /// account advances it by one. It starts from zero and is stored in the
+    /// cross-space contract under `mapped_sender_nonce_key`, so it is
+    /// independent from the nonce of the EVM account at the mapped address.
+    pub fn mapped_sender_nonce(&self, move_account: &[u8]) -> DbResult<U256> {
+        self.mapped_nonce(&mapped_sender_address(move_account))
+    }
+
+    /// The intrinsic gas of a transaction under the spec of the executor,
+    /// i.e. the gas charged before any code is executed. A transaction with a
+    /// lower gas limit is dropped with `TxDropError::NotEnoughBaseGas`, so
+    /// the RPC layers can check user-supplied gas limits without executing
+    /// the transaction.
+    pub fn intrinsic_gas(&self, tx: &impl TransactionInfo) -> u64 {
+        gas_required_for(
+            *tx.action() == Action::Create,
+            &tx.data(),
+            &tx.access_list(),
+            self.spec,
+        )
+    }
+
+    fn mapped_nonce(&self, mapped_sender: &Address) -> DbResult<U256> {
+        self.state.storage_at(
+            &CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space(),
+            &mapped_sender_nonce_key(mapped_sender),
+        )
+    }
+
+    fn set_mapped_nonce(&mut self, mapped_sender: &Address, nonce: U256) -> DbResult<()> {
+        let cross_space = CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space();
+        // The storage can only be written to an existing account.
+        self.state.add_balance(
+            &cross_space,
+            &U256::zero(),
+            CleanupMode::ForceCreate,
+            self.spec.account_start_nonce,
+        )?;
+        self.state
+            .set_storage(&cross_space, mapped_sender_nonce_key(mapped_sender), nonce)
+    }
+
+    /// Checks that the target of a cross-space call has code if
+    /// `CrossVMParams::require_contract` is set.
+    fn check_cross_vm_target(&mut self, params: &CrossVMParams) -> DbResult<vm::Result<()>> {
+        if !params.require_contract || params.action != CrossVMAction::Call {
+            return Ok(Ok(()));
+        }
+        let address = params.code_address.unwrap_or(params.receiver);
+        let (code, _) = self.cached_code(&address.with_space(params.target_space))?;
+        if code.map_or(true, |code| code.is_empty()) {
+            return Ok(Err(vm::Error::CrossSpaceTargetNotContract(address)));
+        }
+        Ok(Ok(()))
+    }
+
+    /// Removes the cached code of the contracts created or removed in the
+    /// substate.
+    fn invalidate_code_cache(&mut self, substate: &Substate) {
+        for address in substate.contracts_created.iter().chain(&substate.suicides) {
+            self.code_cache.remove(address);
         }
     }
 
@@ -107,6 +673,15 @@ impl<'a> TXExecutor<'a> {
         tx: &impl TransactionInfo,
         options: TransactOptions,
     ) -> DbResult<ExecutionOutcome> {
+        let trace_level = options.trace_level;
+        let check_settings = options.check_settings;
+        let receipt_cache = self.receipt_cache.clone();
+        let tx_hash = tx.hash();
+        if let (Some(cache), Some(hash)) = (&receipt_cache, tx_hash) {
+            if cache.lock().contains(&hash) {
+                return Ok(ExecutionOutcome::AlreadyExecuted(hash));
+            }
+        }
         let pre_check_result = self.transact_preprocessing(tx, options)?;
 
         let (top_frame, frame_stack) = match pre_check_result {
@@ -121,24 +696,397 @@ impl<'a> TXExecutor<'a> {
 
         let frame_stack_output = frame_stack.exec(top_frame)?; // stopped here
 
-        Ok(self.transact_postprocessing(tx, frame_stack_output)?)
+        let outcome =
+            self.transact_postprocessing(tx, frame_stack_output, check_settings, trace_level)?;
+        if let (Some(cache), Some(hash)) = (receipt_cache, tx_hash) {
+            if check_settings.real_execution && outcome.succeeded() {
+                cache.lock().insert(hash);
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Executes the transaction like `transact`, with some fields of the
+    /// `Env` replaced by `env_override`. The `Spec` of the executor is kept
+    /// even if the block number is overridden.
+    pub fn transact_with_env(
+        &mut self,
+        tx: &impl TransactionInfo,
+        options: TransactOptions,
+        env_override: EnvOverride,
+    ) -> DbResult<ExecutionOutcome> {
+        let env = env_override.apply(self.env);
+        let mut executor = TXExecutor::new(&mut *self.state, &env, self.machine, self.spec);
+        executor.receipt_cache = self.receipt_cache.clone();
+        executor.transact(tx, options)
+    }
+
+    /// Executes a message call like `eth_call`. Unlike `transact`, no gas is
+    /// charged and no nonce is bumped. The call is executed against a
+    /// checkpoint, and all the state changes are reverted afterward.
+    pub fn call(
+        &mut self,
+        params: ActionParams,
+        observer: MultiObservers,
+    ) -> DbResult<vm::Result<FinalizationResult>> {
+        let top_frame = CallCreateFrame::new_call_raw(
+            params,
+            self.env,
+            self.machine,
+            self.spec,
+            &self.factory,
+            0,     /* depth */
+            false, /* static_flag */
+        );
+        self.state.checkpoint();
+        let output = FrameStack::new(
+            self.state,
+            Substate::new(),
+            observer,
+            0,
+            self.spec.max_depth,
+        )
+        .with_cross_space_depth(self.cross_space_depth)
+        .exec(top_frame);
+        self.state.revert_to_checkpoint();
+        Ok(output?.result)
     }
 
     pub fn cross_vm_call(&mut self, params: CrossVMParams) -> DbResult<CrossVMReturn> {
-        let pre_check_result = self.cross_vm_call_preprocessing(params)?;
+        let receiver = params.receiver;
+        let selector = params.selector();
+        let value = params.value;
+        let mut output = self.execute_cross_vm_call(params)?;
+        output.receipt = Some(CrossSpaceReceipt {
+            receiver: output.created_address.unwrap_or(receiver),
+            selector,
+            value,
+            gas_used: output.gas_used,
+            success: output
+                .result
+                .as_ref()
+                .map_or(false, FinalizationResult::is_success),
+        });
+        Ok(output)
+    }
+
+    /// Executes a cross-space call like `cross_vm_call`, and reports whether
+    /// any log emitted by the call has `topic0` as its first topic. The call
+    /// always runs to the end, only the logs are scanned afterwards.
+    pub fn cross_vm_call_until_event(
+        &mut self,
+        params: CrossVMParams,
+        topic0: H256,
+    ) -> DbResult<(bool, CrossVMReturn)> {
+        let output = self.cross_vm_call(params)?;
+        let matched = output
+            .substate
+            .logs
+            .iter()
+            .any(|log| log.topics.first() == Some(&topic0));
+        Ok((matched, output))
+    }
+
+    fn execute_cross_vm_call(&mut self, mut params: CrossVMParams) -> DbResult<CrossVMReturn> {
+        let return_types = std::mem::take(&mut params.return_types);
+        let gas = params.gas;
+        let frame_gas = params.frame_gas();
+        let value = params.value;
+        let caller_info_hash = keccak_hash::keccak(&params.caller_info);
+        let receiver = params.receiver;
+        let gas_price = params.gas_price;
+        let sponsor = params.sponsor.map(|sponsor| sponsor.with_evm_space());
+
+        if self.cross_space_depth >= self.spec.max_cross_space_depth {
+            return Ok(CrossVMReturn::rejected(
+                vm::Error::CrossSpaceReentrancyLimit,
+                gas,
+            ));
+        }
+        if let Err(error) = params.validate() {
+            return Ok(CrossVMReturn::rejected(error, gas));
+        }
+        if let Err(error) = self.check_cross_vm_target(&params)? {
+            return Ok(CrossVMReturn::rejected(error, gas));
+        }
+
+        let selector = params.selector();
+        let mut observer = MultiObservers::with_no_tracing();
+        if let Some(sponsor) = &sponsor {
+            if !self.is_authorized_sponsor(&sponsor.address, params.mapped_sender) {
+                return Ok(CrossVMReturn::rejected(
+                    vm::Error::UnauthorizedSponsor(sponsor.address),
+                    gas,
+                ));
+            }
+            let gas_cost = gas.saturating_mul(gas_price);
+            let balance = self.state.balance(sponsor)?;
+            if balance < gas_cost {
+                return Ok(CrossVMReturn::rejected(
+                    vm::Error::NotEnoughSponsorBalance {
+                        required: gas_cost,
+                        got: balance,
+                    },
+                    gas,
+                ));
+            }
+            // The whole gas is paid in advance and the unused part is
+            // refunded after the call. A call failing the pre-check uses up
+            // its gas.
+            self.state.sub_balance(
+                sponsor,
+                &gas_cost,
+                &mut cleanup_mode(&mut Substate::new(), &self.spec),
+            )?;
+            observer.as_state_tracer().trace_internal_transfer(
+                AddressPocket::Balance(*sponsor),
+                AddressPocket::GasPayment,
+                gas_cost,
+            );
+        }
+        observer
+            .as_state_tracer()
+            .trace_cross_space_enter(receiver, selector, value);
+        let (pre_check_result, new_address) = self.cross_vm_call_preprocessing(params, observer)?;
 
         let (top_frame, frame_stack) = match pre_check_result {
             PreCheckResult::Pass {
                 top_frame,
                 frame_stack,
             } => (top_frame, frame_stack),
-            PreCheckResult::Fail(_) => unreachable!(),
+            PreCheckResult::Fail(outcome) => {
+                if let Some(sponsor) = &sponsor {
+                    self.settle_sponsored_gas(
+                        sponsor,
+                        gas,
+                        gas,
+                        gas_price,
+                        &mut Substate::new(),
+                        &mut MultiObservers::with_no_tracing(),
+                    )?;
+                }
+                return Ok(CrossVMReturn::rejected(pre_check_error(outcome), gas));
+            },
         };
 
         let FrameStackOutput {
-            substate, result, ..
+            mut substate,
+            result,
+            mut observer,
+            ..
         } = frame_stack.exec(top_frame)?;
-        return Ok(CrossVMReturn { substate, result });
+        self.invalidate_code_cache(&substate);
+
+        let success = result
+            .as_ref()
+            .map_or(false, FinalizationResult::is_success);
+        if !success {
+            // The value deposited to the cross-space contract in preprocessing
+            // is not transferred out, return it to the Move space.
+            let cross_space = CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space();
+            self.state.sub_balance(
+                &cross_space,
+                &value,
+                &mut cleanup_mode(&mut substate, &self.spec),
+            )?;
+            observer.as_state_tracer().trace_internal_transfer(
+                AddressPocket::Balance(cross_space),
+                AddressPocket::MoveSpace,
+                value,
+            );
+        }
+        substate.logs.push(CrossVMCallEvent::log_entry(
+            &(new_address.unwrap_or(receiver), caller_info_hash),
+            &(selector, value, success),
+            &CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space(),
+        ));
+
+        let decoded_output = match result {
+            Ok(ref r) if r.is_success() && !return_types.is_empty() => {
+                Some(abi_decode_values(&return_types, &r.return_data))
+            },
+            _ => None,
+        };
+        let revert_reason = result
+            .as_ref()
+            .ok()
+            .and_then(FinalizationResult::decoded_error);
+        let gas_left = match result {
+            Ok(FinalizationResult { gas_left, .. }) => gas_left,
+            _ => 0.into(),
+        };
+        let gas_used = frame_gas.saturating_sub(gas_left);
+        if let Some(sponsor) = &sponsor {
+            // The gas used from the stipend is not charged.
+            self.settle_sponsored_gas(
+                sponsor,
+                gas,
+                gas_used.min(gas),
+                gas_price,
+                &mut substate,
+                &mut observer,
+            )?;
+        }
+        observer
+            .as_state_tracer()
+            .trace_cross_space_exit(success, gas_used);
+        let created_address = if success { new_address } else { None };
+        let contracts_created = substate.contracts_created.to_vec();
+        let logs = substate.logs.to_vec();
+        return Ok(CrossVMReturn {
+            substate,
+            result,
+            decoded_output,
+            revert_reason,
+            gas_used,
+            gas_left,
+            created_address,
+            contracts_created,
+            logs,
+            receipt: None,
+        });
+    }
+
+    /// Executes a cross-space call like `cross_vm_call`, with `spec` instead
+    /// of the `Spec` of the executor, e.g. to simulate the call under a future
+    /// hard fork.
+    pub fn cross_vm_call_with_spec(
+        &mut self,
+        params: CrossVMParams,
+        spec: &Spec,
+    ) -> DbResult<CrossVMReturn> {
+        let mut executor = TXExecutor::new(&mut *self.state, self.env, self.machine, spec);
+        // Share the code cache, so the entries invalidated by the call are
+        // also removed for this executor.
+        executor.code_cache = std::mem::take(&mut self.code_cache);
+        let output = executor.cross_vm_call(params);
+        self.code_cache = executor.code_cache;
+        output
+    }
+
+    /// Executes the cross-space calls in order. If any call fails or is
+    /// reverted, the state changes of the whole batch are reverted, the
+    /// remaining calls are skipped, the substates, created contracts and logs
+    /// of the returned calls are cleared and their receipts are marked as
+    /// failed.
+    ///
+    /// The contracts created by the batch (see `batch_contracts_created`) are
+    /// ordered by the index of the call in the batch first, then from the
+    /// earliest creation within each call as in
+    /// `CrossVMReturn::contracts_created`.
+    ///
+    /// Each call is executed with its own observers. The gas limits of the
+    /// calls can be estimated by `cross_vm_estimate_batch`.
+    ///
+    /// The calls always run one by one on the shared state, even if they are
+    /// independent: `StateTrait` can not fork a state view for each call and
+    /// merge the views back, and `State` holds the Move VM by a unique
+    /// reference, so the calls can not run on other threads either.
+    pub fn cross_vm_call_batch(
+        &mut self,
+        calls: Vec<CrossVMParams>,
+    ) -> DbResult<Vec<CrossVMReturn>> {
+        self.state.checkpoint();
+
+        let mut returns = Vec::with_capacity(calls.len());
+        for params in calls {
+            let output = match self.cross_vm_call(params) {
+                Ok(output) => output,
+                Err(e) => {
+                    self.state.revert_to_checkpoint();
+                    self.code_cache.clear();
+                    return Err(e);
+                },
+            };
+            let success = output
+                .result
+                .as_ref()
+                .map_or(false, FinalizationResult::is_success);
+            returns.push(output);
+
+            if !success {
+                self.state.revert_to_checkpoint();
+                self.code_cache.clear();
+                for output in &mut returns {
+                    output.substate = Substate::new();
+                    output.contracts_created.clear();
+                    output.logs.clear();
+                    if let Some(receipt) = &mut output.receipt {
+                        receipt.success = false;
+                    }
+                }
+                return Ok(returns);
+            }
+        }
+
+        self.state.discard_checkpoint();
+        Ok(returns)
+    }
+
+    /// Estimates the gas limit required by a cross-space call. The call is
+    /// executed against a checkpoint, and all the state changes are reverted
+    /// afterward. The returned value is padded in the same way as
+    /// `Executed::estimated_gas_limit`. The stipend is not included in the
+    /// returned value since it is granted on top of `CrossVMParams::gas`.
+    pub fn cross_vm_estimate(&mut self, params: CrossVMParams) -> DbResult<U256> {
+        self.state.checkpoint();
+        let output = self.cross_vm_estimate_uncommitted(params);
+        self.state.revert_to_checkpoint();
+        self.code_cache.clear();
+        output
+    }
+
+    /// Estimates the gas limit required by each call of a batch, as
+    /// `cross_vm_estimate` does. The calls are executed in order, so a call
+    /// sees the state changes of the previous ones, and all the state changes
+    /// are reverted afterward.
+    ///
+    /// Each call is traced by a fresh gas observer, so the returned estimates
+    /// are per call rather than cumulative: the gas limit of the i-th call is
+    /// the i-th estimate, and the batch requires their sum.
+    pub fn cross_vm_estimate_batch(&mut self, calls: Vec<CrossVMParams>) -> DbResult<Vec<U256>> {
+        self.state.checkpoint();
+        let output = calls
+            .into_iter()
+            .map(|params| self.cross_vm_estimate_uncommitted(params))
+            .collect();
+        self.state.revert_to_checkpoint();
+        self.code_cache.clear();
+        output
+    }
+
+    /// Executes a cross-space call with a fresh gas observer and returns the
+    /// estimated gas limit. The state changes are kept, the caller must
+    /// revert them.
+    fn cross_vm_estimate_uncommitted(&mut self, params: CrossVMParams) -> DbResult<U256> {
+        let stipend = params.stipend();
+        if let Err(error) = params.validate() {
+            bail!("Cross-space call rejected: {}", error);
+        }
+        if let Err(error) = self.check_cross_vm_target(&params)? {
+            bail!("Cross-space call rejected: {}", error);
+        }
+        let (pre_check_result, _) =
+            self.cross_vm_call_preprocessing(params, MultiObservers::virtual_call())?;
+        let (top_frame, frame_stack) = match pre_check_result {
+            PreCheckResult::Pass {
+                top_frame,
+                frame_stack,
+            } => (top_frame, frame_stack),
+            PreCheckResult::Fail(outcome) => {
+                bail!("Cross-space call rejected: {}", pre_check_error(outcome))
+            },
+        };
+
+        let FrameStackOutput {
+            observer,
+            base_gas_required,
+            ..
+        } = frame_stack.exec(top_frame)?;
+        // Unwrap safety: the gas observer is enabled in `virtual_call`.
+        let gas_required = observer.gas_man.unwrap().gas_required();
+        let estimated: U256 = gas_required * 7 / 6 + base_gas_required;
+        Ok(estimated.saturating_sub(stipend))
     }
 
     fn transact_preprocessing(
@@ -149,7 +1097,11 @@ impl<'a> TXExecutor<'a> {
         let TransactOptions {
             mut observer,
             check_settings,
+            trace_level,
         } = options;
+        if trace_level != TraceLevel::Full {
+            observer.tracer = None;
+        }
 
         let spec = self.spec;
         let sender = tx.sender();
@@ -169,8 +1121,35 @@ impl<'a> TXExecutor<'a> {
             ));
         }
 
-        let base_gas_required =
-            gas_required_for(&*tx.action() == &Action::Create, &tx.data(), spec);
+        // A zero block gas limit means the block gas limit is not enforced,
+        // e.g., in the Move adapter.
+        if check_settings.check_block_gas_limit && !self.env.gas_limit.is_zero() {
+            let remaining_gas = self
+                .env
+                .gas_limit
+                .saturating_sub(self.env.accumulated_gas_used);
+            if *tx.gas() > remaining_gas {
+                return Ok(PreCheckResult::Fail(
+                    ExecutionOutcome::NotExecutedToReconsiderPacking(
+                        ToRepackError::BlockGasLimitExceeded {
+                            limit: remaining_gas,
+                            got: *tx.gas(),
+                        },
+                    ),
+                ));
+            }
+        }
+
+        if &*tx.action() == &Action::Create && tx.data().len() > spec.init_code_data_limit {
+            return Ok(PreCheckResult::Fail(ExecutionOutcome::NotExecutedDrop(
+                TxDropError::InitCodeTooLarge {
+                    limit: spec.init_code_data_limit,
+                    actual: tx.data().len(),
+                },
+            )));
+        }
+
+        let base_gas_required = self.intrinsic_gas(tx);
         if *tx.gas() < base_gas_required.into() {
             return Ok(PreCheckResult::Fail(ExecutionOutcome::NotExecutedDrop(
                 TxDropError::NotEnoughBaseGas {
@@ -180,9 +1159,18 @@ impl<'a> TXExecutor<'a> {
             )));
         }
 
+        if let (Some(base_fee), Some(max_fee)) = (self.base_fee_for(tx), tx.max_fee_per_gas()) {
+            if max_fee < base_fee {
+                return Ok(PreCheckResult::Fail(ExecutionOutcome::NotExecutedDrop(
+                    TxDropError::FeeCapTooLow { base_fee, max_fee },
+                )));
+            }
+        }
+        let gas_price = self.effective_gas_price(tx);
+
         let balance = self.state.balance(&sender)?;
         let gas_cost = if check_settings.charge_gas {
-            tx.gas().full_mul(*tx.gas_price())
+            tx.gas().full_mul(gas_price)
         } else {
             0.into()
         };
@@ -284,7 +1272,7 @@ impl<'a> TXExecutor<'a> {
                     sender: sender.address,
                     original_sender: sender.address,
                     gas: init_gas,
-                    gas_price: *tx.gas_price(),
+                    gas_price,
                     value: ActionValue::Transfer(*tx.value()),
                     code: Some(Arc::new(tx.data().into_owned())),
                     data: None,
@@ -304,6 +1292,7 @@ impl<'a> TXExecutor<'a> {
             },
             Action::Call(ref address) => {
                 let address = address.with_space(sender.space);
+                let (code, code_hash) = self.cached_code(&address)?;
                 let params = ActionParams {
                     space: sender.space,
                     code_address: address.address,
@@ -311,10 +1300,10 @@ impl<'a> TXExecutor<'a> {
                     sender: sender.address,
                     original_sender: sender.address,
                     gas: init_gas,
-                    gas_price: *tx.gas_price(),
+                    gas_price,
                     value: ActionValue::Transfer(*tx.value()),
-                    code: self.state.code(&address)?,
-                    code_hash: self.state.code_hash(&address)?,
+                    code,
+                    code_hash,
                     data: Some(tx.data().into_owned()),
                     call_type: CallType::Call,
                     create_type: CreateType::None,
@@ -332,7 +1321,14 @@ impl<'a> TXExecutor<'a> {
             },
         };
 
-        let frame_stack = FrameStack::new(self.state, tx_substate, observer, base_gas_required);
+        let frame_stack = FrameStack::new(
+            self.state,
+            tx_substate,
+            observer,
+            base_gas_required,
+            self.spec.max_depth,
+        )
+        .with_cross_space_depth(self.cross_space_depth);
 
         Ok(PreCheckResult::Pass {
             top_frame,
@@ -345,6 +1341,8 @@ impl<'a> TXExecutor<'a> {
         &mut self,
         tx: &impl TransactionInfo,
         frame_stack_output: FrameStackOutput,
+        check_settings: TransactCheckSettings,
+        trace_level: TraceLevel,
     ) -> DbResult<ExecutionOutcome> {
         let FrameStackOutput {
             mut substate,
@@ -368,26 +1366,12 @@ impl<'a> TXExecutor<'a> {
             _ => 0.into(),
         };
 
+        let gas_price = self.effective_gas_price(tx);
+
         // gas_used is only used to estimate gas needed
         let gas_used = *tx.gas() - gas_left;
-        // gas_left should be smaller than 1/4 of gas_limit, otherwise
-        // 3/4 of gas_limit is charged.
-        let charge_all = (gas_left + gas_left + gas_left) >= gas_used;
-        let (gas_charged, fees_value, refund_value) = if charge_all {
-            let gas_refunded = *tx.gas() >> 2;
-            let gas_charged = *tx.gas() - gas_refunded;
-            (
-                gas_charged,
-                gas_charged.saturating_mul(*tx.gas_price()),
-                gas_refunded.saturating_mul(*tx.gas_price()),
-            )
-        } else {
-            (
-                gas_used,
-                gas_used.saturating_mul(*tx.gas_price()),
-                gas_left.saturating_mul(*tx.gas_price()),
-            )
-        };
+        let (gas_charged, fees_value, refund_value) =
+            compute_gas_settlement(*tx.gas(), gas_left, gas_price, self.spec);
 
         {
             observer.as_state_tracer().trace_internal_transfer(
@@ -403,36 +1387,60 @@ impl<'a> TXExecutor<'a> {
             )?;
         };
 
+        let burnt_fee = match self.base_fee_for(tx) {
+            Some(base_fee) => {
+                // The base fee portion of the transaction fee is burnt.
+                let burnt_fee = gas_charged.saturating_mul(base_fee);
+                observer.as_state_tracer().trace_internal_transfer(
+                    AddressPocket::GasPayment,
+                    AddressPocket::MintBurn,
+                    burnt_fee,
+                );
+                self.state.subtract_total_issued(burnt_fee);
+                burnt_fee
+            },
+            None => U256::zero(),
+        };
+
+        // The rest of the transaction fee goes to the block author, like the
+        // coinbase in Ethereum.
+        let author_fee = fees_value.saturating_sub(burnt_fee);
+        if check_settings.credit_fees && !self.env.author.is_zero() && !author_fee.is_zero() {
+            let author = self.env.author.with_space(tx.space());
+            observer.as_state_tracer().trace_internal_transfer(
+                AddressPocket::GasPayment,
+                AddressPocket::Balance(author),
+                author_fee,
+            );
+            self.state.add_balance(
+                &author,
+                &author_fee,
+                cleanup_mode(&mut substate, self.spec),
+                self.spec.account_start_nonce,
+            )?;
+        }
+
         // perform suicides
 
-        let subsubstate = self.kill_process(&substate.suicides, observer.as_state_tracer())?;
+        let (subsubstate, tokens_burned) =
+            self.kill_process(&substate.suicides, observer.as_state_tracer())?;
         substate.accrue(subsubstate);
+        self.invalidate_code_cache(&substate);
 
-        // TODO should be added back after enabling dust collection
-        // Should be executed once per block, instead of per transaction?
-        //
-        // When enabling this feature, remember to check touched set in
-        // functions like "add_collateral_for_storage()" in "State"
-        // struct.
-
-        //        // perform garbage-collection
-        //        let min_balance = if spec.kill_dust != CleanDustMode::Off {
-        //            Some(U256::from(spec.tx_gas) * tx.gas_price())
-        //        } else {
-        //            None
-        //        };
-        //
-        //        self.state.kill_garbage(
-        //            &substate.touched,
-        //            spec.kill_empty,
-        //            &min_balance,
-        //            spec.kill_dust == CleanDustMode::WithCodeAndStorage,
-        //        )?;
+        // The garbage collection of the touched dust accounts refunds their
+        // storage collateral, so it is part of the collateral machinery which
+        // is not implemented. The EVM space always runs collateral-free, see
+        // `Spec::storage_collateral_enabled`.
 
         match result {
             Err(vm::Error::StateDbError(e)) => bail!(e.0),
             Err(exception) => Ok(ExecutionOutcome::ExecutionErrorBumpNonce(
-                ExecutionError::VmError(exception),
+                match exception {
+                    vm::Error::OutOfGas => ExecutionError::OutOfGas {
+                        gas_limit: *tx.gas(),
+                    },
+                    exception => ExecutionError::VmError(exception),
+                },
                 Executed::execution_error_fully_charged(
                     tx,
                     observer.tracer.map_or(Default::default(), |t| t.drain()),
@@ -446,14 +1454,20 @@ impl<'a> TXExecutor<'a> {
                     gas_used,
                     gas_charged,
                     fee: fees_value,
-                    logs: substate.logs.to_vec(),
+                    logs: match trace_level {
+                        TraceLevel::None => vec![],
+                        _ => substate.logs.to_vec(),
+                    },
                     contracts_created: substate.contracts_created.to_vec(),
+                    is_create: *tx.action() == Action::Create,
                     output,
                     trace,
                     estimated_gas_limit,
+                    tx_hash: tx.hash(),
+                    tokens_burned,
                 };
 
-                if r.apply_state {
+                if r.is_success() {
                     Ok(ExecutionOutcome::Finished(executed))
                 } else {
                     // Transaction reverted by vm instruction.
@@ -466,74 +1480,288 @@ impl<'a> TXExecutor<'a> {
         }
     }
 
+    /// The block base fee if the transaction is priced by EIP-1559. It is
+    /// `None` for a legacy transaction or if the fee market is not enabled.
+    /// Settles the gas of a sponsored cross-space call, paid in advance by the
+    /// sponsor. The uncharged gas is refunded to the sponsor. Like the fee of
+    /// a transaction in `transact_postprocessing`, the base fee portion of the
+    /// charged gas is burnt and the rest goes to the block author.
+    fn settle_sponsored_gas(
+        &mut self,
+        sponsor: &AddressWithSpace,
+        gas: U256,
+        gas_charged: U256,
+        gas_price: U256,
+        substate: &mut Substate,
+        observer: &mut MultiObservers,
+    ) -> DbResult<()> {
+        let refund = (gas - gas_charged).saturating_mul(gas_price);
+        self.state.add_balance(
+            sponsor,
+            &refund,
+            cleanup_mode(substate, self.spec),
+            self.spec.account_start_nonce,
+        )?;
+        observer.as_state_tracer().trace_internal_transfer(
+            AddressPocket::GasPayment,
+            AddressPocket::Balance(*sponsor),
+            refund,
+        );
+
+        let fee = gas_charged.saturating_mul(gas_price);
+        let burnt_fee = match self.env.base_fee_per_gas {
+            Some(base_fee) => {
+                let burnt_fee = gas_charged.saturating_mul(base_fee.min(gas_price));
+                observer.as_state_tracer().trace_internal_transfer(
+                    AddressPocket::GasPayment,
+                    AddressPocket::MintBurn,
+                    burnt_fee,
+                );
+                self.state.subtract_total_issued(burnt_fee);
+                burnt_fee
+            },
+            None => U256::zero(),
+        };
+
+        let author_fee = fee.saturating_sub(burnt_fee);
+        if !self.env.author.is_zero() && !author_fee.is_zero() {
+            let author = self.env.author.with_evm_space();
+            observer.as_state_tracer().trace_internal_transfer(
+                AddressPocket::GasPayment,
+                AddressPocket::Balance(author),
+                author_fee,
+            );
+            self.state.add_balance(
+                &author,
+                &author_fee,
+                cleanup_mode(substate, self.spec),
+                self.spec.account_start_nonce,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn base_fee_for(&self, tx: &impl TransactionInfo) -> Option<U256> {
+        self.env
+            .base_fee_per_gas
+            .filter(|_| tx.max_fee_per_gas().is_some())
+    }
+
+    /// The gas price actually paid by the transaction. For an EIP-1559
+    /// transaction, it is the base fee plus the priority fee, capped by the max
+    /// fee. Otherwise, it is the gas price of the transaction.
+    fn effective_gas_price(&self, tx: &impl TransactionInfo) -> U256 {
+        match (self.base_fee_for(tx), tx.max_fee_per_gas()) {
+            (Some(base_fee), Some(max_fee)) => {
+                let priority_fee = tx.max_priority_fee_per_gas().unwrap_or_default();
+                max_fee.min(base_fee.saturating_add(priority_fee))
+            },
+            _ => *tx.gas_price(),
+        }
+    }
+
     fn cross_vm_call_preprocessing(
         &mut self,
         cross_vm_params: CrossVMParams,
-    ) -> DbResult<PreCheckResult> {
+        mut observer: MultiObservers,
+    ) -> DbResult<(PreCheckResult, Option<Address>)> {
         let data = cross_vm_params.data();
+        if data.len() > self.spec.max_cross_space_calldata_size {
+            return Ok((
+                PreCheckResult::Fail(ExecutionOutcome::NotExecutedDrop(
+                    TxDropError::CrossSpaceCalldataTooLarge {
+                        limit: self.spec.max_cross_space_calldata_size,
+                        actual: data.len(),
+                    },
+                )),
+                None,
+            ));
+        }
+        // The intrinsic gas is charged from the gas of the call, but not from
+        // the stipend.
+        let base_gas = U256::from(self.spec.cross_space_call_gas);
+        if cross_vm_params.gas < base_gas {
+            return Ok((
+                PreCheckResult::Fail(ExecutionOutcome::NotExecutedDrop(
+                    TxDropError::NotEnoughBaseGas {
+                        expected: self.spec.cross_space_call_gas as u64,
+                        actual: cross_vm_params.gas.low_u64(),
+                    },
+                )),
+                None,
+            ));
+        }
+        let gas = cross_vm_params.frame_gas() - base_gas;
         let CrossVMParams {
+            action,
             receiver,
-            gas,
             gas_price,
             value,
+            mapped_sender,
+            salt,
+            code_address,
+            call_type,
+            max_steps,
+            target_space,
             ..
         } = cross_vm_params;
-        let address = receiver.with_evm_space();
-
-        let params = ActionParams {
-            space: address.space,
-            code_address: address.address,
-            address: address.address,
-            sender: *CROSS_SPACE_CONTRACT_ADDRESS,
-            original_sender: Address::zero(),
-            gas,
-            gas_price,
-            value: ActionValue::Transfer(value),
-            code: self.state.code(&address)?,
-            code_hash: self.state.code_hash(&address)?,
-            data: Some(data),
-            call_type: CallType::Call,
-            create_type: CreateType::None,
-            params_type: vm::ParamsType::Separate,
+        let original_sender = mapped_sender.unwrap_or_default();
+
+        let (top_frame, new_address) = match action {
+            CrossVMAction::Create(code) if code.len() > self.spec.init_code_data_limit => {
+                return Ok((
+                    PreCheckResult::Fail(ExecutionOutcome::NotExecutedDrop(
+                        TxDropError::InitCodeTooLarge {
+                            limit: self.spec.init_code_data_limit,
+                            actual: code.len(),
+                        },
+                    )),
+                    None,
+                ));
+            },
+            CrossVMAction::Create(code) => {
+                let sender = CROSS_SPACE_CONTRACT_ADDRESS.with_space(target_space);
+                let (deployer, nonce) = match mapped_sender {
+                    Some(mapped_sender) => (
+                        mapped_sender.with_space(target_space),
+                        self.mapped_nonce(&mapped_sender)?,
+                    ),
+                    None => (sender, self.state.nonce(&sender)?),
+                };
+                let (address_scheme, create_type) = match salt {
+                    Some(salt) => (
+                        CreateContractAddress::FromSenderSaltAndCodeHash(salt),
+                        CreateType::CREATE2,
+                    ),
+                    None => (CreateContractAddress::FromSenderNonce, CreateType::CREATE),
+                };
+                let (new_address, _code_hash) = contract_address(
+                    address_scheme,
+                    self.env.number.into(),
+                    &deployer,
+                    &nonce,
+                    &code,
+                );
+                // Advance the nonce like the CREATE opcode does, so the next
+                // deployment gets a distinct address.
+                match mapped_sender {
+                    Some(mapped_sender) => self.set_mapped_nonce(&mapped_sender, nonce + 1)?,
+                    None => self
+                        .state
+                        .inc_nonce(&sender, &self.spec.account_start_nonce)?,
+                }
+
+                let params = ActionParams {
+                    space: new_address.space,
+                    code_address: new_address.address,
+                    code_hash: None,
+                    address: new_address.address,
+                    sender: sender.address,
+                    original_sender,
+                    gas,
+                    gas_price,
+                    value: ActionValue::Transfer(value),
+                    code: Some(Arc::new(code)),
+                    data: None,
+                    call_type: CallType::None,
+                    create_type,
+                    params_type: vm::ParamsType::Embedded,
+                };
+                let top_frame = CallCreateFrame::new_create_raw(
+                    params,
+                    self.env,
+                    self.machine,
+                    self.spec,
+                    &self.factory,
+                    0,     /* depth */
+                    false, /* static_flag */
+                );
+                (top_frame, Some(new_address.address))
+            },
+            CrossVMAction::Call => {
+                let address = receiver.with_space(target_space);
+                let code_address = code_address.unwrap_or(receiver).with_space(target_space);
+                let (code, code_hash) = self.cached_code(&code_address)?;
+                let params = ActionParams {
+                    space: address.space,
+                    code_address: code_address.address,
+                    address: address.address,
+                    sender: *CROSS_SPACE_CONTRACT_ADDRESS,
+                    original_sender,
+                    gas,
+                    gas_price,
+                    value: match call_type {
+                        CallType::DelegateCall => ActionValue::Apparent(value),
+                        _ => ActionValue::Transfer(value),
+                    },
+                    code,
+                    code_hash,
+                    data: Some(data),
+                    call_type,
+                    create_type: CreateType::None,
+                    params_type: vm::ParamsType::Separate,
+                };
+                let top_frame = CallCreateFrame::new_call_raw(
+                    params,
+                    self.env,
+                    self.machine,
+                    self.spec,
+                    &self.factory,
+                    0,     /* depth */
+                    false, /* static_flag */
+                );
+                (top_frame, None)
+            },
         };
-        let top_frame = CallCreateFrame::new_call_raw(
-            params,
-            self.env,
-            self.machine,
-            self.spec,
-            &self.factory,
-            0,     /* depth */
-            false, /* static_flag */
-        );
+        let cross_space = CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space();
         let mut substate = Substate::new();
         self.state.add_balance(
-            &CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space(),
+            &cross_space,
             &value,
             cleanup_mode(&mut substate, &self.spec),
             self.spec.account_start_nonce,
         )?;
+        observer.as_state_tracer().trace_internal_transfer(
+            AddressPocket::MoveSpace,
+            AddressPocket::Balance(cross_space),
+            value,
+        );
 
         let frame_stack = FrameStack::new(
             self.state,
             Substate::new(),
-            MultiObservers::with_no_tracing(),
-            0,
-        );
+            observer,
+            self.spec.cross_space_call_gas as u64,
+            self.spec.max_depth,
+        )
+        .with_cross_space_depth(self.cross_space_depth + 1)
+        .with_max_steps(max_steps);
 
-        Ok(PreCheckResult::Pass {
-            top_frame,
-            frame_stack,
-        })
+        Ok((
+            PreCheckResult::Pass {
+                top_frame,
+                frame_stack,
+            },
+            new_address,
+        ))
     }
 
     // TODO: maybe we can find a better interface for doing the suicide
     // post-processing.
-    fn kill_process(
+    /// Removes the suicided contracts and burns their remaining balances.
+    /// Returns the total amount of tokens burned.
+    pub(super) fn kill_process(
         &mut self,
         suicides: &HashSet<AddressWithSpace>,
         tracer: &mut dyn StateTracer,
-    ) -> DbResult<Substate> {
+    ) -> DbResult<(Substate, U256)> {
         let substate = Substate::new();
+        let mut tokens_burned = U256::zero();
+
+        // Process the contracts in address order so that the traces do not
+        // depend on the iteration order of the hash set.
+        let mut suicides: Vec<_> = suicides.iter().collect();
+        suicides.sort();
 
         for contract_address in suicides {
             let contract_balance = self.state.balance(contract_address)?;
@@ -545,8 +1773,9 @@ impl<'a> TXExecutor<'a> {
 
             self.state.remove_contract(contract_address)?;
             self.state.subtract_total_issued(contract_balance);
+            tokens_burned += contract_balance;
         }
 
-        Ok(substate)
+        Ok((substate, tokens_burned))
     }
 }
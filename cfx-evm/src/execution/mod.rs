@@ -1,11 +1,21 @@
+mod cross_vm_args;
 mod estimate;
 pub mod executed;
 mod executor;
 mod options;
+mod receipt_cache;
 mod transaction_info;
 
+#[cfg(test)]
+mod tests;
+
+pub use cross_vm_args::{cross_vm_function_signature, encode_cross_vm_args, CrossVMArg};
 pub use estimate::EstimateRequest;
 pub use executed::*;
-pub use executor::{gas_required_for, CrossVMParams, CrossVMReturn, TXExecutor};
-pub use options::{TransactCheckSettings, TransactOptions};
+pub use executor::{
+    batch_contracts_created, gas_required_for, mapped_sender_address, CrossVMAction, CrossVMParams,
+    CrossVMParamsBuilder, CrossVMReturn, TXExecutor,
+};
+pub use options::{EnvOverride, TraceLevel, TransactCheckSettings, TransactOptions};
+pub use receipt_cache::ReceiptCache;
 pub use transaction_info::TransactionInfo;
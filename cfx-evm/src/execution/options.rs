@@ -1,10 +1,32 @@
 use super::estimate::EstimateRequest;
-use crate::observer::MultiObservers as Observer;
+use crate::{observer::MultiObservers as Observer, vm::Env};
+use cfx_types::U256;
+use primitives::BlockNumber;
 
 /// Transaction execution options.
 pub struct TransactOptions {
     pub observer: Observer,
     pub check_settings: TransactCheckSettings,
+    pub trace_level: TraceLevel,
+}
+
+/// What the executor collects for `Executed::trace` and `Executed::logs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceLevel {
+    /// Neither the traces nor the logs are collected.
+    None,
+    /// Only the logs are collected. The tracer of the observer is dropped
+    /// before execution.
+    LogsOnly,
+    /// Both the traces (if the observer has a tracer) and the logs are
+    /// collected.
+    Full,
+}
+
+impl Default for TraceLevel {
+    fn default() -> Self {
+        TraceLevel::Full
+    }
 }
 
 impl TransactOptions {
@@ -12,6 +34,7 @@ impl TransactOptions {
         Self {
             observer: Observer::with_tracing(),
             check_settings: TransactCheckSettings::all_checks(),
+            trace_level: TraceLevel::Full,
         }
     }
 
@@ -19,6 +42,7 @@ impl TransactOptions {
         Self {
             observer: Observer::with_no_tracing(),
             check_settings: TransactCheckSettings::all_checks(),
+            trace_level: TraceLevel::Full,
         }
     }
 
@@ -26,14 +50,28 @@ impl TransactOptions {
         Self {
             observer: Observer::virtual_call(),
             check_settings: TransactCheckSettings::from_estimate_request(request),
+            trace_level: TraceLevel::Full,
         }
     }
+
+    pub fn with_trace_level(mut self, trace_level: TraceLevel) -> Self {
+        self.trace_level = trace_level;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct TransactCheckSettings {
     pub charge_gas: bool,
     pub real_execution: bool,
+    /// Whether to reject the transaction if its gas limit exceeds the gas
+    /// remaining in the block.
+    pub check_block_gas_limit: bool,
+    /// Whether to credit the transaction fee, except the burnt base fee, to
+    /// the block author (`Env::author`). Otherwise the fee is not paid to
+    /// anyone. It is on for the real executions and off for the estimations.
+    /// A zero author is never credited.
+    pub credit_fees: bool,
 }
 
 impl TransactCheckSettings {
@@ -41,6 +79,8 @@ impl TransactCheckSettings {
         Self {
             charge_gas: true,
             real_execution: true,
+            check_block_gas_limit: true,
+            credit_fees: true,
         }
     }
 
@@ -48,6 +88,39 @@ impl TransactCheckSettings {
         Self {
             charge_gas: request.charge_gas(),
             real_execution: false,
+            check_block_gas_limit: false,
+            credit_fees: false,
+        }
+    }
+}
+
+/// Replaces some fields of the `Env` of a single execution, e.g., to simulate
+/// a transaction in the environment of a past block. The fields left as
+/// `None` are taken from the original `Env`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvOverride {
+    pub number: Option<BlockNumber>,
+    /// The block number seen by the `NUMBER` opcode of the EVM space.
+    pub epoch_height: Option<u64>,
+    pub timestamp: Option<u64>,
+    pub base_fee_per_gas: Option<U256>,
+}
+
+impl EnvOverride {
+    pub fn apply(&self, env: &Env) -> Env {
+        let mut env = env.clone();
+        if let Some(number) = self.number {
+            env.number = number;
+        }
+        if let Some(epoch_height) = self.epoch_height {
+            env.epoch_height = epoch_height;
+        }
+        if let Some(timestamp) = self.timestamp {
+            env.timestamp = timestamp;
+        }
+        if let Some(base_fee_per_gas) = self.base_fee_per_gas {
+            env.base_fee_per_gas = Some(base_fee_per_gas);
         }
+        env
     }
 }
@@ -0,0 +1,69 @@
+use cfx_types::H256;
+use std::collections::{BTreeMap, HashMap};
+
+/// The hashes of the transactions executed successfully, used to detect a
+/// transaction submitted again (see `TXExecutor::with_receipt_cache`). It
+/// keeps at most `capacity` hashes, and the least recently used hash is
+/// evicted first.
+#[derive(Debug)]
+pub struct ReceiptCache {
+    capacity: usize,
+    /// The last use of each hash.
+    last_used: HashMap<H256, u64>,
+    /// The hashes ordered by their last use.
+    by_last_use: BTreeMap<u64, H256>,
+    clock: u64,
+}
+
+impl ReceiptCache {
+    pub fn new(capacity: usize) -> Self {
+        ReceiptCache {
+            capacity,
+            last_used: HashMap::new(),
+            by_last_use: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.last_used.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.last_used.is_empty()
+    }
+
+    /// Whether the transaction is recorded. A hit counts as a use of the
+    /// hash.
+    pub fn contains(&mut self, tx_hash: &H256) -> bool {
+        if !self.last_used.contains_key(tx_hash) {
+            return false;
+        }
+        self.touch(*tx_hash);
+        true
+    }
+
+    /// Records an executed transaction, and evicts the least recently used
+    /// hash if the cache is full.
+    pub fn insert(&mut self, tx_hash: H256) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.touch(tx_hash);
+        while self.last_used.len() > self.capacity {
+            let (_, evicted) = self
+                .by_last_use
+                .pop_first()
+                .expect("The cache is not empty");
+            self.last_used.remove(&evicted);
+        }
+    }
+
+    fn touch(&mut self, tx_hash: H256) {
+        self.clock += 1;
+        if let Some(last_use) = self.last_used.insert(tx_hash, self.clock) {
+            self.by_last_use.remove(&last_use);
+        }
+        self.by_last_use.insert(self.clock, tx_hash);
+    }
+}
@@ -0,0 +1,2722 @@
+// Copyright 2019 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use super::{
+    batch_contracts_created, cross_vm_function_signature, encode_cross_vm_args,
+    executor::{compute_gas_settlement, validate_function_name},
+    gas_required_for, mapped_sender_address, move_abort_decode, revert_reason_decode,
+    CrossVMAction, CrossVMArg, CrossVMParams, CrossVMParamsBuilder, CrossVMReturn, EnvOverride,
+    EstimateRequest, ExecutionError, ExecutionOutcome, MoveAbortInfo, ReceiptCache, TXExecutor,
+    ToRepackError, TraceLevel, TransactOptions, TransactionInfo, TxDropError,
+};
+use crate::{
+    call_create_frame::{contract_address, CallCreateFrame, FrameReturn, FrameStack},
+    evm::FinalizationResult,
+    machine::{new_machine_with_builtin, Machine},
+    observer::{
+        trace::{Action, InternalTransferAction},
+        AddressPocket, ExecutiveTracer, MultiObservers, StateTracer, VmObserve,
+    },
+    state::{FrameStackInfo, State, Substate},
+    vm::{
+        self, ActionParams, ActionValue, CallType, CleanDustMode, CreateContractAddress,
+        CreateType, Env, RefundCap, ReturnData, Spec, TrapResult,
+    },
+    vm_factory::VmFactory,
+};
+use cfx_parameters::internal_contract_addresses::CROSS_SPACE_CONTRACT_ADDRESS;
+use cfx_state::{
+    state_trait::{CheckpointTrait, StateOpsTrait},
+    CallMoveVMError, CallMoveVMOutput, CallMoveVMTrait, CleanupMode,
+};
+use cfx_statedb::StateDb;
+use cfx_storage::InMemoryDb;
+use cfx_types::{Address, AddressSpaceUtil, AddressWithSpace, Space, H256, U256};
+use move_core_types::language_storage::TypeTag;
+use parking_lot::Mutex;
+use primitives::{CrossSpaceReceipt, LogEntry};
+use solidity_abi::{ABIDecodable, ABIEncodable};
+use std::{borrow::Cow, cell::RefCell, rc::Rc, sync::Arc};
+
+fn cross_vm_params(action: CrossVMAction, receiver: Address, value: U256) -> CrossVMParams {
+    CrossVMParams {
+        action,
+        receiver,
+        function_name: String::new(),
+        gas: U256::from(1_000_000),
+        gas_price: U256::zero(),
+        value,
+        evm_params: vec![],
+        caller_info: "0x1::test::Test".to_string(),
+        mapped_sender: None,
+        return_types: vec![],
+        min_stipend: None,
+        salt: None,
+        raw_calldata: None,
+        code_address: None,
+        call_type: CallType::Call,
+        require_contract: false,
+        max_steps: None,
+        target_space: Space::Ethereum,
+        sponsor: None,
+        typed_args: None,
+    }
+}
+
+/// The machine, the environment and the state most tests run with.
+struct TestContext<'a> {
+    machine: Machine,
+    env: Env,
+    spec: Spec,
+    state: State<'a>,
+}
+
+fn setup() -> TestContext<'static> {
+    setup_with_env(Env::default())
+}
+
+fn setup_with_env(env: Env) -> TestContext<'static> {
+    TestContext::new(env, State::new(StateDb::new(InMemoryDb::new())).unwrap())
+}
+
+fn setup_with_move_vm(move_vm: &mut dyn CallMoveVMTrait) -> TestContext<'_> {
+    let state = State::new_with_move_vm(StateDb::new(InMemoryDb::new()), move_vm).unwrap();
+    TestContext::new(Env::default(), state)
+}
+
+impl<'a> TestContext<'a> {
+    fn new(env: Env, state: State<'a>) -> Self {
+        let machine = new_machine_with_builtin(Default::default(), VmFactory::new(1024 * 32));
+        let spec = machine.spec(env.number);
+        TestContext {
+            machine,
+            env,
+            spec,
+            state,
+        }
+    }
+
+    fn executor(&mut self) -> TXExecutor<'_> {
+        TXExecutor::new(&mut self.state, &self.env, &self.machine, &self.spec)
+    }
+
+    /// An executor for the cross-space calls made after `cross_space_depth`
+    /// EVM-Move transitions.
+    fn executor_at_depth(&mut self, cross_space_depth: usize) -> TXExecutor<'_> {
+        let mut executor = self.executor();
+        executor.set_cross_space_depth(cross_space_depth);
+        executor
+    }
+
+    /// Deploy a contract with the runtime code by a cross-space create, and
+    /// return the contract address.
+    fn deploy(&mut self, runtime_code: &[u8]) -> Address {
+        // PUSH1 <len> DUP1 PUSH1 0x0b PUSH1 0x00 CODECOPY PUSH1 0x00 RETURN
+        let mut init_code = vec![
+            0x60,
+            runtime_code.len() as u8,
+            0x80,
+            0x60,
+            0x0b,
+            0x60,
+            0x00,
+            0x39,
+            0x60,
+            0x00,
+            0xf3,
+        ];
+        init_code.extend_from_slice(runtime_code);
+        let output = self
+            .executor()
+            .cross_vm_call(cross_vm_params(
+                CrossVMAction::Create(init_code),
+                Address::zero(),
+                U256::zero(),
+            ))
+            .unwrap();
+        output.created_address.unwrap()
+    }
+}
+
+#[test]
+fn test_cross_vm_call_refund_value_on_revert() {
+    let mut ctx = setup();
+    let cross_space = CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space();
+    let value = U256::from(1_000_000_000);
+
+    // PUSH1 0x00 PUSH1 0x00 REVERT
+    let init_code = vec![0x60, 0x00, 0x60, 0x00, 0xfd];
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(
+            CrossVMAction::Create(init_code),
+            Address::zero(),
+            value,
+        ))
+        .unwrap();
+    assert!(matches!(output.result, Ok(ref r) if !r.apply_state));
+    assert_eq!(output.created_address, None);
+    assert_eq!(ctx.state.balance(&cross_space).unwrap(), U256::zero());
+
+    // INVALID
+    let init_code = vec![0xfe];
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(
+            CrossVMAction::Create(init_code),
+            Address::zero(),
+            value,
+        ))
+        .unwrap();
+    assert!(output.result.is_err());
+    assert_eq!(ctx.state.balance(&cross_space).unwrap(), U256::zero());
+
+    // The value is transferred to the receiver if the call succeeds.
+    let receiver = Address::from_low_u64_be(1024);
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, receiver, value))
+        .unwrap();
+    assert!(matches!(output.result, Ok(ref r) if r.apply_state));
+    assert_eq!(ctx.state.balance(&cross_space).unwrap(), U256::zero());
+    assert_eq!(
+        ctx.state.balance(&receiver.with_evm_space()).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn test_gas_required_for_access_list() {
+    // Ethereum mainnet charges 16 gas for a non-zero data byte since Istanbul.
+    let mut spec = Spec::genesis_spec();
+    spec.tx_data_non_zero_gas = 16;
+
+    let access_list = vec![
+        (
+            Address::from_low_u64_be(1),
+            vec![H256::zero(), H256::from_low_u64_be(1)],
+        ),
+        (Address::from_low_u64_be(2), vec![]),
+    ];
+    assert_eq!(
+        gas_required_for(false, &[], &access_list[..1], &spec),
+        27200
+    );
+    assert_eq!(gas_required_for(false, &[0, 1], &access_list, &spec), 29620);
+    assert_eq!(gas_required_for(true, &[], &access_list, &spec), 61600);
+}
+
+#[test]
+fn test_kill_process_reports_tokens_burned() {
+    let mut ctx = setup();
+
+    let contract_a = Address::from_low_u64_be(1024).with_evm_space();
+    let contract_b = Address::from_low_u64_be(2048).with_evm_space();
+    for (contract, balance) in [(&contract_a, 300u64), (&contract_b, 700u64)] {
+        ctx.state
+            .new_contract(contract, U256::from(balance), U256::zero(), None)
+            .unwrap();
+    }
+    ctx.state.add_total_issued(U256::from(5_000));
+
+    let suicides = [contract_a, contract_b].into_iter().collect();
+    let (_, tokens_burned) = ctx.executor().kill_process(&suicides, &mut ()).unwrap();
+    assert_eq!(tokens_burned, U256::from(1_000));
+    assert_eq!(ctx.state.total_issued_tokens(), U256::from(4_000));
+    assert_eq!(ctx.state.balance(&contract_a).unwrap(), U256::zero());
+    assert_eq!(ctx.state.balance(&contract_b).unwrap(), U256::zero());
+}
+
+#[test]
+fn test_kill_process_trace_order() {
+    let contracts: Vec<_> = (1..=16u64)
+        .rev()
+        .map(|i| Address::from_low_u64_be(i * 1024).with_evm_space())
+        .collect();
+
+    let kill_all = || {
+        let mut ctx = setup();
+        for contract in &contracts {
+            ctx.state
+                .new_contract(contract, U256::from(100), U256::zero(), None)
+                .unwrap();
+        }
+        let mut tracer = ExecutiveTracer::default();
+        ctx.executor()
+            .kill_process(&contracts.iter().cloned().collect(), &mut tracer)
+            .unwrap();
+        tracer
+            .drain()
+            .into_iter()
+            .map(|trace| match trace.action {
+                Action::InternalTransferAction(InternalTransferAction {
+                    from: AddressPocket::Balance(address),
+                    ..
+                }) => address,
+                action => panic!("unexpected trace {:?}", action),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut sorted = contracts.clone();
+    sorted.sort();
+    for _ in 0..8 {
+        assert_eq!(kill_all(), sorted);
+    }
+}
+
+struct CreateTransaction {
+    sender: AddressWithSpace,
+    init_code: Vec<u8>,
+    gas_price: U256,
+    hash: Option<H256>,
+}
+
+impl TransactionInfo for CreateTransaction {
+    fn sender(&self) -> Cow<AddressWithSpace> {
+        Cow::Borrowed(&self.sender)
+    }
+
+    fn nonce(&self) -> Cow<U256> {
+        Cow::Owned(U256::zero())
+    }
+
+    fn gas(&self) -> Cow<U256> {
+        Cow::Owned(U256::from(10_000_000))
+    }
+
+    fn gas_price(&self) -> Cow<U256> {
+        Cow::Borrowed(&self.gas_price)
+    }
+
+    fn data(&self) -> Cow<[u8]> {
+        Cow::Borrowed(&self.init_code)
+    }
+
+    fn action(&self) -> Cow<primitives::transaction::Action> {
+        Cow::Owned(primitives::transaction::Action::Create)
+    }
+
+    fn value(&self) -> Cow<U256> {
+        Cow::Owned(U256::zero())
+    }
+
+    fn hash(&self) -> Option<H256> {
+        self.hash
+    }
+}
+
+#[test]
+fn test_init_code_size_limit() {
+    let mut ctx = setup();
+    let limit = ctx.spec.init_code_data_limit;
+
+    // The init code at the limit is accepted.
+    let tx = CreateTransaction {
+        sender: Address::from_low_u64_be(1025).with_evm_space(),
+        init_code: vec![0; limit],
+        gas_price: U256::zero(),
+        hash: None,
+    };
+    let outcome = ctx
+        .executor()
+        .transact(&tx, TransactOptions::exec_with_no_tracing())
+        .unwrap();
+    assert!(outcome.successfully_executed().is_some());
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(
+            CrossVMAction::Create(vec![0; limit]),
+            Address::zero(),
+            U256::zero(),
+        ))
+        .unwrap();
+    assert!(output.result.unwrap().apply_state);
+
+    let tx = CreateTransaction {
+        sender: Address::from_low_u64_be(1024).with_evm_space(),
+        init_code: vec![0; limit + 1],
+        gas_price: U256::zero(),
+        hash: None,
+    };
+    let outcome = ctx
+        .executor()
+        .transact(&tx, TransactOptions::exec_with_no_tracing())
+        .unwrap();
+    assert!(matches!(
+        outcome,
+        ExecutionOutcome::NotExecutedDrop(TxDropError::InitCodeTooLarge { limit: l, actual })
+            if l == limit && actual == limit + 1
+    ));
+
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(
+            CrossVMAction::Create(vec![0; limit + 1]),
+            Address::zero(),
+            U256::zero(),
+        ))
+        .unwrap();
+    assert_eq!(
+        output.result.unwrap_err(),
+        vm::Error::InitCodeTooLarge {
+            limit,
+            actual: limit + 1
+        }
+    );
+}
+
+#[test]
+fn test_compute_gas_settlement() {
+    let gas_limit = U256::from(100_000);
+    let gas_price = U256::from(2);
+    let spec = Spec::genesis_spec();
+    let settle = |gas_left: u64| {
+        let (gas_charged, fees_value, refund_value) =
+            compute_gas_settlement(gas_limit, U256::from(gas_left), gas_price, &spec);
+        assert_eq!(fees_value, gas_charged * gas_price);
+        assert_eq!(fees_value + refund_value, gas_limit * gas_price);
+        (gas_charged.as_u64(), refund_value.as_u64())
+    };
+
+    // Exactly 1/4 of the gas limit is left.
+    assert_eq!(settle(25_000), (75_000, 50_000));
+    // More than 1/4 is left, so only 1/4 of the gas limit is refunded.
+    assert_eq!(settle(25_001), (75_000, 50_000));
+    assert_eq!(settle(100_000), (75_000, 50_000));
+    // Less than 1/4 is left, so the gas used is charged.
+    assert_eq!(settle(24_999), (75_001, 49_998));
+    assert_eq!(settle(0), (100_000, 0));
+}
+
+#[test]
+fn test_compute_gas_settlement_refund_cap() {
+    let gas_limit = U256::from(100_000);
+    let gas_price = U256::from(2);
+    let settle = |spec: &Spec, gas_left: u64| {
+        let (gas_charged, fees_value, refund_value) =
+            compute_gas_settlement(gas_limit, U256::from(gas_left), gas_price, spec);
+        assert_eq!(fees_value + refund_value, gas_limit * gas_price);
+        (gas_charged.as_u64(), refund_value.as_u64())
+    };
+
+    let conflux = Spec::genesis_spec();
+    let mut eip3529 = Spec::genesis_spec();
+    eip3529.refund_cap = RefundCap::GasUsed;
+    eip3529.max_refund_quotient = 5;
+
+    // 60,000 gas is used. The refund is capped by 1/4 of the gas limit with
+    // the Conflux rule, and by 1/5 of the gas used with the EIP-3529 cap.
+    assert_eq!(settle(&conflux, 40_000), (75_000, 50_000));
+    assert_eq!(settle(&eip3529, 40_000), (88_000, 24_000));
+    // 95,000 gas is used, the whole gas left is refunded with both caps.
+    assert_eq!(settle(&conflux, 5_000), (95_000, 10_000));
+    assert_eq!(settle(&eip3529, 5_000), (95_000, 10_000));
+    // 80,000 gas is used. The EIP-3529 cap refunds 16,000 of the 20,000 gas
+    // left, which is within the Conflux cap.
+    assert_eq!(settle(&conflux, 20_000), (80_000, 40_000));
+    assert_eq!(settle(&eip3529, 20_000), (84_000, 32_000));
+}
+
+#[test]
+fn test_cross_vm_pre_check_failure() {
+    let mut ctx = setup();
+    let init_code = vec![0; ctx.spec.init_code_data_limit + 1];
+    let gas = U256::from(1_000_000);
+
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(
+            CrossVMAction::Create(init_code.clone()),
+            Address::zero(),
+            U256::zero(),
+        ))
+        .unwrap();
+    assert!(output.result.is_err());
+    assert_eq!(output.gas_used, gas);
+    assert_eq!(output.created_address, None);
+
+    let estimated = ctx.executor().cross_vm_estimate(cross_vm_params(
+        CrossVMAction::Create(init_code),
+        Address::zero(),
+        U256::zero(),
+    ));
+    assert!(estimated.is_err());
+}
+
+#[test]
+fn test_cross_vm_create_with_salt() {
+    let mut ctx = setup();
+
+    // PUSH1 0x00 PUSH1 0x00 RETURN
+    let init_code = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+    let salt = H256::from_low_u64_be(42);
+    let mut buffer = vec![0xff];
+    buffer.extend_from_slice(CROSS_SPACE_CONTRACT_ADDRESS.as_bytes());
+    buffer.extend_from_slice(salt.as_bytes());
+    buffer.extend_from_slice(keccak_hash::keccak(&init_code).as_bytes());
+    let expected = Address::from(keccak_hash::keccak(&buffer));
+
+    let mut params = cross_vm_params(
+        CrossVMAction::Create(init_code),
+        Address::zero(),
+        U256::zero(),
+    );
+    params.salt = Some(salt);
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(matches!(output.result, Ok(ref r) if r.apply_state));
+    assert_eq!(output.created_address, Some(expected));
+    assert!(ctx.state.exists(&expected.with_evm_space()).unwrap());
+}
+
+#[test]
+fn test_cross_vm_call_with_raw_calldata() {
+    let mut ctx = setup();
+
+    // Runtime code echoing the calldata:
+    // CALLDATASIZE PUSH1 0x00 PUSH1 0x00 CALLDATACOPY CALLDATASIZE PUSH1 0x00
+    // RETURN
+    let runtime_code = vec![0x36, 0x60, 0x00, 0x60, 0x00, 0x37, 0x36, 0x60, 0x00, 0xf3];
+    let contract = ctx.deploy(&runtime_code);
+
+    // The raw calldata takes precedence over the function name.
+    let raw_calldata = vec![0xa9, 0x05, 0x9c, 0xbb, 0x01, 0x02, 0x03];
+    let mut params = cross_vm_params(CrossVMAction::Call, contract, U256::zero());
+    params.function_name = "transfer".to_string();
+    params.raw_calldata = Some(raw_calldata.clone());
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    match output.result {
+        Ok(ref r) => {
+            assert!(r.apply_state);
+            assert_eq!(&*r.return_data, &raw_calldata[..]);
+        },
+        Err(e) => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn test_cross_vm_call_returns_logs() {
+    let mut ctx = setup();
+
+    // Runtime code emitting two events with topics 1 and 2:
+    // PUSH1 0x01 PUSH1 0x00 PUSH1 0x00 LOG1 PUSH1 0x02 PUSH1 0x00 PUSH1 0x00
+    // LOG1 STOP
+    let runtime_code = vec![
+        0x60, 0x01, 0x60, 0x00, 0x60, 0x00, 0xa1, 0x60, 0x02, 0x60, 0x00, 0x60, 0x00, 0xa1, 0x00,
+    ];
+    let contract = ctx.deploy(&runtime_code);
+
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, contract, U256::zero()))
+        .unwrap();
+    assert!(output.result.unwrap().apply_state);
+    let topics: Vec<H256> = output
+        .logs
+        .iter()
+        .filter(|log| log.address == contract)
+        .flat_map(|log| log.topics.clone())
+        .collect();
+    assert_eq!(
+        topics,
+        vec![H256::from_low_u64_be(1), H256::from_low_u64_be(2)]
+    );
+    // The logs are copied, the substate still holds them.
+    assert_eq!(output.logs, output.substate.logs);
+}
+
+#[test]
+fn test_validate_function_name() {
+    // An empty name denotes a plain transfer.
+    assert!(validate_function_name("").is_ok());
+    for name in ["transfer", "_transfer", "balanceOf", "f2", "__"] {
+        assert!(validate_function_name(name).is_ok(), "{}", name);
+    }
+    for name in [
+        "transfer(address,uint256)",
+        "2f",
+        "a b",
+        "foo-bar",
+        "\u{e9}t\u{e9}",
+        " f",
+    ] {
+        assert_eq!(
+            validate_function_name(name),
+            Err(vm::Error::InvalidFunctionName(name.to_string()))
+        );
+    }
+}
+
+#[test]
+fn test_cross_vm_call_invalid_function_name() {
+    let mut ctx = setup();
+    let receiver = Address::from_low_u64_be(1024);
+    let name = "transfer(address,uint256)".to_string();
+
+    let mut params = cross_vm_params(CrossVMAction::Call, receiver, U256::zero());
+    params.function_name = name.clone();
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert_eq!(
+        output.result.err(),
+        Some(vm::Error::InvalidFunctionName(name.clone()))
+    );
+    assert_eq!(output.gas_used, U256::from(1_000_000));
+
+    let mut params = cross_vm_params(CrossVMAction::Call, receiver, U256::zero());
+    params.function_name = name;
+    let estimated = ctx.executor().cross_vm_estimate(params);
+    assert!(estimated.is_err());
+
+    // The function name is ignored if the raw calldata is given.
+    let mut params = cross_vm_params(CrossVMAction::Call, receiver, U256::zero());
+    params.function_name = "transfer(address,uint256)".to_string();
+    params.raw_calldata = Some(vec![]);
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(matches!(output.result, Ok(ref r) if r.apply_state));
+}
+
+#[test]
+fn test_cross_vm_calldata_size_limit() {
+    let mut ctx = setup();
+    ctx.spec.max_cross_space_calldata_size = 64;
+    let receiver = Address::from_low_u64_be(1024);
+
+    let mut params = cross_vm_params(CrossVMAction::Call, receiver, U256::zero());
+    params.raw_calldata = Some(vec![0; 64]);
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(matches!(output.result, Ok(ref r) if r.apply_state));
+
+    let mut params = cross_vm_params(CrossVMAction::Call, receiver, U256::zero());
+    params.raw_calldata = Some(vec![0; 65]);
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert_eq!(
+        output.result.err(),
+        Some(vm::Error::CrossSpaceCalldataTooLarge {
+            limit: 64,
+            actual: 65
+        })
+    );
+    assert_eq!(output.gas_used, U256::from(1_000_000));
+
+    // The ABI-encoded caller info counts towards the limit as well.
+    let mut params = cross_vm_params(CrossVMAction::Call, receiver, U256::zero());
+    params.function_name = "test".to_string();
+    params.caller_info = "a".repeat(1024);
+    let estimated = ctx.executor().cross_vm_estimate(params);
+    assert!(estimated.is_err());
+}
+
+#[test]
+fn test_cross_vm_call_with_code_address() {
+    let mut ctx = setup();
+
+    // Runtime code: PUSH1 0x01 PUSH1 0x00 SSTORE STOP
+    let runtime_code = vec![0x60, 0x01, 0x60, 0x00, 0x55, 0x00];
+    let logic = ctx.deploy(&runtime_code);
+
+    let proxy = Address::from_low_u64_be(1024);
+    ctx.state
+        .new_contract(&proxy.with_evm_space(), U256::zero(), U256::zero(), None)
+        .unwrap();
+    let mut params = cross_vm_params(CrossVMAction::Call, proxy, U256::zero());
+    params.code_address = Some(logic);
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(matches!(output.result, Ok(ref r) if r.apply_state));
+
+    // The code of the logic contract writes to the storage of the proxy.
+    let key = vec![0u8; 32];
+    assert_eq!(
+        ctx.state.storage_at(&proxy.with_evm_space(), &key).unwrap(),
+        U256::one()
+    );
+    assert_eq!(
+        ctx.state.storage_at(&logic.with_evm_space(), &key).unwrap(),
+        U256::zero()
+    );
+}
+
+#[test]
+fn test_cross_vm_delegate_call() {
+    let mut ctx = setup();
+
+    // Runtime code: CALLER PUSH1 0x00 SSTORE STOP
+    let runtime_code = vec![0x33, 0x60, 0x00, 0x55, 0x00];
+    let logic = ctx.deploy(&runtime_code);
+    let proxy = Address::from_low_u64_be(1024);
+    ctx.state
+        .new_contract(&proxy.with_evm_space(), U256::zero(), U256::zero(), None)
+        .unwrap();
+
+    let mut params = cross_vm_params(CrossVMAction::Call, proxy, U256::zero());
+    params.code_address = Some(logic);
+    params.call_type = CallType::DelegateCall;
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(matches!(output.result, Ok(ref r) if r.apply_state));
+    let key = vec![0u8; 32];
+    let caller = ctx.state.storage_at(&proxy.with_evm_space(), &key).unwrap();
+    assert_eq!(
+        caller,
+        U256::from_big_endian(H256::from(*CROSS_SPACE_CONTRACT_ADDRESS).as_bytes())
+    );
+
+    // A delegate call can not carry value.
+    let mut params = cross_vm_params(CrossVMAction::Call, proxy, U256::from(1));
+    params.code_address = Some(logic);
+    params.call_type = CallType::DelegateCall;
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert_eq!(
+        output.result.err(),
+        Some(vm::Error::CrossSpaceDelegateCallWithValue)
+    );
+
+    let mut params = cross_vm_params(CrossVMAction::Call, proxy, U256::zero());
+    params.call_type = CallType::CallCode;
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert_eq!(
+        output.result.err(),
+        Some(vm::Error::UnsupportedCrossSpaceCallType(CallType::CallCode))
+    );
+}
+
+#[test]
+fn test_cross_vm_create_advances_nonce() {
+    let mut ctx = setup();
+    let cross_space = CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space();
+    let nonce = ctx.state.nonce(&cross_space).unwrap();
+
+    // PUSH1 0x00 PUSH1 0x00 RETURN
+    let init_code = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+    let calls = vec![
+        cross_vm_params(
+            CrossVMAction::Create(init_code.clone()),
+            Address::zero(),
+            U256::zero(),
+        ),
+        cross_vm_params(
+            CrossVMAction::Create(init_code),
+            Address::zero(),
+            U256::zero(),
+        ),
+    ];
+    let returns = ctx.executor().cross_vm_call_batch(calls).unwrap();
+    let addresses: Vec<_> = returns.iter().map(|r| r.created_address.unwrap()).collect();
+    assert_ne!(addresses[0], addresses[1]);
+    assert_eq!(ctx.state.nonce(&cross_space).unwrap(), nonce + 2);
+}
+
+#[test]
+fn test_cross_vm_create_with_mapped_sender() {
+    let mut ctx = setup();
+    let cross_space = CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space();
+    let nonce = ctx.state.nonce(&cross_space).unwrap();
+    let move_account = [0x01u8; 32];
+    let mapped_sender = mapped_sender_address(&move_account);
+
+    // PUSH1 0x00 PUSH1 0x00 RETURN
+    let init_code = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+    let mut params = cross_vm_params(
+        CrossVMAction::Create(init_code.clone()),
+        Address::zero(),
+        U256::zero(),
+    );
+    params.mapped_sender = Some(mapped_sender);
+    let calls = vec![params.clone(), params];
+    let mut executor = ctx.executor();
+    assert_eq!(
+        executor.mapped_sender_nonce(&move_account).unwrap(),
+        U256::zero()
+    );
+    let returns = executor.cross_vm_call_batch(calls).unwrap();
+    assert_eq!(
+        executor.mapped_sender_nonce(&move_account).unwrap(),
+        U256::from(2)
+    );
+
+    for (i, output) in returns.iter().enumerate() {
+        let (expected, _) = contract_address(
+            CreateContractAddress::FromSenderNonce,
+            ctx.env.number.into(),
+            &mapped_sender.with_evm_space(),
+            &U256::from(i),
+            &init_code,
+        );
+        assert_eq!(output.created_address, Some(expected.address));
+    }
+    // Neither the cross-space contract nor the mapped sender account spends
+    // its own nonce.
+    assert_eq!(ctx.state.nonce(&cross_space).unwrap(), nonce);
+    assert_eq!(
+        ctx.state.nonce(&mapped_sender.with_evm_space()).unwrap(),
+        U256::zero()
+    );
+}
+
+#[test]
+fn test_transact_with_env_override() {
+    let mut env = Env::default();
+    env.number = 10;
+    env.epoch_height = 10;
+    env.timestamp = 1000;
+    let mut ctx = setup_with_env(env);
+    let sender = Address::from_low_u64_be(1024).with_evm_space();
+    ctx.state.set_nonce(&sender, &U256::zero()).unwrap();
+
+    // Returns the block number and the timestamp as the contract code:
+    // NUMBER PUSH1 0x00 MSTORE TIMESTAMP PUSH1 0x20 MSTORE PUSH1 0x40 PUSH1 0x00
+    // RETURN
+    let tx = CreateTransaction {
+        sender,
+        init_code: vec![
+            0x43, 0x60, 0x00, 0x52, 0x42, 0x60, 0x20, 0x52, 0x60, 0x40, 0x60, 0x00, 0xf3,
+        ],
+        gas_price: U256::zero(),
+        hash: None,
+    };
+    let env_override = EnvOverride {
+        number: Some(5),
+        epoch_height: Some(5),
+        ..Default::default()
+    };
+    let outcome = ctx
+        .executor()
+        .transact_with_env(&tx, TransactOptions::exec_with_no_tracing(), env_override)
+        .unwrap();
+    let executed = outcome.successfully_executed().unwrap();
+    assert_eq!(U256::from_big_endian(&executed.output[..32]), U256::from(5));
+    // The timestamp is not overridden.
+    assert_eq!(
+        U256::from_big_endian(&executed.output[32..]),
+        U256::from(1000)
+    );
+}
+
+#[test]
+fn test_block_gas_limit() {
+    let mut env = Env::default();
+    env.gas_limit = U256::from(15_000_000);
+    env.accumulated_gas_used = U256::from(6_000_000);
+    let mut ctx = setup_with_env(env);
+    let sender = Address::from_low_u64_be(1024).with_evm_space();
+    ctx.state.set_nonce(&sender, &U256::zero()).unwrap();
+
+    // The mock transaction has a gas limit of 10,000,000.
+    let tx = CreateTransaction {
+        sender,
+        init_code: vec![],
+        gas_price: U256::zero(),
+        hash: None,
+    };
+    let outcome = ctx
+        .executor()
+        .transact(&tx, TransactOptions::exec_with_no_tracing())
+        .unwrap();
+    assert!(matches!(
+        outcome,
+        ExecutionOutcome::NotExecutedToReconsiderPacking(ToRepackError::BlockGasLimitExceeded {
+            limit,
+            got,
+        }) if limit == U256::from(9_000_000) && got == U256::from(10_000_000)
+    ));
+
+    // The block gas limit is not checked for estimation.
+    let request = EstimateRequest {
+        has_sender: true,
+        has_gas_limit: true,
+        has_gas_price: true,
+        has_nonce: true,
+        has_storage_limit: false,
+    };
+    let outcome = ctx
+        .executor()
+        .transact(&tx, TransactOptions::estimate_first_pass(request))
+        .unwrap();
+    assert!(outcome.succeeded());
+}
+
+#[test]
+fn test_credit_fees_to_author() {
+    let mut env = Env::default();
+    env.author = Address::from_low_u64_be(1025);
+    let sender = Address::from_low_u64_be(1024).with_evm_space();
+    let author = env.author.with_evm_space();
+
+    let tx = CreateTransaction {
+        sender,
+        init_code: vec![],
+        gas_price: U256::from(2),
+        hash: None,
+    };
+    let transact = |credit_fees| {
+        let mut ctx = setup_with_env(env.clone());
+        ctx.state
+            .add_balance(
+                &sender,
+                &U256::from(100_000_000),
+                CleanupMode::NoEmpty,
+                U256::zero(),
+            )
+            .unwrap();
+        let mut options = TransactOptions::exec_with_tracing();
+        options.check_settings.credit_fees = credit_fees;
+        let outcome = ctx.executor().transact(&tx, options).unwrap();
+        let executed = outcome.successfully_executed().unwrap();
+        (executed, ctx.state.balance(&author).unwrap())
+    };
+
+    let (executed, author_balance) = transact(true);
+    assert!(!executed.fee.is_zero());
+    assert_eq!(author_balance, executed.fee);
+    assert!(executed.trace.iter().any(|t| matches!(
+        &t.action,
+        Action::InternalTransferAction(InternalTransferAction {
+            from: AddressPocket::GasPayment,
+            to: AddressPocket::Balance(to),
+            value,
+        }) if *to == author && *value == executed.fee
+    )));
+
+    // Without crediting, the fee is not paid to the author.
+    let (_, author_balance) = transact(false);
+    assert_eq!(author_balance, U256::zero());
+
+    // The fee is credited by default, except for the estimations, and never
+    // to a zero author.
+    assert!(
+        TransactOptions::exec_with_no_tracing()
+            .check_settings
+            .credit_fees
+    );
+    let request = EstimateRequest {
+        has_sender: true,
+        has_gas_limit: true,
+        has_gas_price: true,
+        has_nonce: true,
+        has_storage_limit: false,
+    };
+    assert!(
+        !TransactOptions::estimate_first_pass(request)
+            .check_settings
+            .credit_fees
+    );
+    env.author = Address::zero();
+    let mut ctx = setup_with_env(env);
+    ctx.state
+        .add_balance(
+            &sender,
+            &U256::from(100_000_000),
+            CleanupMode::NoEmpty,
+            U256::zero(),
+        )
+        .unwrap();
+    ctx.executor()
+        .transact(&tx, TransactOptions::exec_with_tracing())
+        .unwrap();
+    assert_eq!(
+        ctx.state
+            .balance(&Address::zero().with_evm_space())
+            .unwrap(),
+        U256::zero()
+    );
+}
+
+#[test]
+fn test_out_of_gas_outcome() {
+    let mut ctx = setup();
+    let sender = Address::from_low_u64_be(1024).with_evm_space();
+    ctx.state.set_nonce(&sender, &U256::zero()).unwrap();
+
+    // An infinite loop: JUMPDEST PUSH1 0x00 JUMP
+    let tx = CreateTransaction {
+        sender,
+        init_code: vec![0x5b, 0x60, 0x00, 0x56],
+        gas_price: U256::zero(),
+        hash: None,
+    };
+    let outcome = ctx
+        .executor()
+        .transact(&tx, TransactOptions::exec_with_no_tracing())
+        .unwrap();
+    assert_eq!(outcome.error_code(), 12);
+    match outcome {
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::OutOfGas { gas_limit },
+            executed,
+        ) => {
+            assert_eq!(gas_limit, *tx.gas());
+            assert_eq!(executed.gas_used, *tx.gas());
+        },
+        outcome => panic!("Unexpected outcome: {:?}", outcome),
+    }
+}
+
+#[test]
+fn test_call_discards_state_changes() {
+    let mut ctx = setup();
+
+    // Runtime code writing the storage and echoing the calldata:
+    // PUSH1 0x01 PUSH1 0x00 SSTORE CALLDATASIZE PUSH1 0x00 PUSH1 0x00
+    // CALLDATACOPY CALLDATASIZE PUSH1 0x00 RETURN
+    let runtime_code = vec![
+        0x60, 0x01, 0x60, 0x00, 0x55, 0x36, 0x60, 0x00, 0x60, 0x00, 0x37, 0x36, 0x60, 0x00, 0xf3,
+    ];
+    let contract = ctx.deploy(&runtime_code).with_evm_space();
+    let sender = Address::from_low_u64_be(1024).with_evm_space();
+
+    let data = vec![0x12, 0x34, 0x56, 0x78];
+    let params = ActionParams {
+        space: contract.space,
+        code_address: contract.address,
+        address: contract.address,
+        sender: sender.address,
+        original_sender: sender.address,
+        gas: U256::from(1_000_000),
+        gas_price: U256::zero(),
+        value: ActionValue::Transfer(U256::zero()),
+        code: ctx.state.code(&contract).unwrap(),
+        code_hash: ctx.state.code_hash(&contract).unwrap(),
+        data: Some(data.clone()),
+        call_type: CallType::Call,
+        create_type: CreateType::None,
+        params_type: vm::ParamsType::Separate,
+    };
+    let result = ctx
+        .executor()
+        .call(params, MultiObservers::with_no_tracing())
+        .unwrap()
+        .unwrap();
+    assert!(result.apply_state);
+    assert_eq!(&*result.return_data, &data[..]);
+
+    let key = vec![0u8; 32];
+    assert_eq!(ctx.state.storage_at(&contract, &key).unwrap(), U256::zero());
+    assert_eq!(ctx.state.nonce(&sender).unwrap(), U256::zero());
+}
+
+#[test]
+fn test_executed_created_address() {
+    let mut ctx = setup();
+    let sender = Address::from_low_u64_be(1024).with_evm_space();
+    ctx.state.set_nonce(&sender, &U256::zero()).unwrap();
+
+    // The constructor creates a child contract with empty code:
+    // PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 CREATE STOP
+    let tx = CreateTransaction {
+        sender,
+        init_code: vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0xf0, 0x00],
+        gas_price: U256::zero(),
+        hash: None,
+    };
+    let outcome = ctx
+        .executor()
+        .transact(&tx, TransactOptions::exec_with_no_tracing())
+        .unwrap();
+    let executed = outcome.successfully_executed().unwrap();
+    assert_eq!(executed.contracts_created.len(), 2);
+
+    let (expected, _) = crate::contract_address(
+        vm::CreateContractAddress::FromSenderNonce,
+        ctx.env.number.into(),
+        &sender,
+        &U256::zero(),
+        &tx.init_code,
+    );
+    assert_eq!(executed.created_address(), Some(expected));
+}
+
+#[test]
+fn test_trace_level() {
+    let sender = Address::from_low_u64_be(1024).with_evm_space();
+
+    // PUSH1 0x00 PUSH1 0x00 LOG0 STOP
+    let tx = CreateTransaction {
+        sender,
+        init_code: vec![0x60, 0x00, 0x60, 0x00, 0xa0, 0x00],
+        gas_price: U256::zero(),
+        hash: None,
+    };
+    let transact = |trace_level| {
+        let mut ctx = setup();
+        ctx.state.set_nonce(&sender, &U256::zero()).unwrap();
+        let options = TransactOptions::exec_with_tracing().with_trace_level(trace_level);
+        let outcome = ctx.executor().transact(&tx, options).unwrap();
+        outcome.successfully_executed().unwrap()
+    };
+
+    let executed = transact(TraceLevel::Full);
+    assert!(!executed.trace.is_empty());
+    assert_eq!(executed.logs.len(), 1);
+
+    let executed = transact(TraceLevel::LogsOnly);
+    assert!(executed.trace.is_empty());
+    assert_eq!(executed.logs.len(), 1);
+
+    let executed = transact(TraceLevel::None);
+    assert!(executed.trace.is_empty());
+    assert!(executed.logs.is_empty());
+}
+
+#[test]
+fn test_code_cache_invalidated_on_create() {
+    let mut ctx = setup();
+    let cross_space = CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space();
+
+    // Runtime code echoing the calldata:
+    // CALLDATASIZE PUSH1 0x00 PUSH1 0x00 CALLDATACOPY CALLDATASIZE PUSH1 0x00
+    // RETURN
+    let runtime_code = vec![0x36, 0x60, 0x00, 0x60, 0x00, 0x37, 0x36, 0x60, 0x00, 0xf3];
+    // PUSH10 <runtime_code> PUSH1 0x00 MSTORE PUSH1 0x0a PUSH1 0x16 RETURN
+    let mut init_code = vec![0x69];
+    init_code.extend_from_slice(&runtime_code);
+    init_code.extend_from_slice(&[0x60, 0x00, 0x52, 0x60, 0x0a, 0x60, 0x16, 0xf3]);
+    let (contract, _) = crate::contract_address(
+        vm::CreateContractAddress::FromSenderNonce,
+        ctx.env.number.into(),
+        &cross_space,
+        &ctx.state.nonce(&cross_space).unwrap(),
+        &init_code,
+    );
+
+    let call = || {
+        let mut params = cross_vm_params(CrossVMAction::Call, contract.address, U256::zero());
+        params.raw_calldata = Some(vec![0x12, 0x34]);
+        params
+    };
+    let create = cross_vm_params(
+        CrossVMAction::Create(init_code),
+        Address::zero(),
+        U256::zero(),
+    );
+    let returns = ctx
+        .executor()
+        .cross_vm_call_batch(vec![call(), create, call()])
+        .unwrap();
+    assert_eq!(returns.len(), 3);
+    assert!(matches!(returns[0].result, Ok(ref r) if r.return_data.is_empty()));
+    assert_eq!(returns[1].created_address, Some(contract.address));
+    assert!(matches!(returns[2].result, Ok(ref r) if &*r.return_data == &[0x12, 0x34]));
+}
+
+#[test]
+fn test_cross_vm_call_require_contract() {
+    let mut ctx = setup();
+    let receiver = Address::from_low_u64_be(1024);
+
+    // The call succeeds without output by default.
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, receiver, U256::zero()))
+        .unwrap();
+    assert!(matches!(output.result, Ok(ref r) if r.apply_state));
+
+    let mut params = cross_vm_params(CrossVMAction::Call, receiver, U256::zero());
+    params.require_contract = true;
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert_eq!(
+        output.result.err(),
+        Some(vm::Error::CrossSpaceTargetNotContract(receiver))
+    );
+
+    // PUSH1 0x00 PUSH1 0x00 RETURN
+    let runtime_code = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+    let contract = ctx.deploy(&runtime_code);
+    let mut params = cross_vm_params(CrossVMAction::Call, contract, U256::zero());
+    params.require_contract = true;
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(matches!(output.result, Ok(ref r) if r.apply_state));
+}
+
+/// A Move VM which echoes the first argument, aborts on function `abort` and
+/// fails on function `fail`. A call uses `MOVE_CALL_GAS`, except that function
+/// `burn` reports more gas than its budget.
+struct EchoMoveVM;
+
+const MOVE_CALL_GAS: u64 = 1000;
+
+impl CallMoveVMTrait for EchoMoveVM {
+    fn call_move_vm(
+        &mut self,
+        _caller: Address,
+        address: Vec<u8>,
+        module: String,
+        function: String,
+        data: Vec<Vec<u8>>,
+        _types: Vec<TypeTag>,
+        _value: U256,
+        gas: U256,
+        _cross_space_depth: usize,
+    ) -> CallMoveVMOutput {
+        let result = match function.as_str() {
+            "ihe_abort" => Err(CallMoveVMError::Abort {
+                address: H256::from_slice(&address),
+                module,
+                code: 7,
+            }),
+            "ihe_fail" => Err(CallMoveVMError::Other("Cannot call".to_string())),
+            _ => Ok(data.into_iter().next().unwrap_or_default()),
+        };
+        let gas_used = match function.as_str() {
+            "ihe_burn" => gas * 2,
+            _ => U256::from(MOVE_CALL_GAS),
+        };
+        CallMoveVMOutput { result, gas_used }
+    }
+}
+
+#[test]
+fn test_call_move_from_evm() {
+    let mut move_vm = EchoMoveVM;
+    let mut ctx = setup_with_move_vm(&mut move_vm);
+    let sender = Address::from_low_u64_be(1024);
+    let gas = U256::from(1_000_000);
+    // The call itself with one word of arguments, and the return data of three
+    // words.
+    let base_gas = U256::from(ctx.spec.call_gas + ctx.spec.copy_gas);
+    let return_gas = U256::from(3 * ctx.spec.memory_gas);
+
+    let selector = &keccak_hash::keccak("callMove(bytes,string,bytes[])")[..4];
+    let mut call_move = |function: &str| {
+        let args = (vec![1u8; 32], function.to_string(), vec![vec![1u8, 2, 3]]);
+        let params = ActionParams {
+            space: Space::Ethereum,
+            code_address: *CROSS_SPACE_CONTRACT_ADDRESS,
+            address: *CROSS_SPACE_CONTRACT_ADDRESS,
+            sender,
+            original_sender: sender,
+            gas,
+            gas_price: U256::zero(),
+            value: ActionValue::Transfer(U256::zero()),
+            code: None,
+            code_hash: None,
+            data: Some([selector, &args.abi_encode()].concat()),
+            call_type: CallType::Call,
+            create_type: CreateType::None,
+            params_type: vm::ParamsType::Separate,
+        };
+        ctx.executor()
+            .call(params, MultiObservers::with_no_tracing())
+            .unwrap()
+    };
+
+    let result = call_move("module::echo").unwrap();
+    assert!(result.apply_state);
+    assert_eq!(
+        Vec::<u8>::abi_decode(&result.return_data).unwrap(),
+        vec![1u8, 2, 3]
+    );
+    // Only the gas used by the Move VM is charged from the forwarded budget.
+    assert_eq!(result.gas_left, gas - base_gas - MOVE_CALL_GAS - return_gas);
+
+    // The gas used is capped by the budget, which keeps one 64th of the gas.
+    let result = call_move("module::burn").unwrap();
+    assert!(result.apply_state);
+    assert_eq!(result.gas_left, (gas - base_gas) / 64 - return_gas);
+
+    let result = call_move("module::abort").unwrap();
+    assert!(!result.apply_state);
+    assert_eq!(
+        move_abort_decode(&result.return_data),
+        Some(MoveAbortInfo {
+            address: H256::repeat_byte(1),
+            module: "module".to_string(),
+            code: 7,
+        })
+    );
+
+    let result = call_move("module::fail").unwrap();
+    assert!(!result.apply_state);
+    assert_eq!(revert_reason_decode(&result.return_data), "Cannot call");
+
+    assert!(matches!(
+        call_move("echo"),
+        Err(vm::Error::InvalidFunctionName(_))
+    ));
+}
+
+#[test]
+fn test_cross_vm_params_builder() {
+    let receiver = Address::from_low_u64_be(1024);
+    let gas = U256::from(1_000_000);
+
+    let builder = || CrossVMParamsBuilder::new(CrossVMAction::Call, receiver, gas);
+    assert_eq!(
+        builder().value(U256::one()).build().unwrap_err(),
+        vm::Error::MissingCrossSpaceCalldata
+    );
+
+    let params = builder()
+        .function_name("transfer".to_string())
+        .evm_params(vec![vec![1u8]])
+        .call_type(CallType::DelegateCall)
+        .build()
+        .unwrap();
+    assert_eq!(params.receiver, receiver);
+    assert_eq!(params.gas, gas);
+    assert_eq!(params.function_name, "transfer");
+    assert_eq!(params.evm_params, vec![vec![1u8]]);
+    assert_eq!(params.call_type, CallType::DelegateCall);
+    assert!(params.value.is_zero());
+    assert_eq!(params.raw_calldata, None);
+    assert_eq!(params.target_space, Space::Ethereum);
+    assert_eq!(params.sponsor, None);
+
+    // A plain transfer sets an empty calldata.
+    let params = builder()
+        .value(U256::one())
+        .raw_calldata(vec![])
+        .build()
+        .unwrap();
+    assert_eq!(params.raw_calldata, Some(vec![]));
+
+    // A contract creation needs no calldata.
+    let params = CrossVMParamsBuilder::new(CrossVMAction::Create(vec![0xfe]), receiver, gas)
+        .salt(H256::repeat_byte(1))
+        .build()
+        .unwrap();
+    assert_eq!(params.salt, Some(H256::repeat_byte(1)));
+}
+
+#[test]
+fn test_cross_vm_call_step_limit() {
+    let mut ctx = setup();
+
+    // Runtime code looping forever: JUMPDEST PUSH1 0x00 JUMP
+    let contract = ctx.deploy(&[0x5b, 0x60, 0x00, 0x56]);
+
+    let mut params = cross_vm_params(CrossVMAction::Call, contract, U256::zero());
+    params.raw_calldata = Some(vec![]);
+    params.max_steps = Some(100);
+    let output = ctx.executor().cross_vm_call(params.clone()).unwrap();
+    assert_eq!(output.result.unwrap_err(), vm::Error::StepLimitReached);
+
+    // Without a step limit, the loop runs out of gas.
+    params.max_steps = None;
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert_eq!(output.result.unwrap_err(), vm::Error::OutOfGas);
+}
+
+#[test]
+fn test_receipt_cache_eviction() {
+    let hash = H256::from_low_u64_be;
+    let mut cache = ReceiptCache::new(2);
+    cache.insert(hash(1));
+    cache.insert(hash(2));
+    // Using the first hash makes the second one the least recently used.
+    assert!(cache.contains(&hash(1)));
+    cache.insert(hash(3));
+    assert_eq!(cache.len(), 2);
+    assert!(cache.contains(&hash(1)));
+    assert!(!cache.contains(&hash(2)));
+    assert!(cache.contains(&hash(3)));
+}
+
+#[test]
+fn test_transact_already_executed() {
+    let mut ctx = setup();
+    let receipt_cache = Arc::new(Mutex::new(ReceiptCache::new(16)));
+
+    let tx_hash = H256::from_low_u64_be(1);
+    let tx = CreateTransaction {
+        sender: Address::from_low_u64_be(1024).with_evm_space(),
+        init_code: vec![],
+        gas_price: U256::zero(),
+        hash: Some(tx_hash),
+    };
+    let transact = |ctx: &mut TestContext, receipt_cache: Option<Arc<Mutex<ReceiptCache>>>| {
+        let mut executor = ctx.executor();
+        if let Some(receipt_cache) = receipt_cache {
+            executor = executor.with_receipt_cache(receipt_cache);
+        }
+        executor
+            .transact(&tx, TransactOptions::exec_with_no_tracing())
+            .unwrap()
+    };
+
+    let outcome = transact(&mut ctx, Some(receipt_cache.clone()));
+    assert!(outcome.succeeded());
+    assert!(receipt_cache.lock().contains(&tx_hash));
+
+    let outcome = transact(&mut ctx, Some(receipt_cache.clone()));
+    assert!(matches!(outcome, ExecutionOutcome::AlreadyExecuted(hash) if hash == tx_hash));
+
+    // Without the cache, the transaction is rejected by the nonce.
+    let outcome = transact(&mut ctx, None);
+    assert!(matches!(
+        outcome,
+        ExecutionOutcome::NotExecutedDrop(TxDropError::OldNonce(..))
+    ));
+}
+
+#[test]
+fn test_finalization_result_helpers() {
+    let finalization_result = |apply_state: bool, data: Vec<u8>| {
+        let size = data.len();
+        FinalizationResult {
+            space: Space::Ethereum,
+            gas_left: U256::zero(),
+            apply_state,
+            return_data: ReturnData::new(data, 0, size),
+        }
+    };
+
+    let succeeded = finalization_result(true, vec![1, 2, 3]);
+    assert!(succeeded.is_success());
+    assert_eq!(succeeded.decoded_error(), None);
+
+    let reason = "Insufficient balance".to_string();
+    let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+    data.extend(reason.abi_encode());
+    let reverted = finalization_result(false, data);
+    assert!(!reverted.is_success());
+    assert_eq!(reverted.decoded_error(), Some(reason));
+}
+
+#[test]
+fn test_cross_vm_call_with_spec() {
+    let mut ctx = setup();
+
+    // Runtime code: PUSH1 0x00 SLOAD STOP
+    let contract = ctx.deploy(&[0x60, 0x00, 0x54, 0x00]);
+
+    let mut params = cross_vm_params(CrossVMAction::Call, contract, U256::zero());
+    params.raw_calldata = Some(vec![]);
+    let mut forked_spec = ctx.spec.clone();
+    forked_spec.sload_gas += 1000;
+
+    let mut executor = ctx.executor();
+    let forked = executor
+        .cross_vm_call_with_spec(params.clone(), &forked_spec)
+        .unwrap();
+    let current = executor.cross_vm_call(params).unwrap();
+    assert!(forked.result.unwrap().is_success());
+    assert!(current.result.unwrap().is_success());
+    assert_eq!(forked.gas_used, current.gas_used + 1000);
+}
+
+#[test]
+fn test_collateral_free_substate() {
+    let mut ctx = setup();
+    // Only the dust collection would track the touched accounts.
+    ctx.spec.no_empty = false;
+    ctx.spec.kill_dust = CleanDustMode::BasicOnly;
+    let receiver = Address::from_low_u64_be(1024);
+    let value = U256::from(1_000_000_000);
+
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, receiver, value))
+        .unwrap();
+    assert!(output.result.unwrap().is_success());
+    assert!(output.substate.touched.is_empty());
+
+    ctx.spec.storage_collateral_enabled = true;
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, receiver, value))
+        .unwrap();
+    assert!(output.result.unwrap().is_success());
+    assert!(output.substate.touched.contains(&receiver.with_evm_space()));
+}
+
+#[test]
+fn test_frame_stack_max_depth() {
+    let mut ctx = setup();
+    let factory = ctx.machine.vm_factory();
+
+    // Runtime code returning whether a sub-call succeeds:
+    // PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 PUSH1 0xff GAS
+    // CALL PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+    let runtime_code = vec![
+        0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0xff, 0x5a, 0xf1, 0x60,
+        0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+    ];
+    let contract = ctx.deploy(&runtime_code).with_evm_space();
+    let sender = Address::from_low_u64_be(1024).with_evm_space();
+
+    let params = ActionParams {
+        space: contract.space,
+        code_address: contract.address,
+        address: contract.address,
+        sender: sender.address,
+        original_sender: sender.address,
+        gas: U256::from(1_000_000),
+        gas_price: U256::zero(),
+        value: ActionValue::Transfer(U256::zero()),
+        code: ctx.state.code(&contract).unwrap(),
+        code_hash: ctx.state.code_hash(&contract).unwrap(),
+        data: None,
+        call_type: CallType::Call,
+        create_type: CreateType::None,
+        params_type: vm::ParamsType::Separate,
+    };
+    let mut exec = |cross_space_depth: usize, max_depth: usize| {
+        let top_frame = CallCreateFrame::new_call_raw(
+            params.clone(),
+            &ctx.env,
+            &ctx.machine,
+            &ctx.spec,
+            &factory,
+            0,     /* depth */
+            false, /* static_flag */
+        );
+        FrameStack::new(
+            &mut ctx.state,
+            Substate::new(),
+            MultiObservers::with_no_tracing(),
+            0,
+            max_depth,
+        )
+        .with_cross_space_depth(cross_space_depth)
+        .exec(top_frame)
+        .unwrap()
+        .result
+    };
+
+    let sub_call_succeeded = |result: vm::Result<FinalizationResult>| {
+        let result = result.unwrap();
+        assert!(result.is_success());
+        result.return_data[31] == 1
+    };
+    assert!(sub_call_succeeded(exec(0, 1)));
+    assert!(!sub_call_succeeded(exec(0, 0)));
+    // The cross-space calls leading to the frame stack count as depth.
+    assert!(!sub_call_succeeded(exec(1, 1)));
+    assert_eq!(exec(1, 0).unwrap_err(), vm::Error::OutOfDepth);
+
+    // A cross-space call counts the transitions of the executor as well as
+    // its own, so the sub-call fails one transition earlier.
+    ctx.spec.max_depth = 2;
+    let mut cross_vm_call = |cross_space_depth: usize| {
+        ctx.executor_at_depth(cross_space_depth)
+            .cross_vm_call(cross_vm_params(
+                CrossVMAction::Call,
+                contract.address,
+                U256::zero(),
+            ))
+            .unwrap()
+            .result
+    };
+    assert!(sub_call_succeeded(cross_vm_call(0)));
+    assert!(!sub_call_succeeded(cross_vm_call(1)));
+}
+
+/// Records the storage reads and ignores the other events.
+#[derive(Default)]
+struct StorageReadRecorder {
+    reads: Vec<(AddressWithSpace, Vec<u8>)>,
+}
+
+impl StateTracer for StorageReadRecorder {
+    fn trace_internal_transfer(&mut self, _: AddressPocket, _: AddressPocket, _: U256) {}
+
+    fn trace_storage_read(&mut self, address: &AddressWithSpace, key: &[u8]) {
+        self.reads.push((*address, key.to_vec()));
+    }
+
+    fn checkpoint(&mut self) {}
+
+    fn discard_checkpoint(&mut self) {}
+
+    fn revert_to_checkpoint(&mut self) {}
+}
+
+impl VmObserve for StorageReadRecorder {
+    fn record_call(&mut self, _: &ActionParams) {}
+
+    fn record_call_result(&mut self, _: &vm::Result<FrameReturn>) {}
+
+    fn record_create(&mut self, _: &ActionParams) {}
+
+    fn record_create_result(&mut self, _: &vm::Result<FrameReturn>) {}
+}
+
+#[test]
+fn test_trace_storage_read() {
+    let mut ctx = setup();
+    let factory = ctx.machine.vm_factory();
+
+    // Runtime code: PUSH1 0x05 SLOAD STOP
+    let contract = ctx.deploy(&[0x60, 0x05, 0x54, 0x00]).with_evm_space();
+    let code = ctx.state.code(&contract).unwrap();
+    let sender = Address::from_low_u64_be(1024);
+
+    let mut exec = |address: Address, code: Option<Arc<Vec<u8>>>, data: Option<Vec<u8>>| {
+        let params = ActionParams {
+            space: Space::Ethereum,
+            code_address: address,
+            address,
+            sender,
+            original_sender: sender,
+            gas: U256::from(1_000_000),
+            gas_price: U256::zero(),
+            value: ActionValue::Transfer(U256::zero()),
+            code,
+            code_hash: None,
+            data,
+            call_type: CallType::Call,
+            create_type: CreateType::None,
+            params_type: vm::ParamsType::Separate,
+        };
+        let frame = CallCreateFrame::new_call_raw(
+            params,
+            &ctx.env,
+            &ctx.machine,
+            &ctx.spec,
+            &factory,
+            0,
+            false,
+        );
+        let mut recorder = StorageReadRecorder::default();
+        let result = frame
+            .exec(&mut ctx.state, &mut FrameStackInfo::new(), &mut recorder)
+            .unwrap();
+        assert!(matches!(result, TrapResult::Return(Ok(ref r)) if r.apply_state));
+        recorder.reads
+    };
+
+    let mut key = vec![0u8; 32];
+    key[31] = 5;
+    assert_eq!(
+        exec(contract.address, code, None),
+        vec![(contract, key.clone())]
+    );
+
+    // The reads by the internal contracts are traced as well.
+    let selector = &keccak_hash::keccak("staticReadStorage(address,bytes32)")[..4];
+    let args = (contract.address, [5u8; 32]);
+    let data = [selector, &args.abi_encode()].concat();
+    assert_eq!(
+        exec(*CROSS_SPACE_CONTRACT_ADDRESS, None, Some(data)),
+        vec![(contract, vec![5u8; 32])]
+    );
+}
+
+#[test]
+fn test_batch_contracts_created_order() {
+    let mut ctx = setup();
+
+    // The constructor creates an empty contract:
+    // PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 CREATE POP STOP
+    let factory_code = vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0xf0, 0x50, 0x00];
+    let calls = vec![
+        cross_vm_params(
+            CrossVMAction::Create(factory_code),
+            Address::zero(),
+            U256::zero(),
+        ),
+        cross_vm_params(CrossVMAction::Create(vec![]), Address::zero(), U256::zero()),
+    ];
+    let returns = ctx.executor().cross_vm_call_batch(calls).unwrap();
+    let created: Vec<_> = returns
+        .iter()
+        .map(|output| output.created_address.unwrap().with_evm_space())
+        .collect();
+    // A contract starts with the nonce 1.
+    let (child, _) = contract_address(
+        CreateContractAddress::FromSenderNonce,
+        ctx.env.number.into(),
+        &created[0],
+        &U256::one(),
+        &[],
+    );
+
+    // The contract created by the constructor ends first in the first call.
+    assert_eq!(
+        batch_contracts_created(&returns),
+        vec![child, created[0], created[1]]
+    );
+}
+
+#[test]
+fn test_cross_space_call_gas() {
+    let mut ctx = setup();
+    let receiver = Address::from_low_u64_be(1024);
+    let value = U256::from(1_000_000_000);
+
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, receiver, value))
+        .unwrap();
+    assert!(output.result.unwrap().is_success());
+    let free_gas_used = output.gas_used;
+
+    ctx.spec.cross_space_call_gas = 5000;
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, receiver, value))
+        .unwrap();
+    assert!(output.result.unwrap().is_success());
+    assert_eq!(output.gas_used, free_gas_used + 5000);
+    assert_eq!(
+        ctx.state.balance(&receiver.with_evm_space()).unwrap(),
+        value * 2
+    );
+
+    // The gas can not cover the intrinsic gas.
+    let mut params = cross_vm_params(CrossVMAction::Call, receiver, value);
+    params.gas = U256::from(4999);
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert_eq!(output.result.unwrap_err(), vm::Error::OutOfGas);
+    assert_eq!(output.gas_used, U256::from(4999));
+    assert_eq!(
+        ctx.state.balance(&receiver.with_evm_space()).unwrap(),
+        value * 2
+    );
+}
+
+#[test]
+fn test_cross_vm_return_from_parts() {
+    let contract = Address::from_low_u64_be(1024);
+    let mut substate = Substate::new();
+    substate.logs.push(LogEntry {
+        address: contract,
+        topics: vec![],
+        data: vec![1, 2, 3],
+        space: Space::Ethereum,
+    });
+    substate.contracts_created.push(contract.with_evm_space());
+
+    let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+    data.extend("Paused".to_string().abi_encode());
+    let size = data.len();
+    let result = Ok(FinalizationResult {
+        space: Space::Ethereum,
+        gas_left: U256::from(100),
+        apply_state: false,
+        return_data: ReturnData::new(data, 0, size),
+    });
+    let output = CrossVMReturn::from_parts(substate, result);
+    assert_eq!(output.revert_reason, Some("Paused".to_string()));
+    assert_eq!(output.gas_left, U256::from(100));
+    assert_eq!(output.logs, output.substate.logs);
+    assert_eq!(output.contracts_created, vec![contract.with_evm_space()]);
+
+    let output = CrossVMReturn::from_parts(Substate::new(), Err(vm::Error::OutOfGas));
+    assert_eq!(output.revert_reason, None);
+    assert!(output.gas_left.is_zero());
+}
+
+#[test]
+fn test_cross_vm_estimate_batch() {
+    let mut ctx = setup();
+    let receiver = Address::from_low_u64_be(1024);
+
+    // Init code: PUSH1 0x01 PUSH1 0x00 SSTORE STOP
+    let expensive = cross_vm_params(
+        CrossVMAction::Create(vec![0x60, 0x01, 0x60, 0x00, 0x55, 0x00]),
+        Address::zero(),
+        U256::zero(),
+    );
+    let cheap = cross_vm_params(CrossVMAction::Call, receiver, U256::from(1_000_000_000));
+
+    let mut executor = ctx.executor();
+    let expensive_estimate = executor.cross_vm_estimate(expensive.clone()).unwrap();
+    let cheap_estimate = executor.cross_vm_estimate(cheap.clone()).unwrap();
+    assert!(expensive_estimate > cheap_estimate);
+
+    let estimates = executor
+        .cross_vm_estimate_batch(vec![expensive.clone(), cheap.clone()])
+        .unwrap();
+    assert_eq!(estimates, vec![expensive_estimate, cheap_estimate]);
+    let estimates = executor
+        .cross_vm_estimate_batch(vec![cheap, expensive])
+        .unwrap();
+    assert_eq!(estimates, vec![cheap_estimate, expensive_estimate]);
+
+    // The ctx.state changes of the batch are reverted.
+    assert!(ctx
+        .state
+        .balance(&receiver.with_evm_space())
+        .unwrap()
+        .is_zero());
+}
+
+#[test]
+fn test_cross_vm_call_sponsor() {
+    let mut ctx = setup_with_env(Env {
+        author: Address::from_low_u64_be(1025),
+        base_fee_per_gas: Some(U256::from(4)),
+        ..Default::default()
+    });
+    ctx.spec.cross_space_call_gas = 5000;
+    let author = ctx.env.author.with_evm_space();
+    let receiver = Address::from_low_u64_be(1024);
+    let sponsor = Address::from_low_u64_be(2048);
+    let caller = Address::from_low_u64_be(4096);
+    let gas_price = U256::from(10);
+    let sponsored = || {
+        let mut params = cross_vm_params(CrossVMAction::Call, receiver, U256::one());
+        params.gas_price = gas_price;
+        params.sponsor = Some(sponsor);
+        params.mapped_sender = Some(caller);
+        params
+    };
+    let call = |ctx: &mut TestContext, params: CrossVMParams| {
+        let mut executor = ctx.executor();
+        executor.authorize_sponsor(sponsor, caller);
+        executor.cross_vm_call(params).unwrap()
+    };
+
+    // The sponsor can not afford the gas.
+    let output = call(&mut ctx, sponsored());
+    assert_eq!(
+        output.result.unwrap_err(),
+        vm::Error::NotEnoughSponsorBalance {
+            required: U256::from(10_000_000),
+            got: U256::zero(),
+        }
+    );
+
+    let funded = U256::from(100_000_000);
+    ctx.state
+        .add_balance(
+            &sponsor.with_evm_space(),
+            &funded,
+            CleanupMode::NoEmpty,
+            U256::zero(),
+        )
+        .unwrap();
+    ctx.state.add_total_issued(funded);
+    let output = call(&mut ctx, sponsored());
+    assert!(output.result.unwrap().is_success());
+    assert_eq!(output.gas_used, U256::from(5000));
+    let fee = output.gas_used * gas_price;
+    assert_eq!(
+        ctx.state.balance(&sponsor.with_evm_space()).unwrap(),
+        funded - fee
+    );
+    // The base fee portion of the fee is burnt, and the rest goes to the
+    // block author.
+    let burnt_fee = output.gas_used * 4;
+    assert_eq!(ctx.state.total_issued_tokens(), funded - burnt_fee);
+    assert_eq!(ctx.state.balance(&author).unwrap(), fee - burnt_fee);
+
+    // The sponsor has not authorized the caller, or the caller is unknown.
+    let mut stranger = sponsored();
+    stranger.mapped_sender = Some(Address::from_low_u64_be(4097));
+    let mut anonymous = sponsored();
+    anonymous.mapped_sender = None;
+    for params in [stranger, anonymous] {
+        let before = ctx.state.balance(&sponsor.with_evm_space()).unwrap();
+        let output = call(&mut ctx, params);
+        assert_eq!(
+            output.result.unwrap_err(),
+            vm::Error::UnauthorizedSponsor(sponsor)
+        );
+        assert_eq!(
+            ctx.state.balance(&sponsor.with_evm_space()).unwrap(),
+            before
+        );
+    }
+    // Without an authorization, the sponsor is rejected.
+    let output = ctx.executor().cross_vm_call(sponsored()).unwrap();
+    assert_eq!(
+        output.result.unwrap_err(),
+        vm::Error::UnauthorizedSponsor(sponsor)
+    );
+
+    // Without a sponsor, the EVM space does not pay for the gas.
+    let mut params = sponsored();
+    params.sponsor = None;
+    let before = ctx.state.balance(&sponsor.with_evm_space()).unwrap();
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(output.result.unwrap().is_success());
+    assert_eq!(
+        ctx.state.balance(&sponsor.with_evm_space()).unwrap(),
+        before
+    );
+}
+
+#[test]
+fn test_intrinsic_gas() {
+    let mut ctx = setup();
+
+    let tx = CreateTransaction {
+        sender: Address::from_low_u64_be(1024).with_evm_space(),
+        init_code: vec![0, 1, 2],
+        gas_price: U256::zero(),
+        hash: None,
+    };
+    let intrinsic_gas = ctx.executor().intrinsic_gas(&tx);
+    assert_eq!(
+        intrinsic_gas,
+        (ctx.spec.tx_create_gas + ctx.spec.tx_data_zero_gas + 2 * ctx.spec.tx_data_non_zero_gas)
+            as u64
+    );
+    assert_eq!(
+        intrinsic_gas,
+        gas_required_for(true, &tx.init_code, &[], &ctx.spec)
+    );
+}
+
+#[test]
+fn test_cross_vm_call_receipt() {
+    let mut ctx = setup();
+    let receiver = Address::from_low_u64_be(1024);
+    let value = U256::from(1_000_000_000);
+
+    let mut params = cross_vm_params(CrossVMAction::Call, receiver, value);
+    params.raw_calldata = Some(vec![0x12, 0x34, 0x56, 0x78]);
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert_eq!(
+        output.receipt,
+        Some(CrossSpaceReceipt {
+            receiver,
+            selector: [0x12, 0x34, 0x56, 0x78],
+            value,
+            gas_used: output.gas_used,
+            success: true,
+        })
+    );
+
+    // A rejected call still has a receipt.
+    let params = cross_vm_params(CrossVMAction::Call, receiver, value);
+    let output = ctx
+        .executor_at_depth(ctx.spec.max_cross_space_depth)
+        .cross_vm_call(params)
+        .unwrap();
+    let receipt = output.receipt.unwrap();
+    assert!(!receipt.success);
+    assert_eq!(receipt.gas_used, output.gas_used);
+
+    assert_eq!(
+        CrossVMReturn::from_parts(Substate::new(), Err(vm::Error::OutOfGas)).receipt,
+        None
+    );
+}
+
+#[test]
+fn test_cross_vm_call_until_event() {
+    let mut ctx = setup();
+
+    // Runtime code emitting an event with topic 7:
+    // PUSH1 0x07 PUSH1 0x00 PUSH1 0x00 LOG1 STOP
+    let runtime_code = vec![0x60, 0x07, 0x60, 0x00, 0x60, 0x00, 0xa1, 0x00];
+    let contract = ctx.deploy(&runtime_code);
+
+    let (matched, output) = ctx
+        .executor()
+        .cross_vm_call_until_event(
+            cross_vm_params(CrossVMAction::Call, contract, U256::zero()),
+            H256::from_low_u64_be(7),
+        )
+        .unwrap();
+    assert!(matched);
+    assert!(output.result.unwrap().apply_state);
+
+    let (matched, _) = ctx
+        .executor()
+        .cross_vm_call_until_event(
+            cross_vm_params(CrossVMAction::Call, contract, U256::zero()),
+            H256::from_low_u64_be(8),
+        )
+        .unwrap();
+    assert!(!matched);
+}
+
+#[test]
+fn test_encode_cross_vm_args() {
+    let receiver = Address::from_low_u64_be(1024);
+    let args = vec![
+        CrossVMArg::Address(receiver),
+        CrossVMArg::Uint256(U256::from(5)),
+    ];
+    assert_eq!(
+        cross_vm_function_signature("transfer", &args),
+        "transfer(address,uint256)"
+    );
+    assert_eq!(
+        encode_cross_vm_args(&args),
+        (receiver, U256::from(5)).abi_encode()
+    );
+
+    // The dynamic arguments are encoded like the `evm_params` path.
+    let args = vec![
+        CrossVMArg::String("0x1::test::Test".to_string()),
+        CrossVMArg::BytesArray(vec![vec![1u8], vec![2u8; 40]]),
+    ];
+    assert_eq!(
+        cross_vm_function_signature("foo", &args),
+        "foo(string,bytes[])"
+    );
+    assert_eq!(
+        encode_cross_vm_args(&args),
+        (
+            "0x1::test::Test".to_string(),
+            vec![vec![1u8], vec![2u8; 40]]
+        )
+            .abi_encode()
+    );
+    assert_eq!(cross_vm_function_signature("bar", &[]), "bar()");
+    assert!(encode_cross_vm_args(&[]).is_empty());
+}
+
+#[test]
+fn test_cross_vm_call_with_typed_args() {
+    let mut ctx = setup();
+    let receiver = Address::from_low_u64_be(1024);
+
+    let params = CrossVMParamsBuilder::new(CrossVMAction::Call, receiver, U256::from(1_000_000))
+        .function_name("transfer".to_string())
+        .typed_args(vec![
+            CrossVMArg::Address(receiver),
+            CrossVMArg::Uint256(U256::from(5)),
+        ])
+        .build()
+        .unwrap();
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(output.result.unwrap().apply_state);
+    assert_eq!(
+        output.receipt.unwrap().selector[..],
+        keccak_hash::keccak("transfer(address,uint256)")[..4]
+    );
+}
+
+#[test]
+fn test_cross_vm_call_revert_reason() {
+    let mut ctx = setup();
+    // Runtime code reverting with the data appended to it:
+    // PUSH1 <len> DUP1 PUSH1 0x0b PUSH1 0x00 CODECOPY PUSH1 0x00 REVERT
+    let reverting_code = |data: &[u8]| {
+        let mut code = vec![
+            0x60,
+            data.len() as u8,
+            0x80,
+            0x60,
+            0x0b,
+            0x60,
+            0x00,
+            0x39,
+            0x60,
+            0x00,
+            0xfd,
+        ];
+        code.extend_from_slice(data);
+        code
+    };
+    let error_data = |reason: &str| {
+        let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+        data.extend(reason.to_string().abi_encode());
+        data
+    };
+
+    let contract = ctx.deploy(&reverting_code(&error_data("Paused")));
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, contract, U256::zero()))
+        .unwrap();
+    assert!(matches!(output.result, Ok(ref r) if !r.apply_state));
+    assert_eq!(output.revert_reason, Some("Paused".to_string()));
+    assert!(!output.receipt.unwrap().success);
+
+    // A long reason is truncated.
+    let reason = "a".repeat(60);
+    let contract = ctx.deploy(&reverting_code(&error_data(&reason)));
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, contract, U256::zero()))
+        .unwrap();
+    assert_eq!(output.revert_reason, Some(format!("{}...", &reason[..50])));
+
+    // The data without the `Error(string)` selector is shown in hex.
+    let contract = ctx.deploy(&reverting_code(&[0x12, 0x34]));
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, contract, U256::zero()))
+        .unwrap();
+    assert_eq!(output.revert_reason, Some("0x1234".to_string()));
+
+    // A successful call has no revert reason.
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(
+            CrossVMAction::Call,
+            Address::from_low_u64_be(1024),
+            U256::zero(),
+        ))
+        .unwrap();
+    assert!(output.result.unwrap().apply_state);
+    assert_eq!(output.revert_reason, None);
+}
+
+#[test]
+fn test_cross_vm_call_gas_used() {
+    let mut ctx = setup();
+    ctx.spec.cross_space_call_gas = 5000;
+
+    // Runtime code: PUSH1 0x01 PUSH1 0x00 SSTORE STOP
+    let contract = ctx.deploy(&[0x60, 0x01, 0x60, 0x00, 0x55, 0x00]);
+    let params = cross_vm_params(CrossVMAction::Call, contract, U256::zero());
+    let gas = params.gas;
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(output.result.unwrap().apply_state);
+    assert!(output.gas_used > U256::from(5000 + ctx.spec.sstore_set_gas));
+    assert_eq!(output.gas_used + output.gas_left, gas);
+    assert_eq!(output.receipt.unwrap().gas_used, output.gas_used);
+
+    // A reverted call returns the gas left.
+    // PUSH1 0x00 PUSH1 0x00 REVERT
+    let contract = ctx.deploy(&[0x60, 0x00, 0x60, 0x00, 0xfd]);
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, contract, U256::zero()))
+        .unwrap();
+    assert!(!output.result.unwrap().apply_state);
+    assert!(!output.gas_left.is_zero());
+    assert_eq!(output.gas_used + output.gas_left, gas);
+
+    // An exceptional halt uses up the gas.
+    // INVALID
+    let contract = ctx.deploy(&[0xfe]);
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, contract, U256::zero()))
+        .unwrap();
+    assert!(output.result.is_err());
+    assert!(output.gas_left.is_zero());
+    assert_eq!(output.gas_used, gas);
+}
+
+#[test]
+fn test_cross_vm_create() {
+    let mut ctx = setup();
+    let cross_space = CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space();
+    let value = U256::from(1_000_000_000);
+
+    // Runtime code: STOP
+    // PUSH1 0x00 PUSH1 0x00 MSTORE8 PUSH1 0x01 PUSH1 0x00 RETURN
+    let init_code = vec![0x60, 0x00, 0x60, 0x00, 0x53, 0x60, 0x01, 0x60, 0x00, 0xf3];
+    let (expected, _) = contract_address(
+        CreateContractAddress::FromSenderNonce,
+        ctx.env.number.into(),
+        &cross_space,
+        &ctx.state.nonce(&cross_space).unwrap(),
+        &init_code,
+    );
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(
+            CrossVMAction::Create(init_code),
+            Address::zero(),
+            value,
+        ))
+        .unwrap();
+    assert!(output.result.unwrap().apply_state);
+    assert_eq!(output.created_address, Some(expected.address));
+    assert_eq!(output.contracts_created, vec![expected]);
+    assert_eq!(*ctx.state.code(&expected).unwrap().unwrap(), vec![0x00]);
+    assert_eq!(ctx.state.balance(&expected).unwrap(), value);
+    assert!(ctx.state.balance(&cross_space).unwrap().is_zero());
+}
+
+#[test]
+fn test_cross_vm_call_event() {
+    let mut ctx = setup();
+    let cross_space = *CROSS_SPACE_CONTRACT_ADDRESS;
+    let value = U256::from(1_000_000_000);
+    let event = |receiver: Address, selector: [u8; 4], value: U256, success: bool| LogEntry {
+        address: cross_space,
+        topics: vec![
+            keccak_hash::keccak("CrossVMCall(address,bytes32,bytes4,uint256,bool)"),
+            H256::from(receiver),
+            keccak_hash::keccak("0x1::test::Test"),
+        ],
+        data: (selector, value, success).abi_encode(),
+        space: Space::Ethereum,
+    };
+
+    let receiver = Address::from_low_u64_be(1024);
+    let mut params = cross_vm_params(CrossVMAction::Call, receiver, value);
+    params.raw_calldata = Some(vec![0x12, 0x34, 0x56, 0x78]);
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(output.result.unwrap().apply_state);
+    assert_eq!(
+        output.logs,
+        vec![event(receiver, [0x12, 0x34, 0x56, 0x78], value, true)]
+    );
+
+    // The logs of a reverted call are dropped, but the event is still emitted.
+    // PUSH1 0x07 PUSH1 0x00 PUSH1 0x00 LOG1 PUSH1 0x00 PUSH1 0x00 REVERT
+    let contract = ctx.deploy(&[
+        0x60, 0x07, 0x60, 0x00, 0x60, 0x00, 0xa1, 0x60, 0x00, 0x60, 0x00, 0xfd,
+    ]);
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, contract, value))
+        .unwrap();
+    assert!(!output.result.unwrap().apply_state);
+    assert_eq!(output.logs, vec![event(contract, [0u8; 4], value, false)]);
+
+    // The receiver of a create is the created contract.
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(
+            CrossVMAction::Create(vec![]),
+            Address::zero(),
+            U256::zero(),
+        ))
+        .unwrap();
+    let created = output.created_address.unwrap();
+    assert_eq!(
+        output.logs,
+        vec![event(created, [0u8; 4], U256::zero(), true)]
+    );
+}
+
+#[test]
+fn test_cross_vm_call_with_mapped_sender() {
+    let mut ctx = setup();
+    let cross_space = *CROSS_SPACE_CONTRACT_ADDRESS;
+    let origin_key = H256::zero();
+    let caller_key = H256::from_low_u64_be(1);
+
+    // The mapping is deterministic and distinct between Move accounts.
+    let mapped_sender = mapped_sender_address(&[0x01u8; 32]);
+    assert_eq!(mapped_sender, mapped_sender_address(&[0x01u8; 32]));
+    assert_ne!(mapped_sender, mapped_sender_address(&[0x02u8; 32]));
+    assert_ne!(mapped_sender, Address::zero());
+
+    // Runtime code storing `tx.origin` and `msg.sender`:
+    // ORIGIN PUSH1 0x00 SSTORE CALLER PUSH1 0x01 SSTORE STOP
+    let contract = ctx.deploy(&[0x32, 0x60, 0x00, 0x55, 0x33, 0x60, 0x01, 0x55, 0x00]);
+    let stored = |ctx: &TestContext, key: &H256| {
+        ctx.state
+            .storage_at(&contract.with_evm_space(), key.as_bytes())
+            .unwrap()
+    };
+
+    let mut params = cross_vm_params(CrossVMAction::Call, contract, U256::zero());
+    params.mapped_sender = Some(mapped_sender);
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(output.result.unwrap().apply_state);
+    assert_eq!(
+        stored(&ctx, &origin_key),
+        U256::from(H256::from(mapped_sender).as_bytes())
+    );
+    // The cross-space contract is still the direct caller.
+    assert_eq!(
+        stored(&ctx, &caller_key),
+        U256::from(H256::from(cross_space).as_bytes())
+    );
+
+    // Without a mapped sender, the origin is the zero address.
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(CrossVMAction::Call, contract, U256::zero()))
+        .unwrap();
+    assert!(output.result.unwrap().apply_state);
+    assert!(stored(&ctx, &origin_key).is_zero());
+    assert_eq!(
+        stored(&ctx, &caller_key),
+        U256::from(H256::from(cross_space).as_bytes())
+    );
+}
+
+#[test]
+fn test_cross_vm_call_batch_rollback() {
+    let mut ctx = setup();
+    let cross_space = CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space();
+    let receiver = Address::from_low_u64_be(1024);
+    let value = U256::from(1_000_000_000);
+    // PUSH1 0x00 PUSH1 0x00 REVERT
+    let reverting = ctx.deploy(&[0x60, 0x00, 0x60, 0x00, 0xfd]);
+    let nonce = ctx.state.nonce(&cross_space).unwrap();
+
+    // The constructor emits an event with topic 7:
+    // PUSH1 0x07 PUSH1 0x00 PUSH1 0x00 LOG1 STOP
+    let create = || {
+        cross_vm_params(
+            CrossVMAction::Create(vec![0x60, 0x07, 0x60, 0x00, 0x60, 0x00, 0xa1, 0x00]),
+            Address::zero(),
+            value,
+        )
+    };
+    let transfer = || cross_vm_params(CrossVMAction::Call, receiver, value);
+
+    // The calls after the failed one are not executed.
+    let calls = vec![
+        transfer(),
+        create(),
+        cross_vm_params(CrossVMAction::Call, reverting, U256::zero()),
+        transfer(),
+    ];
+    let returns = ctx.executor().cross_vm_call_batch(calls).unwrap();
+    assert_eq!(returns.len(), 3);
+    assert!(returns[0].result.as_ref().unwrap().apply_state);
+    let created = returns[1].created_address.unwrap().with_evm_space();
+    assert!(!returns[2].result.as_ref().unwrap().apply_state);
+    for output in &returns {
+        assert!(output.logs.is_empty());
+        assert!(output.substate.logs.is_empty());
+        assert!(output.contracts_created.is_empty());
+        assert!(!output.receipt.as_ref().unwrap().success);
+    }
+    // The state changes of the successful calls are reverted.
+    assert!(ctx
+        .state
+        .balance(&receiver.with_evm_space())
+        .unwrap()
+        .is_zero());
+    assert!(!ctx.state.exists(&created).unwrap());
+    assert_eq!(ctx.state.nonce(&cross_space).unwrap(), nonce);
+
+    // The state changes are kept if all the calls succeed.
+    let returns = ctx
+        .executor()
+        .cross_vm_call_batch(vec![transfer(), create()])
+        .unwrap();
+    assert_eq!(returns[1].logs.len(), 2);
+    assert_eq!(returns[1].contracts_created.len(), 1);
+    assert!(returns.iter().all(|r| r.receipt.as_ref().unwrap().success));
+    assert_eq!(
+        ctx.state.balance(&receiver.with_evm_space()).unwrap(),
+        value
+    );
+    assert_eq!(
+        ctx.state
+            .balance(&returns[1].created_address.unwrap().with_evm_space())
+            .unwrap(),
+        value
+    );
+}
+
+#[test]
+fn test_cross_vm_estimate() {
+    let mut ctx = setup();
+    ctx.spec.cross_space_call_gas = 5000;
+    let cross_space = CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space();
+    let value = U256::from(1_000_000_000);
+
+    // Runtime code: PUSH1 0x01 PUSH1 0x00 SSTORE STOP
+    let contract = ctx.deploy(&[0x60, 0x01, 0x60, 0x00, 0x55, 0x00]);
+    let nonce = ctx.state.nonce(&cross_space).unwrap();
+    let call = || cross_vm_params(CrossVMAction::Call, contract, value);
+
+    let estimated = ctx.executor().cross_vm_estimate(call()).unwrap();
+    // The state changes are discarded.
+    assert!(ctx
+        .state
+        .storage_at(&contract.with_evm_space(), H256::zero().as_bytes())
+        .unwrap()
+        .is_zero());
+    assert!(ctx
+        .state
+        .balance(&contract.with_evm_space())
+        .unwrap()
+        .is_zero());
+    assert_eq!(ctx.state.nonce(&cross_space).unwrap(), nonce);
+
+    // The estimated gas is enough for the call, and it is padded by 7/6 over
+    // the gas used by the EVM execution, on top of the intrinsic gas.
+    let mut params = call();
+    params.gas = estimated;
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(output.result.unwrap().apply_state);
+    assert_eq!(
+        estimated,
+        (output.gas_used - 5000) * 7 / 6 + U256::from(5000)
+    );
+}
+
+/// Runtime code forwarding the calldata to the cross-space contract, and
+/// returning whether the call succeeds:
+/// CALLDATASIZE PUSH1 0x00 PUSH1 0x00 CALLDATACOPY
+/// PUSH1 0x00 PUSH1 0x00 CALLDATASIZE PUSH1 0x00 PUSH1 0x00
+/// PUSH20 <cross-space contract> GAS CALL
+/// PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+fn cross_space_proxy_code() -> Vec<u8> {
+    let mut proxy_code = vec![
+        0x36, 0x60, 0x00, 0x60, 0x00, 0x37, 0x60, 0x00, 0x60, 0x00, 0x36, 0x60, 0x00, 0x60, 0x00,
+        0x73,
+    ];
+    proxy_code.extend_from_slice(CROSS_SPACE_CONTRACT_ADDRESS.as_bytes());
+    proxy_code.extend_from_slice(&[0x5a, 0xf1, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3]);
+    proxy_code
+}
+
+/// A cross-space call to the proxy deployed with `cross_space_proxy_code`,
+/// which calls back into Move function `module::echo`.
+fn call_back_params(proxy: Address) -> CrossVMParams {
+    let selector = &keccak_hash::keccak("callMove(bytes,string,bytes[])")[..4];
+    let args = (vec![1u8; 32], "module::echo".to_string(), vec![vec![1u8]]);
+    let mut params = cross_vm_params(CrossVMAction::Call, proxy, U256::zero());
+    params.raw_calldata = Some([selector, &args.abi_encode()].concat());
+    params
+}
+
+#[test]
+fn test_cross_space_depth_limit() {
+    let mut move_vm = EchoMoveVM;
+    let mut ctx = setup_with_move_vm(&mut move_vm);
+    let max_depth = ctx.spec.max_cross_space_depth;
+    let receiver = Address::from_low_u64_be(1024);
+    let value = U256::from(1_000_000_000);
+
+    let params = cross_vm_params(CrossVMAction::Call, receiver, value);
+    let output = ctx
+        .executor_at_depth(max_depth - 1)
+        .cross_vm_call(params)
+        .unwrap();
+    assert!(output.result.unwrap().apply_state);
+    assert_eq!(
+        ctx.state.balance(&receiver.with_evm_space()).unwrap(),
+        value
+    );
+
+    let params = cross_vm_params(CrossVMAction::Call, receiver, value);
+    let gas = params.gas;
+    let output = ctx
+        .executor_at_depth(max_depth)
+        .cross_vm_call(params)
+        .unwrap();
+    assert_eq!(
+        output.result.unwrap_err(),
+        vm::Error::CrossSpaceReentrancyLimit
+    );
+    assert_eq!(output.gas_used, gas);
+    assert_eq!(
+        ctx.state.balance(&receiver.with_evm_space()).unwrap(),
+        value
+    );
+
+    // The EVM frames of a cross-space call are one level deeper than the
+    // call, so calling back into Move is rejected one level earlier.
+    let proxy = ctx.deploy(&cross_space_proxy_code());
+    let output = ctx
+        .executor_at_depth(max_depth - 2)
+        .cross_vm_call(call_back_params(proxy))
+        .unwrap();
+    assert_eq!(
+        &*output.result.unwrap().return_data,
+        H256::from_low_u64_be(1).as_bytes()
+    );
+    let output = ctx
+        .executor_at_depth(max_depth - 1)
+        .cross_vm_call(call_back_params(proxy))
+        .unwrap();
+    assert_eq!(
+        &*output.result.unwrap().return_data,
+        H256::zero().as_bytes()
+    );
+}
+
+/// A Move VM whose functions call back into EVM the way the `call_evm` native
+/// does: a new executor continuing the cross-space depth of the call makes a
+/// cross-space call to a proxy, which calls back into this Move VM. Each call
+/// records its depth and the error of the cross-space call it makes.
+struct PingPongMoveVM {
+    calls: PingPongCalls,
+}
+
+/// The depth of each call into `PingPongMoveVM` and the error of the
+/// cross-space call made by it, innermost first.
+type PingPongCalls = Rc<RefCell<Vec<(usize, Option<vm::Error>)>>>;
+
+impl CallMoveVMTrait for PingPongMoveVM {
+    fn call_move_vm(
+        &mut self,
+        _caller: Address,
+        _address: Vec<u8>,
+        _module: String,
+        _function: String,
+        _data: Vec<Vec<u8>>,
+        _types: Vec<TypeTag>,
+        _value: U256,
+        _gas: U256,
+        cross_space_depth: usize,
+    ) -> CallMoveVMOutput {
+        let mut move_vm = PingPongMoveVM {
+            calls: self.calls.clone(),
+        };
+        let mut ctx = setup_with_move_vm(&mut move_vm);
+        let proxy = ctx.deploy(&cross_space_proxy_code());
+        let output = ctx
+            .executor_at_depth(cross_space_depth)
+            .cross_vm_call(call_back_params(proxy))
+            .unwrap();
+        let (result, error) = match output.result {
+            Ok(result) => (Ok(result.return_data.to_vec()), None),
+            Err(error) => (Err(CallMoveVMError::Other(error.to_string())), Some(error)),
+        };
+        self.calls.borrow_mut().push((cross_space_depth, error));
+        CallMoveVMOutput {
+            result,
+            gas_used: U256::from(MOVE_CALL_GAS),
+        }
+    }
+}
+
+#[test]
+fn test_cross_space_ping_pong_depth_limit() {
+    let calls = Rc::new(RefCell::new(vec![]));
+    let mut move_vm = PingPongMoveVM {
+        calls: calls.clone(),
+    };
+    let mut ctx = setup_with_move_vm(&mut move_vm);
+    let max_depth = ctx.spec.max_cross_space_depth;
+    assert_eq!(max_depth % 2, 0);
+
+    // Each Move -> EVM -> Move round trip adds two transitions, until the
+    // innermost call from Move into EVM reaches the limit.
+    let proxy = ctx.deploy(&cross_space_proxy_code());
+    let output = ctx
+        .executor()
+        .cross_vm_call(call_back_params(proxy))
+        .unwrap();
+    assert_eq!(
+        &*output.result.unwrap().return_data,
+        H256::from_low_u64_be(1).as_bytes()
+    );
+    let mut expected: Vec<_> = (1..max_depth / 2).map(|round| (2 * round, None)).collect();
+    expected.push((max_depth, Some(vm::Error::CrossSpaceReentrancyLimit)));
+    expected.reverse();
+    assert_eq!(*calls.borrow(), expected);
+}
+
+#[test]
+fn test_cross_vm_call_contracts_created() {
+    let mut ctx = setup();
+    let cross_space = CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space();
+    let child_of = |ctx: &TestContext, parent: &AddressWithSpace, nonce: u64| {
+        contract_address(
+            CreateContractAddress::FromSenderNonce,
+            ctx.env.number.into(),
+            parent,
+            &U256::from(nonce),
+            &[],
+        )
+        .0
+    };
+
+    // The constructor creates an empty contract:
+    // PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 CREATE POP STOP
+    let init_code = vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0xf0, 0x50, 0x00];
+    let (parent, _) = contract_address(
+        CreateContractAddress::FromSenderNonce,
+        ctx.env.number.into(),
+        &cross_space,
+        &ctx.state.nonce(&cross_space).unwrap(),
+        &init_code,
+    );
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(
+            CrossVMAction::Create(init_code),
+            Address::zero(),
+            U256::zero(),
+        ))
+        .unwrap();
+    // The child ends its creation before the parent.
+    let child = child_of(&ctx, &parent, 1);
+    assert_eq!(output.contracts_created, vec![child, parent]);
+    assert_eq!(output.contracts_created, output.substate.contracts_created);
+
+    // Runtime code creating two empty contracts:
+    // (PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 CREATE POP) * 2 STOP
+    let factory = ctx
+        .deploy(&[
+            0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0xf0, 0x50, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00,
+            0xf0, 0x50, 0x00,
+        ])
+        .with_evm_space();
+    let output = ctx
+        .executor()
+        .cross_vm_call(cross_vm_params(
+            CrossVMAction::Call,
+            factory.address,
+            U256::zero(),
+        ))
+        .unwrap();
+    assert_eq!(
+        output.contracts_created,
+        vec![child_of(&ctx, &factory, 1), child_of(&ctx, &factory, 2)]
+    );
+
+    // A call running out of gas in the second creation creates no contract.
+    let mut params = cross_vm_params(CrossVMAction::Call, factory.address, U256::zero());
+    params.gas = U256::from(60_000);
+    let output = ctx.executor().cross_vm_call(params).unwrap();
+    assert!(output.result.is_err());
+    assert!(output.contracts_created.is_empty());
+}
+
+#[test]
+fn test_transient_storage_checkpoint() {
+    let mut ctx = setup();
+    let state = &mut ctx.state;
+    let address = Address::from_low_u64_be(1024).with_evm_space();
+    let key = vec![1u8];
+    let slot = |state: &State, key: &[u8]| state.transient_storage_at(&address, key);
+
+    state.checkpoint();
+    state.set_transient_storage(&address, key.clone(), U256::from(1));
+
+    // A sub-frame sees the slots written by its callers, and its writes are
+    // undone if it reverts.
+    state.checkpoint();
+    assert_eq!(slot(state, &key), U256::from(1));
+    state.set_transient_storage(&address, key.clone(), U256::from(2));
+    state.set_transient_storage(&address, vec![2u8], U256::from(3));
+    state.revert_to_checkpoint();
+    assert_eq!(slot(state, &key), U256::from(1));
+    assert!(slot(state, &[2u8]).is_zero());
+
+    // The writes of a successful sub-frame are kept, including the cleared
+    // slots.
+    state.checkpoint();
+    state.set_transient_storage(&address, key.clone(), U256::zero());
+    state.set_transient_storage(&address, vec![2u8], U256::from(3));
+    state.discard_checkpoint();
+    assert!(slot(state, &key).is_zero());
+    assert_eq!(slot(state, &[2u8]), U256::from(3));
+
+    // A checkpoint discarded into its parent is still reverted with it.
+    state.checkpoint();
+    state.checkpoint();
+    state.set_transient_storage(&address, vec![2u8], U256::from(4));
+    state.discard_checkpoint();
+    state.revert_to_checkpoint();
+    assert_eq!(slot(state, &[2u8]), U256::from(3));
+
+    // The slots are dropped at the end of the transaction.
+    state.discard_checkpoint();
+    assert!(slot(state, &[2u8]).is_zero());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_executed_serde_round_trip() {
+    let env = Env {
+        author: Address::from_low_u64_be(1025),
+        ..Default::default()
+    };
+    let sender = Address::from_low_u64_be(1024).with_evm_space();
+    let mut ctx = setup_with_env(env);
+    ctx.state
+        .add_balance(
+            &sender,
+            &U256::from(100_000_000),
+            CleanupMode::NoEmpty,
+            U256::zero(),
+        )
+        .unwrap();
+
+    // LOG0 with empty data, then deploy the one-byte code `STOP`.
+    let tx = CreateTransaction {
+        sender,
+        init_code: vec![0x60, 0x00, 0x60, 0x00, 0xa0, 0x60, 0x01, 0x60, 0x00, 0xf3],
+        gas_price: U256::from(2),
+        hash: Some(H256::from_low_u64_be(1)),
+    };
+    let outcome = ctx
+        .executor()
+        .transact(&tx, TransactOptions::exec_with_tracing())
+        .unwrap();
+    let executed = outcome.successfully_executed().unwrap();
+    assert_eq!(executed.logs.len(), 1);
+    assert_eq!(executed.contracts_created.len(), 1);
+    assert!(executed
+        .trace
+        .iter()
+        .any(|t| matches!(t.action, Action::Create(_))));
+    assert!(executed
+        .trace
+        .iter()
+        .any(|t| matches!(t.action, Action::InternalTransferAction(_))));
+
+    let json = serde_json::to_string(&executed).unwrap();
+    let decoded: super::Executed = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, executed);
+
+    // Every outcome round-trips, with the same error code.
+    let outcomes = vec![
+        ExecutionOutcome::NotExecutedDrop(TxDropError::OldNonce(1.into(), 2.into())),
+        ExecutionOutcome::NotExecutedDrop(TxDropError::NotEnoughBaseGas {
+            expected: 21000,
+            actual: 100,
+        }),
+        ExecutionOutcome::NotExecutedDrop(TxDropError::FeeCapTooLow {
+            base_fee: 2.into(),
+            max_fee: 1.into(),
+        }),
+        ExecutionOutcome::NotExecutedDrop(TxDropError::InitCodeTooLarge {
+            limit: 1,
+            actual: 2,
+        }),
+        ExecutionOutcome::NotExecutedDrop(TxDropError::CrossSpaceCalldataTooLarge {
+            limit: 1,
+            actual: 2,
+        }),
+        ExecutionOutcome::NotExecutedToReconsiderPacking(ToRepackError::InvalidNonce {
+            expected: 1.into(),
+            got: 2.into(),
+        }),
+        ExecutionOutcome::NotExecutedToReconsiderPacking(ToRepackError::SenderDoesNotExist),
+        ExecutionOutcome::NotExecutedToReconsiderPacking(ToRepackError::BlockGasLimitExceeded {
+            limit: 1.into(),
+            got: 2.into(),
+        }),
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::NotEnoughCash {
+                required: 2.into(),
+                got: 1.into(),
+                actual_gas_cost: 1.into(),
+            },
+            executed.clone(),
+        ),
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::OutOfGas {
+                gas_limit: 21000.into(),
+            },
+            executed.clone(),
+        ),
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::VmError(vm::Error::Reverted),
+            executed.clone(),
+        ),
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::VmError(vm::Error::BadInstruction { instruction: 0xfe }),
+            executed.clone(),
+        ),
+        ExecutionOutcome::Finished(executed),
+        ExecutionOutcome::AlreadyExecuted(H256::from_low_u64_be(1)),
+    ];
+    for outcome in outcomes {
+        let json = serde_json::to_string(&outcome).unwrap();
+        let decoded: ExecutionOutcome = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+        assert_eq!(decoded.error_code(), outcome.error_code());
+    }
+
+    // The VM errors without fields are restored as they are, and the others
+    // by their display strings.
+    let round_trip = |error: vm::Error| {
+        let json = serde_json::to_string(&ExecutionError::VmError(error)).unwrap();
+        serde_json::from_str::<ExecutionError>(&json).unwrap()
+    };
+    assert_eq!(
+        round_trip(vm::Error::CrossSpaceReentrancyLimit),
+        ExecutionError::VmError(vm::Error::CrossSpaceReentrancyLimit)
+    );
+    assert_eq!(
+        round_trip(vm::Error::RevertedWithReason("reason".to_string())),
+        ExecutionError::VmError(vm::Error::Message("Reverted: reason".to_string()))
+    );
+
+    // The internal transfers without an address round-trip as well.
+    let action = Action::InternalTransferAction(InternalTransferAction {
+        from: AddressPocket::MintBurn,
+        to: AddressPocket::MoveSpace,
+        value: U256::from(1),
+    });
+    let json = serde_json::to_value(&action).unwrap();
+    assert_eq!(json["InternalTransferAction"]["fromPocket"], "mint_or_burn");
+    assert_eq!(json["InternalTransferAction"]["toPocket"], "move_space");
+    let decoded: Action = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, action);
+
+    // An unknown pocket is rejected.
+    let mut json = serde_json::to_value(&action).unwrap();
+    json["InternalTransferAction"]["fromPocket"] = "unknown".into();
+    assert!(serde_json::from_value::<Action>(json).is_err());
+}
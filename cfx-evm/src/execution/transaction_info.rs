@@ -1,4 +1,4 @@
-use cfx_types::{AddressWithSpace, Space, U256};
+use cfx_types::{Address, AddressWithSpace, Space, H256, U256};
 use primitives::{Action, SignedTransaction};
 use std::borrow::Cow;
 use Cow::{Borrowed, Owned};
@@ -15,6 +15,26 @@ pub trait TransactionInfo {
     fn space(&self) -> Space {
         Space::Ethereum
     }
+
+    /// The max fee per gas of an EIP-1559 transaction.
+    fn max_fee_per_gas(&self) -> Option<U256> {
+        None
+    }
+
+    /// The max priority fee per gas of an EIP-1559 transaction.
+    fn max_priority_fee_per_gas(&self) -> Option<U256> {
+        None
+    }
+
+    /// The access list of the transaction (EIP-2930).
+    fn access_list(&self) -> Cow<[(Address, Vec<H256>)]> {
+        Borrowed(&[])
+    }
+
+    /// The hash of the transaction, if it is known.
+    fn hash(&self) -> Option<H256> {
+        None
+    }
 }
 
 impl TransactionInfo for SignedTransaction {
@@ -45,4 +65,8 @@ impl TransactionInfo for SignedTransaction {
     fn value(&self) -> Cow<U256> {
         Borrowed((**self).value())
     }
+
+    fn hash(&self) -> Option<H256> {
+        Some(SignedTransaction::hash(self))
+    }
 }
@@ -1,10 +1,14 @@
 use crate::{
+    observer::VmObserve,
     state::{FrameStackInfo, Substate},
     vm::{self, ActionParams, Env, Spec},
 };
 use cfx_state::state_trait::StateOpsTrait;
 use cfx_statedb::Result as DbResult;
-use cfx_types::{address_util::AddressUtil, Address, AddressSpaceUtil, H256, U256};
+use cfx_types::{
+    address_util::AddressUtil, Address, AddressSpaceUtil, AddressWithSpace, H256, U256,
+};
+use std::sync::Arc;
 
 /// The internal contracts need to access the context parameter directly, e.g.,
 /// `foo(env, spec)`. But `foo(context.env(), context.spec())` will incur
@@ -60,9 +64,70 @@ impl<'a> InternalRefContext<'a> {
             .map_err(|e| e.into())
     }
 
-    pub fn storage_at(&mut self, params: &ActionParams, key: &[u8]) -> DbResult<U256> {
+    pub fn storage_at(
+        &mut self,
+        params: &ActionParams,
+        key: &[u8],
+        tracer: &mut dyn VmObserve,
+    ) -> DbResult<U256> {
+        let receiver = params.address.with_space(params.space);
+        self.storage_at_address(&receiver, key, tracer)
+    }
+
+    /// Read the transient storage (EIP-1153) of the current contract. It lives
+    /// in the state for the transaction and never reaches the state db.
+    pub fn tload(&self, params: &ActionParams, key: &[u8]) -> U256 {
+        let receiver = params.address.with_space(params.space);
+        self.state.transient_storage_at(&receiver, key)
+    }
+
+    /// Write the transient storage (EIP-1153) of the current contract. The
+    /// write is reverted together with the frame.
+    pub fn tstore(&mut self, params: &ActionParams, key: Vec<u8>, value: U256) -> vm::Result<()> {
+        if self.static_flag {
+            return Err(vm::Error::MutableCallInStaticContext);
+        }
+
         let receiver = params.address.with_space(params.space);
-        self.state.storage_at(&receiver, key).map_err(|e| e.into())
+        self.state.set_transient_storage(&receiver, key, value);
+        Ok(())
+    }
+
+    /// Read the storage of another contract. Unset slots are zero.
+    pub fn storage_at_address(
+        &mut self,
+        address: &AddressWithSpace,
+        key: &[u8],
+        tracer: &mut dyn VmObserve,
+    ) -> DbResult<U256> {
+        let value = self.state.storage_at(address, key)?;
+        tracer.trace_storage_read(address, key);
+        Ok(value)
+    }
+
+    /// The code of `address` in the space of `params`.
+    pub fn code_at(
+        &self,
+        params: &ActionParams,
+        address: &Address,
+    ) -> vm::Result<Option<Arc<Vec<u8>>>> {
+        let address = address.with_space(params.space);
+        Ok(self.state.code(&address)?)
+    }
+
+    /// The code hash of `address` in the space of `params`. It is zero if the
+    /// account does not exist, the same as `EXTCODEHASH`.
+    pub fn code_hash_at(&self, params: &ActionParams, address: &Address) -> vm::Result<H256> {
+        let address = address.with_space(params.space);
+        Ok(self.state.code_hash(&address)?.unwrap_or_default())
+    }
+
+    pub fn balance(&self, address: &AddressWithSpace) -> DbResult<U256> {
+        self.state.balance(address)
+    }
+
+    pub fn nonce(&self, address: &AddressWithSpace) -> DbResult<U256> {
+        self.state.nonce(address)
     }
 
     pub fn is_contract_address(&self, address: &Address) -> vm::Result<bool> {
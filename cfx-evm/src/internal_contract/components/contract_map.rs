@@ -1,4 +1,9 @@
-use super::{super::contracts::all_internal_contracts, InternalContractTrait};
+use super::{
+    super::contracts::{
+        all_internal_contracts, internal_contracts_of_all_forks, validate_internal_contracts,
+    },
+    InternalContractTrait,
+};
 use crate::{evm::Spec, spec::CommonParams};
 use cfx_types::{Address, AddressWithSpace};
 use primitives::BlockNumber;
@@ -26,7 +31,10 @@ impl InternalContractMap {
         // of them are activated at the genesis block. The activation of the
         // internal contracts are controlled by the `CommonParams` and
         // `vm::Spec`.
-        let mut internal_contracts = all_internal_contracts();
+        let mut internal_contracts = internal_contracts_of_all_forks();
+        if let Err(address) = validate_internal_contracts(&internal_contracts) {
+            panic!("Duplicated internal contract address {:?}", address);
+        }
 
         while let Some(contract) = internal_contracts.pop() {
             let address = *contract.address();
@@ -50,8 +58,8 @@ impl InternalContractMap {
     }
 
     #[cfg(test)]
-    pub fn initialize_for_test() -> Vec<Address> {
-        all_internal_contracts()
+    pub fn initialize_for_test(spec: &Spec) -> Vec<Address> {
+        all_internal_contracts(spec)
             .iter()
             .map(|contract| *contract.address())
             .collect()
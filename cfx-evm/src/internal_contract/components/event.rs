@@ -1,6 +1,7 @@
 use super::context::InternalRefContext;
 use crate::{evm::ActionParams, vm};
-use cfx_types::H256;
+use cfx_types::{AddressWithSpace, H256};
+use primitives::log_entry::LogEntry;
 use solidity_abi::{ABIEncodable, EventIndexEncodable};
 
 /// Native implementation of a solidity-interface function.
@@ -22,6 +23,24 @@ pub trait SolidityEventTrait: Send + Sync {
 
         context.log(param, context.spec, topics, data)
     }
+
+    /// Build the log entry directly, for the events emitted outside an
+    /// internal contract call.
+    fn log_entry(
+        indexed: &Self::Indexed,
+        non_indexed: &Self::NonIndexed,
+        address: &AddressWithSpace,
+    ) -> LogEntry {
+        let mut topics = vec![Self::EVENT_SIG];
+        topics.extend_from_slice(&indexed.indexed_event_encode());
+
+        LogEntry {
+            address: address.address,
+            topics,
+            data: non_indexed.abi_encode(),
+            space: address.space,
+        }
+    }
 }
 
 #[macro_export]
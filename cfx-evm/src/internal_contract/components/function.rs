@@ -4,6 +4,7 @@
 
 use super::{InternalRefContext, IsActive};
 use crate::{
+    execution::{move_abort_encode, MoveAbortInfo},
     observer::VmObserve,
     state::FrameStackInfo,
     vm::{self, ActionParams, CallType, ExecTrapResult, GasLeft, ReturnData, Spec, TrapResult},
@@ -67,31 +68,39 @@ impl<T: SolidityFunctionConfigTrait + ExecutionTrait + IsActive> SolidityFunctio
             },
         };
 
-        let gas_left = params.gas - cost;
+        let mut gas_left = params.gas - cost;
 
         match ExecutionTrait::execute_inner(
             self,
             solidity_params,
             params,
-            gas_left,
+            &mut gas_left,
             context,
             tracer,
         ) {
             TrapResult::Return(output) => {
-                let vm_result = output.and_then(|output| {
-                    let output = output.abi_encode();
-                    let length = output.len();
-                    let return_cost = U256::from((length + 31) / 32 * context.spec.memory_gas);
-                    if gas_left < return_cost {
-                        Err(vm::Error::OutOfGas)
-                    } else {
-                        Ok(GasLeft::NeedsReturn {
-                            gas_left: gas_left - return_cost,
-                            data: ReturnData::new(output, 0, length),
-                            apply_state: true,
-                        })
-                    }
-                });
+                let vm_result = match output {
+                    Ok(output) => return_with_gas(output.abi_encode(), true, gas_left, context),
+                    // The reason is returned in the `Error(string)` format, so
+                    // the caller can decode it as a solidity revert.
+                    Err(vm::Error::RevertedWithReason(reason)) => {
+                        let output = [&REVERT_REASON_SIG[..], &reason.abi_encode()].concat();
+                        return_with_gas(output, false, gas_left, context)
+                    },
+                    Err(vm::Error::MoveAbort {
+                        address,
+                        module,
+                        code,
+                    }) => {
+                        let info = MoveAbortInfo {
+                            address,
+                            module,
+                            code,
+                        };
+                        return_with_gas(move_abort_encode(&info), false, gas_left, context)
+                    },
+                    Err(err) => Err(err),
+                };
                 TrapResult::Return(vm_result)
             },
             TrapResult::SubCallCreate(trap_error) => TrapResult::SubCallCreate(trap_error),
@@ -107,6 +116,28 @@ impl<T: SolidityFunctionConfigTrait + ExecutionTrait + IsActive> SolidityFunctio
     }
 }
 
+/// The function selector of `Error(string)`.
+const REVERT_REASON_SIG: [u8; 4] = [8, 195, 121, 160];
+
+fn return_with_gas(
+    output: Vec<u8>,
+    apply_state: bool,
+    gas_left: U256,
+    context: &InternalRefContext,
+) -> vm::Result<GasLeft> {
+    let length = output.len();
+    let return_cost = U256::from((length + 31) / 32 * context.spec.memory_gas);
+    if gas_left < return_cost {
+        Err(vm::Error::OutOfGas)
+    } else {
+        Ok(GasLeft::NeedsReturn {
+            gas_left: gas_left - return_cost,
+            data: ReturnData::new(output, 0, length),
+            apply_state,
+        })
+    }
+}
+
 fn preprocessing<T: SolidityFunctionConfigTrait>(
     sol_fn: &T,
     input: &[u8],
@@ -139,11 +170,13 @@ pub trait PreExecCheckTrait: Send + Sync {
 }
 
 pub trait ExecutionTrait: Send + Sync + InterfaceTrait {
+    /// `gas_left` is the gas left after the upfront payment. A function whose
+    /// gas is only known after the execution charges it from `gas_left`.
     fn execute_inner(
         &self,
         input: Self::Input,
         params: &ActionParams,
-        gas_left: U256,
+        gas_left: &mut U256,
         context: &mut InternalRefContext,
         tracer: &mut dyn VmObserve,
     ) -> ExecTrapResult<<Self as InterfaceTrait>::Output>;
@@ -168,7 +201,7 @@ where
         &self,
         input: Self::Input,
         params: &ActionParams,
-        _gas_left: U256,
+        _gas_left: &mut U256,
         context: &mut InternalRefContext,
         tracer: &mut dyn VmObserve,
     ) -> ExecTrapResult<<Self as InterfaceTrait>::Output> {
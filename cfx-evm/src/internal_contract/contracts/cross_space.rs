@@ -3,8 +3,13 @@
 // See http://www.gnu.org/licenses/
 
 use super::preludes::*;
-use crate::{internal_bail, state::cleanup_mode};
+use crate::{
+    internal_bail,
+    state::cleanup_mode,
+    vm::{ExecTrapResult, TrapResult},
+};
 use cfx_parameters::internal_contract_addresses::CROSS_SPACE_CONTRACT_ADDRESS;
+use cfx_state::CallMoveVMError;
 use cfx_types::{Address, AddressSpaceUtil, U256};
 use move_core_types::language_storage::TypeTag as MoveTypeTag;
 
@@ -18,22 +23,26 @@ make_solidity_contract! {
 fn generate_fn_table() -> SolFnTable {
     make_function_table!(
         CallToMove,
+        CallMove,
         Log,
         EncodeU64,
         EncodeBytes32,
         DecodeU64,
-        DecodeBytes32
+        DecodeBytes32,
+        StaticReadStorage
     )
 }
 
 group_impl_is_active!(
     "genesis",
     CallToMove,
+    CallMove,
     Log,
     EncodeU64,
     EncodeBytes32,
     DecodeU64,
     DecodeBytes32,
+    StaticReadStorage,
 );
 
 make_solidity_function! {
@@ -56,6 +65,11 @@ impl SimpleExecutionTrait for CallToMove {
         context: &mut InternalRefContext,
         _tracer: &mut dyn VmObserve,
     ) -> vm::Result<Bytes> {
+        if context.callstack.cross_space_depth() >= context.spec.max_cross_space_depth {
+            return Err(vm::Error::CrossSpaceReentrancyLimit);
+        }
+        let cross_space_depth = context.callstack.cross_space_depth() + 1;
+
         let call = || {
             let value = params.value.value();
             let caller = params.sender;
@@ -103,7 +117,9 @@ impl SimpleExecutionTrait for CallToMove {
                 types,
                 value,
                 params.gas,
+                cross_space_depth,
             )
+            .result
             .map_err(|err| vm::Error::InternalContract(format!("Fail to call move vm: {}", err)))
         };
 
@@ -132,6 +148,115 @@ impl SimpleExecutionTrait for CallToMove {
     }
 }
 
+make_solidity_function! {
+    pub struct CallMove((Bytes, String, Vec<Bytes>), "callMove(bytes,string,bytes[])", Bytes);
+}
+
+impl_function_type!(CallMove, "payable_write");
+
+/// The gas charged for the call itself, excluding the Move execution.
+fn call_move_base_gas(args: &[Bytes], spec: &Spec) -> U256 {
+    let words: usize = args.iter().map(|arg| (arg.len() + 31) / 32).sum();
+    U256::from(spec.call_gas) + U256::from(spec.copy_gas) * words
+}
+
+/// The gas budget forwarded to the Move VM. Like a sub-call in EVM, at most
+/// all but one 64th of the gas left is forwarded, and the gas not used by the
+/// Move VM is returned to the caller.
+fn call_move_gas_budget(gas_left: U256, spec: &Spec) -> U256 {
+    match spec.sub_gas_cap_divisor {
+        Some(divisor) => gas_left - gas_left / divisor,
+        None => gas_left,
+    }
+}
+
+impl UpfrontPaymentTrait for CallMove {
+    fn upfront_gas_payment(
+        &self,
+        (_address, _function, args): &(Bytes, String, Vec<Bytes>),
+        _params: &ActionParams,
+        context: &InternalRefContext,
+    ) -> DbResult<U256> {
+        Ok(call_move_base_gas(args, context.spec))
+    }
+}
+
+impl ExecutionTrait for CallMove {
+    fn execute_inner(
+        &self,
+        input: (Bytes, String, Vec<Bytes>),
+        params: &ActionParams,
+        gas_left: &mut U256,
+        context: &mut InternalRefContext,
+        _tracer: &mut dyn VmObserve,
+    ) -> ExecTrapResult<Bytes> {
+        TrapResult::Return(call_move(input, params, gas_left, context))
+    }
+}
+
+fn call_move(
+    (address, function, args): (Bytes, String, Vec<Bytes>),
+    params: &ActionParams,
+    gas_left: &mut U256,
+    context: &mut InternalRefContext,
+) -> vm::Result<Bytes> {
+    if context.callstack.cross_space_depth() >= context.spec.max_cross_space_depth {
+        return Err(vm::Error::CrossSpaceReentrancyLimit);
+    }
+    let cross_space_depth = context.callstack.cross_space_depth() + 1;
+    if address.len() != 32 {
+        internal_bail!("Move module address must be 32 bytes");
+    }
+    let (module, function) = match function.split_once("::") {
+        Some((module, function)) if !module.is_empty() && !function.is_empty() => {
+            (module.to_string(), format!("ihe_{}", function))
+        },
+        _ => return Err(vm::Error::InvalidFunctionName(function)),
+    };
+
+    let gas = call_move_gas_budget(*gas_left, context.spec);
+    let value = params.value.value();
+    context.state.sub_balance(
+        &CROSS_SPACE_CONTRACT_ADDRESS.with_evm_space(),
+        &value,
+        &mut cleanup_mode(context.substate, context.spec),
+    )?;
+    let vm = if let Some(vm) = context.state.as_move_vm() {
+        vm
+    } else {
+        internal_bail!("Cannot call move vm");
+    };
+
+    let output = vm.call_move_vm(
+        params.sender,
+        address,
+        module,
+        function,
+        args,
+        vec![],
+        value,
+        gas,
+        cross_space_depth,
+    );
+    *gas_left -= output.gas_used.min(gas);
+
+    // A Move abort reverts the EVM frame with the abort code and location
+    // as the revert data. Other failures revert with the message as the
+    // revert reason.
+    output.result.map_err(|err| match err {
+        CallMoveVMError::Abort {
+            address,
+            module,
+            code,
+        } => vm::Error::MoveAbort {
+            address,
+            module,
+            code,
+        },
+        CallMoveVMError::Other(msg) => vm::Error::RevertedWithReason(msg),
+    })
+}
+
 make_solidity_function! {
     pub struct Log(Bytes, "log(bytes)");
 }
@@ -226,6 +351,31 @@ impl SimpleExecutionTrait for EncodeBytes32 {
     }
 }
 
+make_solidity_function! {
+    pub struct StaticReadStorage((Address, Bytes32), "staticReadStorage(address,bytes32)", Bytes32);
+}
+impl_function_type!(StaticReadStorage, "query", gas: |spec: &Spec| spec.sload_gas);
+impl SimpleExecutionTrait for StaticReadStorage {
+    fn execute_inner(
+        &self,
+        (address, key): (Address, Bytes32),
+        _params: &ActionParams,
+        context: &mut InternalRefContext,
+        tracer: &mut dyn VmObserve,
+    ) -> vm::Result<Bytes32> {
+        let value = context.storage_at_address(&address.with_evm_space(), &key, tracer)?;
+        let mut word = [0u8; 32];
+        value.to_big_endian(&mut word);
+        Ok(word)
+    }
+}
+
+make_solidity_event! {
+    /// Emitted by the executor when a cross-space call from Move completes.
+    /// The second topic is the keccak hash of the Move caller info.
+    pub struct CrossVMCallEvent("CrossVMCall(address,bytes32,bytes4,uint256,bool)", indexed: (Address, H256), non_indexed: ([u8; 4], U256, bool));
+}
+
 // make_solidity_event! {
 //     pub struct CallEvent("Call(bytes20,bytes20,uint256,uint256,bytes)", indexed: (Bytes20, Bytes20), non_indexed: (U256, U256, Bytes));
 // }
@@ -7,6 +7,19 @@ pub(super) mod cross_space;
 mod future;
 pub(super) mod system_storage;
 
+use super::components::{InternalContractTrait, InternalRefContext, IsActive, SolFnTable};
+use crate::{
+    bytes::Bytes,
+    evm::{ActionParams, Spec},
+    observer::VmObserve,
+    spec::CommonParams,
+    vm::{ExecTrapResult, GasLeft},
+};
+use cfx_types::{Address, H256};
+use parking_lot::Mutex;
+use primitives::BlockNumber;
+use std::{collections::HashSet, sync::Arc};
+
 mod preludes {
     pub use super::super::components::{
         activation::IsActive,
@@ -37,9 +50,14 @@ mod preludes {
     pub use sha3_macro::keccak;
 }
 
-/// All Built-in contracts. All these addresses will be initialized as an
-/// internal contract in the genesis block of test mode.
-pub fn all_internal_contracts() -> Vec<Box<dyn super::InternalContractTrait>> {
+lazy_static! {
+    static ref REGISTERED_CONTRACTS: Mutex<Vec<Arc<dyn InternalContractTrait>>> =
+        Mutex::new(Vec::new());
+}
+
+/// All Built-in contracts. These addresses will be initialized as an internal
+/// contract in the genesis block of test mode once they are active.
+fn builtin_internal_contracts() -> Vec<Box<dyn InternalContractTrait>> {
     vec![
         Box::new(context::Context::instance()),
         Box::new(cross_space::CrossSpaceCall::instance()),
@@ -50,3 +68,150 @@ pub fn all_internal_contracts() -> Vec<Box<dyn super::InternalContractTrait>> {
         Box::new(future::Reserved11::instance()),
     ]
 }
+
+/// The internal contracts active under `spec`, in the order of
+/// `internal_contracts_of_all_forks`. The reserved contracts stay dormant
+/// until their activation.
+pub fn all_internal_contracts(spec: &Spec) -> Vec<Box<dyn InternalContractTrait>> {
+    internal_contracts_of_all_forks()
+        .into_iter()
+        .filter(|contract| contract.is_active(spec))
+        .collect()
+}
+
+/// The built-in contracts followed by the contracts added with
+/// `register_internal_contract`, whether they are active or not.
+pub fn internal_contracts_of_all_forks() -> Vec<Box<dyn InternalContractTrait>> {
+    let mut contracts = builtin_internal_contracts();
+    for contract in REGISTERED_CONTRACTS.lock().iter() {
+        if contracts
+            .iter()
+            .all(|existing| existing.address() != contract.address())
+        {
+            contracts.push(Box::new(RegisteredContract(contract.clone())));
+        }
+    }
+    contracts
+}
+
+/// Check that the internal contracts have distinct addresses. Returns the
+/// first duplicated address.
+pub fn validate_internal_contracts(
+    contracts: &[Box<dyn InternalContractTrait>],
+) -> Result<(), Address> {
+    let mut addresses = HashSet::new();
+    for contract in contracts {
+        if !addresses.insert(*contract.address()) {
+            return Err(*contract.address());
+        }
+    }
+    Ok(())
+}
+
+/// Add an internal contract on top of the built-in ones. It takes effect for
+/// the machines created afterwards. Returns the address if it is already
+/// taken by another internal contract.
+pub fn register_internal_contract(contract: Box<dyn InternalContractTrait>) -> Result<(), Address> {
+    let mut registered = REGISTERED_CONTRACTS.lock();
+    let address = *contract.address();
+    let taken = builtin_internal_contracts()
+        .iter()
+        .any(|builtin| *builtin.address() == address)
+        || registered.iter().any(|other| *other.address() == address);
+    if taken {
+        return Err(address);
+    }
+    registered.push(Arc::from(contract));
+    Ok(())
+}
+
+/// A registered contract shared by all the machines.
+struct RegisteredContract(Arc<dyn InternalContractTrait>);
+
+impl InternalContractTrait for RegisteredContract {
+    fn address(&self) -> &Address {
+        self.0.address()
+    }
+
+    fn initialize_block(&self, params: &CommonParams) -> BlockNumber {
+        self.0.initialize_block(params)
+    }
+
+    fn get_func_table(&self) -> &SolFnTable {
+        self.0.get_func_table()
+    }
+
+    fn execute(
+        &self,
+        params: &ActionParams,
+        context: &mut InternalRefContext,
+        tracer: &mut dyn VmObserve,
+    ) -> ExecTrapResult<GasLeft> {
+        self.0.execute(params, context, tracer)
+    }
+
+    fn code(&self) -> Arc<Bytes> {
+        self.0.code()
+    }
+
+    fn code_hash(&self) -> H256 {
+        self.0.code_hash()
+    }
+
+    fn code_size(&self) -> usize {
+        self.0.code_size()
+    }
+}
+
+impl IsActive for RegisteredContract {
+    fn is_active(&self, spec: &Spec) -> bool {
+        self.0.is_active(spec)
+    }
+}
+
+#[test]
+fn test_register_conflicting_internal_contract() {
+    use cfx_parameters::internal_contract_addresses::RESERVED3;
+
+    let contract = Box::new(future::Reserved3::instance());
+    assert_eq!(register_internal_contract(contract), Err(*RESERVED3));
+}
+
+#[test]
+fn test_validate_internal_contracts() {
+    use cfx_parameters::internal_contract_addresses::RESERVED3;
+
+    assert_eq!(
+        validate_internal_contracts(&internal_contracts_of_all_forks()),
+        Ok(())
+    );
+
+    let contracts: Vec<Box<dyn InternalContractTrait>> = vec![
+        Box::new(future::Reserved3::instance()),
+        Box::new(future::Reserved8::instance()),
+        Box::new(future::Reserved3::instance()),
+    ];
+    assert_eq!(validate_internal_contracts(&contracts), Err(*RESERVED3));
+}
+
+#[test]
+fn test_all_internal_contracts_before_activation() {
+    use crate::{machine::new_machine_with_builtin, vm_factory::VmFactory};
+    use cfx_parameters::internal_contract_addresses::{
+        CROSS_SPACE_CONTRACT_ADDRESS, RESERVED11, RESERVED3, RESERVED8, RESERVED9,
+    };
+
+    let machine = new_machine_with_builtin(Default::default(), VmFactory::new(1024 * 32));
+    let spec = machine.spec(0);
+    let addresses: Vec<Address> = all_internal_contracts(&spec)
+        .iter()
+        .map(|contract| *contract.address())
+        .collect();
+    assert!(addresses.contains(&*CROSS_SPACE_CONTRACT_ADDRESS));
+    for reserved in [*RESERVED3, *RESERVED8, *RESERVED9, *RESERVED11] {
+        assert!(!addresses.contains(&reserved));
+    }
+    assert!(internal_contracts_of_all_forks()
+        .iter()
+        .any(|contract| *contract.address() == *RESERVED3));
+}
@@ -3,11 +3,92 @@
 // See http://www.gnu.org/licenses/
 
 use super::preludes::*;
+use crate::internal_bail;
 use cfx_parameters::internal_contract_addresses::SYSTEM_STORAGE_ADDRESS;
 use cfx_types::U256;
 
+type Bytes32 = [u8; 32];
+
 make_solidity_contract! {
-    pub struct SystemStorage(SYSTEM_STORAGE_ADDRESS, SolFnTable::default, initialize: |params: &CommonParams| params.transition_numbers.cip94, is_active: |spec: &Spec| spec.cip94);
+    pub struct SystemStorage(SYSTEM_STORAGE_ADDRESS, generate_fn_table, initialize: |params: &CommonParams| params.transition_numbers.cip94, is_active: |spec: &Spec| spec.cip94);
+}
+
+fn generate_fn_table() -> SolFnTable {
+    make_function_table!(SetBatch, GetBatch)
+}
+
+group_impl_is_active!(|spec: &Spec| spec.cip94, SetBatch, GetBatch);
+
+make_solidity_function! {
+    struct SetBatch((Vec<Bytes32>, Vec<Bytes32>), "setBatch(bytes32[],bytes32[])");
+}
+
+impl_function_type!(SetBatch, "non_payable_write");
+
+impl UpfrontPaymentTrait for SetBatch {
+    fn upfront_gas_payment(
+        &self,
+        (keys, _values): &(Vec<Bytes32>, Vec<Bytes32>),
+        _params: &ActionParams,
+        context: &InternalRefContext,
+    ) -> DbResult<U256> {
+        Ok(U256::from(context.spec.sstore_reset_gas) * keys.len())
+    }
+}
+
+impl SimpleExecutionTrait for SetBatch {
+    fn execute_inner(
+        &self,
+        (keys, values): (Vec<Bytes32>, Vec<Bytes32>),
+        params: &ActionParams,
+        context: &mut InternalRefContext,
+        _tracer: &mut dyn VmObserve,
+    ) -> vm::Result<()> {
+        if keys.len() != values.len() {
+            internal_bail!("Keys and values have different lengths");
+        }
+        for (key, value) in keys.into_iter().zip(values) {
+            context.set_storage(params, key.to_vec(), U256::from_big_endian(&value))?;
+        }
+        Ok(())
+    }
+}
+
+make_solidity_function! {
+    struct GetBatch(Vec<Bytes32>, "getBatch(bytes32[])", Vec<Bytes32>);
+}
+
+impl_function_type!(GetBatch, "query");
+
+impl UpfrontPaymentTrait for GetBatch {
+    fn upfront_gas_payment(
+        &self,
+        keys: &Vec<Bytes32>,
+        _params: &ActionParams,
+        context: &InternalRefContext,
+    ) -> DbResult<U256> {
+        Ok(U256::from(context.spec.sload_gas) * keys.len())
+    }
+}
+
+impl SimpleExecutionTrait for GetBatch {
+    fn execute_inner(
+        &self,
+        keys: Vec<Bytes32>,
+        params: &ActionParams,
+        context: &mut InternalRefContext,
+        tracer: &mut dyn VmObserve,
+    ) -> vm::Result<Vec<Bytes32>> {
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            let mut word = [0u8; 32];
+            context
+                .storage_at(params, &key, tracer)?
+                .to_big_endian(&mut word);
+            values.push(word);
+        }
+        Ok(values)
+    }
 }
 
 #[allow(unused)]
@@ -15,3 +96,9 @@ pub fn base_slot(contract: Address) -> U256 {
     let hash = keccak(H256::from(contract).as_ref());
     U256::from_big_endian(hash.as_ref())
 }
+
+#[test]
+fn test_system_storage_contract_sig() {
+    check_func_signature!(SetBatch, "6eed5e58");
+    check_func_signature!(GetBatch, "50a5fd68");
+}
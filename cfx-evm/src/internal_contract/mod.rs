@@ -8,6 +8,10 @@ mod impls;
 mod utils;
 
 pub use self::{
-    components::{InterfaceTrait, InternalContractMap, InternalContractTrait, InternalRefContext},
+    components::{
+        InterfaceTrait, InternalContractMap, InternalContractTrait, InternalRefContext, IsActive,
+        SolFnTable, SolidityEventTrait,
+    },
+    contracts::{cross_space::CrossVMCallEvent, register_internal_contract},
     impls::admin::suicide,
 };
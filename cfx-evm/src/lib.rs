@@ -24,12 +24,16 @@ mod vm_factory;
 pub const EVM_CHAINID: u64 = 129;
 
 pub use call_create_frame::contract_address;
-pub use cfx_state::{CallMoveVMTrait, StateTrait};
+pub use cfx_state::{CallMoveVMError, CallMoveVMOutput, CallMoveVMTrait, StateTrait};
 pub use cfx_statedb::{StateDb, StateDbExt, StateDbTrait};
 pub use cfx_storage::StorageTrait;
 pub use evm::FinalizationResult;
 pub use execution::{
-    CrossVMParams, CrossVMReturn, ExecutionOutcome, TXExecutor, TransactOptions, TransactionInfo,
+    CrossVMAction, CrossVMArg, CrossVMParams, CrossVMParamsBuilder, CrossVMReturn, EnvOverride,
+    ExecutionOutcome, ReceiptCache, TXExecutor, TransactOptions, TransactionInfo,
+};
+pub use internal_contract::{
+    register_internal_contract, InternalContractTrait, InternalRefContext, IsActive, SolFnTable,
 };
 pub use machine::{new_machine_with_builtin, Machine};
 pub use spec::CommonParams;
@@ -14,13 +14,17 @@ use cfx_types::{Address, Bloom, BloomInput, Space, H256, U256, U64};
 use malloc_size_of_derive::MallocSizeOf;
 use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
 use rlp_derive::{RlpDecodable, RlpEncodable};
-use serde::{ser::SerializeStruct, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use serde::{
+    de::Error as _, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer,
+};
 use strum_macros::EnumDiscriminants;
 
 /// Description of a _call_ action, either a `CALL` operation or a message
 /// transaction.
-#[derive(Debug, Clone, PartialEq, RlpEncodable, RlpDecodable, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Call {
     /// The space
     pub space: Space,
@@ -75,8 +79,9 @@ impl Call {
 }
 
 /// The outcome of the action result.
-#[derive(Debug, PartialEq, Clone, Serialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Outcome {
     Success,
     Reverted,
@@ -110,8 +115,9 @@ impl Decodable for Outcome {
 }
 
 /// Description of the result of a _call_ action.
-#[derive(Debug, Clone, PartialEq, RlpEncodable, RlpDecodable, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct CallResult {
     /// The outcome of the result
     pub outcome: Outcome,
@@ -155,8 +161,9 @@ impl From<&vmResult<FrameReturn>> for CallResult {
 
 /// Description of a _create_ action, either a `CREATE` operation or a create
 /// transaction.
-#[derive(Debug, Clone, PartialEq, RlpEncodable, RlpDecodable, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Create {
     /// Space
     pub space: Space,
@@ -194,8 +201,9 @@ impl Create {
 }
 
 /// Description of the result of a _create_ action.
-#[derive(Debug, Clone, PartialEq, RlpEncodable, RlpDecodable, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct CreateResult {
     /// The outcome of the create
     pub outcome: Outcome,
@@ -268,12 +276,13 @@ pub struct InternalTransferAction {
     pub value: U256,
 }
 
+#[cfg(feature = "serde")]
 impl Serialize for InternalTransferAction {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut s = serializer.serialize_struct("InternalTransferAction", 5)?;
+        let mut s = serializer.serialize_struct("InternalTransferAction", 7)?;
         s.serialize_field("from", &self.from.inner_address_or_default())?;
         s.serialize_field("fromPocket", &*self.from.pocket())?;
         s.serialize_field("fromSpace", &*self.from.space())?;
@@ -285,6 +294,56 @@ impl Serialize for InternalTransferAction {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for InternalTransferAction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Fields {
+            from: Address,
+            from_pocket: String,
+            from_space: String,
+            to: Address,
+            to_pocket: String,
+            to_space: String,
+            value: U256,
+        }
+
+        let fields = Fields::deserialize(deserializer)?;
+        Ok(InternalTransferAction {
+            from: address_pocket(fields.from, &fields.from_pocket, &fields.from_space)
+                .map_err(D::Error::custom)?,
+            to: address_pocket(fields.to, &fields.to_pocket, &fields.to_space)
+                .map_err(D::Error::custom)?,
+            value: fields.value,
+        })
+    }
+}
+
+/// Rebuilds the `AddressPocket` from the fields written by
+/// `InternalTransferAction::serialize`.
+#[cfg(feature = "serde")]
+fn address_pocket(address: Address, pocket: &str, space: &str) -> Result<AddressPocket, String> {
+    Ok(match (pocket, space) {
+        ("balance", "evm") => AddressPocket::Balance(cfx_types::AddressWithSpace {
+            address,
+            space: Space::Ethereum,
+        }),
+        ("mint_or_burn", "none") => AddressPocket::MintBurn,
+        ("gas_payment", "none") => AddressPocket::GasPayment,
+        ("move_space", "none") => AddressPocket::MoveSpace,
+        _ => {
+            return Err(format!(
+                "Invalid address pocket {} in space {}",
+                pocket, space
+            ))
+        },
+    })
+}
+
 impl InternalTransferAction {
     pub fn bloom(&self) -> Bloom {
         let mut bloom = Bloom::default();
@@ -298,6 +357,7 @@ impl InternalTransferAction {
 
 /// Description of an action that we trace; will be either a call or a create.
 #[derive(Debug, Clone, PartialEq, EnumDiscriminants)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[strum_discriminants(name(ActionType))]
 pub enum Action {
     /// It's a call action.
@@ -371,6 +431,7 @@ impl Action {
 ///
 /// Parent and children indexes refer to positions in this vector.
 #[derive(Debug, PartialEq, Clone, MallocSizeOf)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ExecTrace {
     #[ignore_malloc_size_of = "ignored for performance reason"]
     /// Type of action performed by a transaction.
@@ -32,6 +32,12 @@ pub struct CommonParams {
     /// Set the internal contracts to state at the genesis blocks, even if it
     /// is not activated.
     pub early_set_internal_contracts_states: bool,
+    /// Maximum depth of the nested cross-space calls.
+    pub max_cross_space_depth: usize,
+    /// Maximum size of the calldata of a cross-space call.
+    pub max_cross_space_calldata_size: usize,
+    /// Intrinsic gas of a cross-space call entering the EVM space.
+    pub cross_space_call_gas: usize,
     /// The upgrades activated at given block number.
     pub transition_numbers: TransitionsBlockNumber,
     /// The upgrades activated at given block height (a.k.a. epoch number).
@@ -96,6 +102,9 @@ impl Default for CommonParams {
             max_transaction_size: 300 * 1024,
             evm_transaction_gas_ratio: EVM_TRANSACTION_GAS_RATIO,
             early_set_internal_contracts_states: false,
+            max_cross_space_depth: 4,
+            max_cross_space_calldata_size: 128 * 1024,
+            cross_space_call_gas: 0,
             transition_numbers: Default::default(),
             transition_heights: Default::default(),
         }
@@ -64,8 +64,15 @@ pub struct State<'a> {
     // Checkpoint to the changes.
     world_statistics_checkpoints: RwLock<Vec<WorldStatistics>>,
     checkpoints: RwLock<Vec<HashMap<AddressWithSpace, Option<AccountEntry>>>>,
+
+    // Transient storage (EIP-1153) of the current transaction, and the old
+    // values of the slots written after each checkpoint.
+    transient_storage: HashMap<TransientStorageKey, U256>,
+    transient_storage_checkpoints: Vec<HashMap<TransientStorageKey, Option<U256>>>,
 }
 
+type TransientStorageKey = (AddressWithSpace, Vec<u8>);
+
 impl<'a> StateTrait for State<'a> {
     fn commit(
         &mut self,
@@ -321,6 +328,25 @@ impl<'a> StateOpsTrait for State<'a> {
     fn get_system_storage(&self, key: &[u8]) -> DbResult<U256> {
         self.storage_at(&SYSTEM_STORAGE_ADDRESS.with_evm_space(), key)
     }
+
+    fn transient_storage_at(&self, address: &AddressWithSpace, key: &[u8]) -> U256 {
+        self.transient_storage
+            .get(&(*address, key.to_vec()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn set_transient_storage(&mut self, address: &AddressWithSpace, key: Vec<u8>, value: U256) {
+        let key = (*address, key);
+        let old_value = if value.is_zero() {
+            self.transient_storage.remove(&key)
+        } else {
+            self.transient_storage.insert(key.clone(), value)
+        };
+        if let Some(checkpoint) = self.transient_storage_checkpoints.last_mut() {
+            checkpoint.entry(key).or_insert(old_value);
+        }
+    }
 }
 
 impl<'a> CheckpointTrait for State<'a> {
@@ -332,6 +358,7 @@ impl<'a> CheckpointTrait for State<'a> {
         self.world_statistics_checkpoints
             .get_mut()
             .push(self.world_statistics.clone());
+        self.transient_storage_checkpoints.push(HashMap::new());
         let checkpoints = self.checkpoints.get_mut();
         let index = checkpoints.len();
         checkpoints.push(HashMap::new());
@@ -357,6 +384,7 @@ impl<'a> CheckpointTrait for State<'a> {
                 }
             }
         }
+        self.discard_transient_storage_checkpoint();
     }
 
     /// Revert to the last checkpoint and discard it.
@@ -387,6 +415,7 @@ impl<'a> CheckpointTrait for State<'a> {
                 }
             }
         }
+        self.revert_transient_storage_checkpoint();
     }
 }
 
@@ -414,6 +443,8 @@ impl<'a> State<'a> {
             cache: Default::default(),
             world_statistics_checkpoints: Default::default(),
             checkpoints: Default::default(),
+            transient_storage: Default::default(),
+            transient_storage_checkpoints: Default::default(),
             world_statistics,
             accounts_to_notify: Default::default(),
         })
@@ -435,11 +466,42 @@ impl<'a> State<'a> {
             cache: Default::default(),
             world_statistics_checkpoints: Default::default(),
             checkpoints: Default::default(),
+            transient_storage: Default::default(),
+            transient_storage_checkpoints: Default::default(),
             world_statistics,
             accounts_to_notify: Default::default(),
         })
     }
 
+    // The transient storage is dropped when the outermost checkpoint is closed.
+    fn discard_transient_storage_checkpoint(&mut self) {
+        if let Some(checkpoint) = self.transient_storage_checkpoints.pop() {
+            match self.transient_storage_checkpoints.last_mut() {
+                Some(prev) => {
+                    for (k, v) in checkpoint {
+                        prev.entry(k).or_insert(v);
+                    }
+                },
+                None => self.transient_storage.clear(),
+            }
+        }
+    }
+
+    fn revert_transient_storage_checkpoint(&mut self) {
+        if let Some(checkpoint) = self.transient_storage_checkpoints.pop() {
+            if self.transient_storage_checkpoints.is_empty() {
+                self.transient_storage.clear();
+            } else {
+                for (k, v) in checkpoint {
+                    match v {
+                        Some(v) => self.transient_storage.insert(k, v),
+                        None => self.transient_storage.remove(&k),
+                    };
+                }
+            }
+        }
+    }
+
     fn needs_update(require: RequireCache, account: &OverlayAccount) -> bool {
         trace!("update_account_cache account={:?}", account);
         match require {
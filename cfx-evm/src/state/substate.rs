@@ -12,6 +12,13 @@ use std::collections::{HashMap, HashSet};
 pub struct FrameStackInfo {
     call_stack_recipient_addresses: Vec<(AddressWithSpace, bool)>,
     address_counter: HashMap<AddressWithSpace, u32>,
+    /// The number of cross-space calls on the call chain that leads to this
+    /// frame stack.
+    cross_space_depth: usize,
+    /// The number of instructions executed by all the frames.
+    steps: u64,
+    /// If set, the execution fails once `steps` would exceed this limit.
+    max_steps: Option<u64>,
 }
 
 impl FrameStackInfo {
@@ -19,9 +26,37 @@ impl FrameStackInfo {
         FrameStackInfo {
             call_stack_recipient_addresses: Vec::default(),
             address_counter: HashMap::default(),
+            cross_space_depth: 0,
+            steps: 0,
+            max_steps: None,
         }
     }
 
+    pub fn with_cross_space_depth(cross_space_depth: usize) -> Self {
+        FrameStackInfo {
+            cross_space_depth,
+            ..Self::new()
+        }
+    }
+
+    pub fn cross_space_depth(&self) -> usize {
+        self.cross_space_depth
+    }
+
+    pub fn set_max_steps(&mut self, max_steps: Option<u64>) {
+        self.max_steps = max_steps;
+    }
+
+    /// Counts an executed instruction. Returns `false` if the instruction
+    /// exceeds the step limit.
+    pub fn count_step(&mut self) -> bool {
+        if self.max_steps.map_or(false, |limit| self.steps >= limit) {
+            return false;
+        }
+        self.steps += 1;
+        true
+    }
+
     pub fn push(&mut self, address: AddressWithSpace, is_create: bool) {
         self.call_stack_recipient_addresses
             .push((address.clone(), is_create));
@@ -84,10 +119,11 @@ impl Substate {
     }
 }
 
-/// Get the cleanup mode object from this.
+/// Get the cleanup mode object from this. The touched accounts are not tracked
+/// for the dust collection if storage collateral is disabled.
 pub fn cleanup_mode<'a>(substate: &'a mut Substate, spec: &Spec) -> CleanupMode<'a> {
     match (
-        spec.kill_dust != CleanDustMode::Off,
+        spec.storage_collateral_enabled && spec.kill_dust != CleanDustMode::Off,
         spec.no_empty,
         spec.kill_empty,
     ) {
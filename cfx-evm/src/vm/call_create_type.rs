@@ -22,11 +22,13 @@
 
 use crate::evm::CreateContractAddress;
 use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// The type of the call-like instruction.
-#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum CallType {
     /// Not a CALL.
     None,
@@ -71,8 +73,9 @@ impl Decodable for CallType {
 }
 
 /// The type of the create-like instruction.
-#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum CreateType {
     /// Not a create
     None,
@@ -166,6 +166,12 @@ pub trait Context {
     /// then A depth is 0, B is 1, C is 2 and so on.
     fn depth(&self) -> usize;
 
+    /// Count an instruction to be executed, and fail if the step limit of the
+    /// frame stack is reached.
+    fn count_step(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     /// Decide if any more operations should be traced. Passthrough for the VM
     /// trace.
     fn trace_next_instruction(&mut self, _pc: usize, _instruction: u8, _current_gas: U256) -> bool {
@@ -47,6 +47,9 @@ pub struct Env {
     pub accumulated_gas_used: U256,
     /// The epoch height.
     pub epoch_height: u64,
+    /// The base fee per gas of the block (EIP-1559). It is `None` if the fee
+    /// market is not enabled.
+    pub base_fee_per_gas: Option<U256>,
 }
 
 #[cfg(test)]
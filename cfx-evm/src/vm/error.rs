@@ -20,10 +20,10 @@
 
 //! VM errors module
 
-use super::{action_params::ActionParams, ResumeCall, ResumeCreate};
+use super::{action_params::ActionParams, CallType, ResumeCall, ResumeCreate};
 // use bls_signatures::Error as CryptoError;
 use cfx_statedb::{Error as DbError, Result as DbResult};
-use cfx_types::{Address, U256};
+use cfx_types::{Address, Space, H256, U256};
 use solidity_abi::ABIDecodeError;
 use std::fmt;
 
@@ -117,10 +117,66 @@ pub enum Error {
     OutOfBounds,
     /// Execution has been reverted with REVERT.
     Reverted,
+    /// Returned on revert with a reason, which is returned to the caller in
+    /// the `Error(string)` format
+    RevertedWithReason(String),
+    /// Returned when a call into the Move VM aborted with `code` in module
+    /// `module` published at `address`
+    MoveAbort {
+        address: H256,
+        module: String,
+        code: u64,
+    },
     /// Invalid address
     InvalidAddress(Address),
     /// Create a contract on an address with existing contract
     ConflictAddress(Address),
+    /// When the nested cross-space calls exceed the depth limit
+    CrossSpaceReentrancyLimit,
+    /// When the combined depth of the frames and the cross-space calls exceeds
+    /// the depth limit of the frame stack
+    OutOfDepth,
+    /// When the number of executed instructions exceeds the step limit of a
+    /// cross-space call
+    StepLimitReached,
+    /// When the init code of a cross-space contract creation exceeds
+    /// `Spec::init_code_data_limit`
+    InitCodeTooLarge {
+        limit: usize,
+        actual: usize,
+    },
+    /// When the calldata of a cross-space call exceeds the size limit
+    CrossSpaceCalldataTooLarge {
+        limit: usize,
+        actual: usize,
+    },
+    /// When a cross-space call uses a call type other than `CALL` and
+    /// `DELEGATECALL`
+    UnsupportedCrossSpaceCallType(CallType),
+    /// When a cross-space call targets a space other than the EVM space
+    UnsupportedCrossSpaceTarget(Space),
+    /// When a cross-space delegate call carries value
+    CrossSpaceDelegateCallWithValue,
+    /// When a cross-space call requiring a contract targets an address
+    /// without code
+    CrossSpaceTargetNotContract(Address),
+    /// When the function name of a cross-space call is not a legal Solidity
+    /// identifier
+    InvalidFunctionName(String),
+    /// When a cross-space call sets neither a function name nor raw calldata
+    MissingCrossSpaceCalldata,
+    /// When the sponsor of a cross-space call can not afford the gas of the
+    /// call
+    NotEnoughSponsorBalance {
+        required: U256,
+        got: U256,
+    },
+    /// When the sponsor of a cross-space call has not authorized the caller
+    UnauthorizedSponsor(Address),
+    /// An error only known by its display string, e.g., an error with fields
+    /// restored from a serialized `ExecutionOutcome`. It is displayed as the
+    /// message itself.
+    Message(String),
 }
 
 #[derive(Debug)]
@@ -189,10 +245,52 @@ impl fmt::Display for Error {
             Wasm(ref msg) => write!(f, "Internal error: {}", msg),
             OutOfBounds => write!(f, "Out of bounds"),
             Reverted => write!(f, "Reverted by bytecode"),
+            RevertedWithReason(ref reason) => write!(f, "Reverted: {}", reason),
+            MoveAbort {
+                ref address,
+                ref module,
+                code,
+            } => write!(f, "Move abort {} in {:?}::{}", code, address, module),
             InvalidAddress(ref addr) => write!(f, "InvalidAddress: {}", addr),
             ConflictAddress(ref addr) => {
                 write!(f, "Contract creation on an existing address: {}", addr)
             },
+            CrossSpaceReentrancyLimit => write!(f, "Exceed cross-space call depth limit"),
+            OutOfDepth => write!(f, "Exceed frame stack depth limit"),
+            StepLimitReached => write!(f, "Exceed cross-space call step limit"),
+            InitCodeTooLarge { limit, actual } => {
+                write!(f, "Init code too large {}/{}", actual, limit)
+            },
+            CrossSpaceCalldataTooLarge { limit, actual } => {
+                write!(f, "Cross-space calldata too large {}/{}", actual, limit)
+            },
+            UnsupportedCrossSpaceCallType(ref call_type) => {
+                write!(f, "Unsupported cross-space call type {:?}", call_type)
+            },
+            UnsupportedCrossSpaceTarget(ref space) => {
+                write!(f, "Unsupported cross-space target space {:?}", space)
+            },
+            CrossSpaceDelegateCallWithValue => {
+                write!(f, "Cross-space delegate call can not carry value")
+            },
+            CrossSpaceTargetNotContract(ref addr) => {
+                write!(f, "Cross-space call target is not a contract: {:?}", addr)
+            },
+            InvalidFunctionName(ref name) => write!(f, "Invalid function name: {:?}", name),
+            MissingCrossSpaceCalldata => {
+                write!(f, "Cross-space call without function name or calldata")
+            },
+            NotEnoughSponsorBalance { required, got } => {
+                write!(f, "Not enough sponsor balance for gas {}/{}", required, got)
+            },
+            UnauthorizedSponsor(ref sponsor) => {
+                write!(
+                    f,
+                    "Cross-space call sponsor {:?} is not authorized",
+                    sponsor
+                )
+            },
+            Message(ref msg) => write!(f, "{}", msg),
         }
     }
 }
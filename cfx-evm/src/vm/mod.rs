@@ -23,7 +23,7 @@ pub use self::{
         TrapResult,
     },
     return_data::{GasLeft, ReturnData},
-    spec::{CleanDustMode, Spec, WasmCosts},
+    spec::{CleanDustMode, RefundCap, Spec, WasmCosts},
 };
 use crate::observer::VmObserve;
 
@@ -33,6 +33,13 @@ pub struct Spec {
     pub stack_limit: usize,
     /// Max number of nested calls/creates
     pub max_depth: usize,
+    /// Maximum depth of the nested cross-space calls
+    pub max_cross_space_depth: usize,
+    /// Maximum size of the calldata of a cross-space call
+    pub max_cross_space_calldata_size: usize,
+    /// Gas charged from the gas of a cross-space call for entering the EVM
+    /// space, before the top frame is executed
+    pub cross_space_call_gas: usize,
     /// Gas prices for instructions in all tiers
     pub tier_step_gas: [usize; 8],
     /// Gas price for `EXP` opcode
@@ -79,6 +86,9 @@ pub struct Spec {
     pub create_data_gas: usize,
     /// Maximum code size when creating a contract.
     pub create_data_limit: usize,
+    /// Maximum init code size of a contract creation. Like EIP-3860, it is
+    /// twice the maximum code size.
+    pub init_code_data_limit: usize,
     /// Transaction cost
     pub tx_gas: usize,
     /// `CREATE` transaction cost
@@ -87,6 +97,10 @@ pub struct Spec {
     pub tx_data_zero_gas: usize,
     /// Aditional cost for non-empty data transaction
     pub tx_data_non_zero_gas: usize,
+    /// Additional cost for each address in the access list (EIP-2930)
+    pub access_list_address_cost: usize,
+    /// Additional cost for each storage key in the access list (EIP-2930)
+    pub access_list_storage_key_cost: usize,
     /// Gas price for copying memory
     pub copy_gas: usize,
     /// Price of EXTCODESIZE
@@ -114,6 +128,11 @@ pub struct Spec {
     ///     let CALL's gas = (requested > GAS ? \[OOG\] : GAS);
     ///     let CREATE's gas = GAS;
     pub sub_gas_cap_divisor: Option<usize>,
+    /// The unused gas refunded to the sender of a transaction is at most the
+    /// base selected by `refund_cap` divided by this quotient.
+    pub max_refund_quotient: usize,
+    /// The base of the cap of the refunded unused gas.
+    pub refund_cap: RefundCap,
     /// Don't ever make empty accounts; contracts start with nonce=1. Also,
     /// don't charge 25k when sending/suicide zero-value.
     pub no_empty: bool,
@@ -123,6 +142,11 @@ pub struct Spec {
     pub blockhash_gas: usize,
     /// Kill basic accounts below this balance if touched.
     pub kill_dust: CleanDustMode,
+    /// Whether storage is charged with collateral, as in the Conflux core
+    /// space. The EVM space always runs collateral-free, so no collateral is
+    /// kept in the substate and the dust collection, which refunds the
+    /// collateral of the killed accounts, is skipped while it is `false`.
+    pub storage_collateral_enabled: bool,
     /// VM execution does not increase null signed address nonce if this field
     /// is true.
     pub keep_unsigned_nonce: bool,
@@ -229,6 +253,18 @@ pub enum CleanDustMode {
     WithCodeAndStorage,
 }
 
+/// The base of the cap of the unused gas refunded to the sender of a
+/// transaction, see `Spec::max_refund_quotient`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RefundCap {
+    /// The refund is capped by the gas limit. With the quotient 4, it is the
+    /// Conflux rule which charges at least 3/4 of the gas limit.
+    GasLimit,
+    /// The refund is capped by the gas used, like EIP-3529 (with the quotient
+    /// 5).
+    GasUsed,
+}
+
 impl Spec {
     /// The spec when Conflux launches the mainnet. It should never changed
     /// since the mainnet has launched.
@@ -237,6 +273,9 @@ impl Spec {
             exceptional_failed_code_deposit: true,
             stack_limit: 1024,
             max_depth: 1024,
+            max_cross_space_depth: 4,
+            max_cross_space_calldata_size: 128 * 1024,
+            cross_space_call_gas: 0,
             tier_step_gas: [0, 2, 3, 5, 8, 10, 20, 0],
             exp_gas: 10,
             exp_byte_gas: 50,
@@ -260,10 +299,13 @@ impl Spec {
             quad_coeff_div: 512,
             create_data_gas: 200,
             create_data_limit: 49152,
+            init_code_data_limit: 98304,
             tx_gas: 21000,
             tx_create_gas: 53000,
             tx_data_zero_gas: 4,
             tx_data_non_zero_gas: 68,
+            access_list_address_cost: 2400,
+            access_list_storage_key_cost: 1900,
             copy_gas: 3,
             extcodesize_gas: 700,
             extcodecopy_base_gas: 700,
@@ -274,6 +316,8 @@ impl Spec {
             eip1820_gas: 1_500_000,
             suicide_to_new_account_cost: 25000,
             sub_gas_cap_divisor: Some(64),
+            max_refund_quotient: 4,
+            refund_cap: RefundCap::GasLimit,
             no_empty: true,
             kill_empty: true,
             blockhash_gas: 20,
@@ -283,6 +327,7 @@ impl Spec {
              * should be 0. */
             account_start_nonce: U256([0, 0, 0, 0]),
             kill_dust: CleanDustMode::Off,
+            storage_collateral_enabled: false,
             keep_unsigned_nonce: false,
             wasm: None,
             cip43_init: false,
@@ -305,6 +350,9 @@ impl Spec {
 
     pub fn new_spec_from_common_params(params: &CommonParams, number: BlockNumber) -> Spec {
         let mut spec = Self::genesis_spec();
+        spec.max_cross_space_depth = params.max_cross_space_depth;
+        spec.max_cross_space_calldata_size = params.max_cross_space_calldata_size;
+        spec.cross_space_call_gas = params.cross_space_call_gas;
         spec.cip43_contract = number >= params.transition_numbers.cip43a;
         spec.cip43_init =
             number >= params.transition_numbers.cip43a && number < params.transition_numbers.cip43b;
@@ -5,8 +5,8 @@
 extern crate ethereum_types;
 extern crate rlp;
 extern crate rlp_derive;
+#[cfg(feature = "serde")]
 extern crate serde;
-extern crate serde_derive;
 
 pub use self::space_util::AddressSpaceUtil;
 pub use ethereum_types::{
@@ -15,9 +15,11 @@ pub use ethereum_types::{
 };
 use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
 use rlp_derive::{RlpDecodable, RlpEncodable};
-use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug, Ord, PartialOrd, Serialize, Deserialize)]
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Space {
     Ethereum,
 }
@@ -91,6 +93,7 @@ impl Default for Space {
 }
 
 #[derive(Default, Eq, PartialEq, Hash, Copy, Clone, Debug, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AddressWithSpace {
     pub address: Address,
     pub space: Space,
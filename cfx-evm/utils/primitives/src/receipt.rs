@@ -7,6 +7,7 @@ use cfx_types::{Address, Bloom, Space, U256, U64};
 use malloc_size_of::{MallocSizeOf, MallocSizeOfOps};
 use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
 use rlp_derive::{RlpDecodable, RlpEncodable};
+use serde_derive::{Deserialize, Serialize};
 
 pub const TRANSACTION_OUTCOME_SUCCESS: u8 = 0;
 pub const TRANSACTION_OUTCOME_EXCEPTION_WITH_NONCE_BUMPING: u8 = 1; // gas fee charged
@@ -151,6 +152,20 @@ impl MallocSizeOf for BlockReceipts {
     }
 }
 
+/// The receipt of a cross-space call from the Move space, persisted so that
+/// the indexers can query the past cross-space interactions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossSpaceReceipt {
+    /// The called contract, or the created contract of a contract creation.
+    pub receiver: Address,
+    /// The function selector of the call, or zeros for a plain transfer or a
+    /// contract creation.
+    pub selector: [u8; 4],
+    pub value: U256,
+    pub gas_used: U256,
+    pub success: bool,
+}
+
 #[test]
 fn test_transaction_outcome_rlp() {
     assert_eq!(rlp::encode(&TransactionOutcome::Success), rlp::encode(&0u8));
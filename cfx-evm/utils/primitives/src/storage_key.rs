@@ -2,7 +2,10 @@
 // Conflux is free software and distributed under GNU General Public License.
 // See http://www.gnu.org/licenses/
 
-use cfx_types::AddressWithSpace;
+use cfx_types::{Address, AddressSpaceUtil, AddressWithSpace};
+
+const STORAGE_PREFIX: [u8; 5] = *b"store";
+const CODE_PREFIX: [u8; 4] = *b"code";
 
 // The original StorageKeys unprocessed, in contrary to StorageKey which is
 // processed to use in DeltaMpt.
@@ -57,11 +60,46 @@ pub enum OwnedStateKey {
     CodeKey(AddressWithSpace),
 }
 
+impl OwnedStateKey {
+    pub fn as_state_key(&self) -> StateKey {
+        match self {
+            OwnedStateKey::AccountKey(address) => StateKey::AccountKey(address),
+            OwnedStateKey::StorageKey {
+                address,
+                storage_key,
+            } => StateKey::StorageKey {
+                address,
+                storage_key,
+            },
+            OwnedStateKey::CodeKey(address) => StateKey::CodeKey(address),
+        }
+    }
+
+    /// Decode the key from its storage encoding. The encoding does not keep
+    /// the space, so the decoded address is always in the ethereum space.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 20 {
+            return None;
+        }
+        let (address, rest) = bytes.split_at(20);
+        let address = Address::from_slice(address).with_evm_space();
+        if rest.is_empty() {
+            Some(OwnedStateKey::AccountKey(address))
+        } else if rest == &CODE_PREFIX[..] {
+            Some(OwnedStateKey::CodeKey(address))
+        } else if rest.starts_with(&STORAGE_PREFIX) {
+            Some(OwnedStateKey::StorageKey {
+                address,
+                storage_key: rest[STORAGE_PREFIX.len()..].to_vec(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
 impl<'a> From<OwnedStateKey> for Vec<u8> {
     fn from(key: OwnedStateKey) -> Self {
-        const STORAGE_PREFIX: [u8; 5] = *b"store";
-        const CODE_PREFIX: [u8; 4] = *b"code";
-
         match key {
             OwnedStateKey::AccountKey(address) => [&address.address.0[..]].concat(),
             OwnedStateKey::StorageKey {
@@ -72,3 +110,32 @@ impl<'a> From<OwnedStateKey> for Vec<u8> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::OwnedStateKey;
+    use cfx_types::{Address, AddressSpaceUtil};
+
+    #[test]
+    fn test_owned_state_key_bytes() {
+        let address = Address::from_low_u64_be(33).with_evm_space();
+        let keys = vec![
+            OwnedStateKey::AccountKey(address),
+            OwnedStateKey::CodeKey(address),
+            OwnedStateKey::StorageKey {
+                address,
+                storage_key: b"code".to_vec(),
+            },
+            OwnedStateKey::StorageKey {
+                address,
+                storage_key: vec![],
+            },
+        ];
+        for key in keys {
+            let bytes: Vec<u8> = key.clone().into();
+            assert_eq!(OwnedStateKey::from_bytes(&bytes), Some(key));
+        }
+        assert_eq!(OwnedStateKey::from_bytes(&[0u8; 19]), None);
+        assert_eq!(OwnedStateKey::from_bytes(&[0u8; 22]), None);
+    }
+}
@@ -10,12 +10,16 @@ mod string;
 #[cfg(test)]
 mod tests;
 mod utils;
+mod value;
 
 #[cfg(test)]
 #[macro_use]
 extern crate lazy_static;
 
-pub use self::utils::{read_abi_list, ABIListWriter, LinkedBytes};
+pub use self::{
+    utils::{read_abi_list, ABIListWriter, LinkedBytes},
+    value::{abi_decode_values, ABIValue},
+};
 use cfx_types::H256;
 use keccak_hash::keccak;
 
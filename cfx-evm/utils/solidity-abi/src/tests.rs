@@ -3,7 +3,7 @@
 // See http://www.gnu.org/licenses/
 
 use super::{ABIDecodable, ABIDecodeError, ABIEncodable};
-use crate::{ABIPackedEncodable, ABIVariable};
+use crate::{abi_decode_values, ABIPackedEncodable, ABIValue, ABIVariable};
 use cfx_types::{Address, U256};
 use lazy_static;
 use rustc_hex::{FromHex, ToHex};
@@ -492,3 +492,26 @@ fn test_two_dynamic_struct() {
         input
     );
 }
+
+#[test]
+fn test_decode_values() {
+    let types: Vec<String> = vec!["uint256".into(), "address".into(), "bytes[]".into()];
+    let encoded = (U256::from(33), ADDR1.clone(), vec![vec![5u8, 6], vec![7u8]]).abi_encode();
+    assert_eq!(
+        abi_decode_values(&types, encoded.as_slice()).unwrap(),
+        vec![
+            ABIValue::Uint(U256::from(33)),
+            ABIValue::Address(ADDR1.clone()),
+            ABIValue::Array(vec![ABIValue::Bytes(vec![5, 6]), ABIValue::Bytes(vec![7])]),
+        ]
+    );
+
+    assert_eq!(
+        abi_decode_values(&types, &encoded[..64]).unwrap_err(),
+        ABIDecodeError("Data is shorter than the declared types")
+    );
+    assert_eq!(
+        abi_decode_values(&["uint8".to_string()], encoded.as_slice()).unwrap_err(),
+        ABIDecodeError("Unsupported solidity type")
+    );
+}
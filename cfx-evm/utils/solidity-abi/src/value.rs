@@ -0,0 +1,76 @@
+// Copyright 2020 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use super::{
+    utils::{abi_require, read_abi_list},
+    ABIDecodeError,
+};
+use cfx_types::{Address, H256, U256};
+use std::slice::Iter;
+
+/// A dynamically typed ABI value. It is used when the solidity types are only
+/// known at runtime, e.g., the return types declared by a cross-space caller.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ABIValue {
+    Uint(U256),
+    Address(Address),
+    Bool(bool),
+    FixedBytes(H256),
+    Bytes(Vec<u8>),
+    String(String),
+    Array(Vec<ABIValue>),
+}
+
+/// Decode `data` as an ABI list whose element types are given by solidity
+/// type names, like `uint256`, `address` or `bytes[]`.
+pub fn abi_decode_values(types: &[String], data: &[u8]) -> Result<Vec<ABIValue>, ABIDecodeError> {
+    // Every supported type takes exactly one 32-byte head slot.
+    abi_require(
+        data.len() >= types.len() * 32,
+        "Data is shorter than the declared types",
+    )?;
+    let mut pointer = data.iter();
+    types
+        .iter()
+        .map(|ty| read_abi_value(ty.as_str(), data, &mut pointer))
+        .collect()
+}
+
+fn read_abi_value(
+    ty: &str,
+    data: &[u8],
+    pointer: &mut Iter<u8>,
+) -> Result<ABIValue, ABIDecodeError> {
+    macro_rules! read_array {
+        ($ty:ty, $variant:expr) => {
+            ABIValue::Array(
+                read_abi_list::<Vec<$ty>>(data, pointer)?
+                    .into_iter()
+                    .map($variant)
+                    .collect(),
+            )
+        };
+    }
+
+    let value = match ty {
+        "uint256" | "uint" => ABIValue::Uint(read_abi_list::<U256>(data, pointer)?),
+        "uint128" => ABIValue::Uint(read_abi_list::<u128>(data, pointer)?.into()),
+        "uint64" => ABIValue::Uint(read_abi_list::<u64>(data, pointer)?.into()),
+        "uint32" => ABIValue::Uint(read_abi_list::<u32>(data, pointer)?.into()),
+        "uint16" => ABIValue::Uint(read_abi_list::<u16>(data, pointer)?.into()),
+        "address" => ABIValue::Address(read_abi_list::<Address>(data, pointer)?),
+        "bool" => ABIValue::Bool(read_abi_list::<bool>(data, pointer)?),
+        "bytes32" => ABIValue::FixedBytes(read_abi_list::<H256>(data, pointer)?),
+        "bytes" => ABIValue::Bytes(read_abi_list::<Vec<u8>>(data, pointer)?),
+        "string" => ABIValue::String(read_abi_list::<String>(data, pointer)?),
+        "uint256[]" | "uint[]" => read_array!(U256, ABIValue::Uint),
+        "address[]" => read_array!(Address, ABIValue::Address),
+        "bool[]" => read_array!(bool, ABIValue::Bool),
+        "bytes32[]" => read_array!(H256, ABIValue::FixedBytes),
+        "bytes[]" => read_array!(Vec<u8>, ABIValue::Bytes),
+        "string[]" => read_array!(String, ABIValue::String),
+        _ => return Err(ABIDecodeError("Unsupported solidity type")),
+    };
+    Ok(value)
+}
@@ -26,6 +26,8 @@ pub struct MempoolConfig {
     pub system_transaction_timeout_secs: u64,
     pub system_transaction_gc_interval_ms: u64,
     pub broadcast_buckets: Vec<u64>,
+    // broadcast transactions calling the cross-space contract before the others
+    pub cross_space_priority_lane: bool,
 }
 
 // IMPORTANT: MEMPOOL CONFIG
@@ -47,6 +49,7 @@ impl Default for MempoolConfig {
             system_transaction_timeout_secs: 600,
             system_transaction_gc_interval_ms: 60_000,
             broadcast_buckets: DEFAULT_BROADCAST_BUCKETS.to_vec(),
+            cross_space_priority_lane: true,
         }
     }
 }
@@ -336,20 +336,24 @@ impl MultiBucketTimelineIndex {
     }
 
     #[inline]
-    fn get_timeline(&mut self, ranking_score: u64) -> &mut TimelineIndex {
-        let index = self
-            .bucket_mins
-            .binary_search(&ranking_score)
-            .unwrap_or_else(|i| i - 1);
+    fn get_timeline(&mut self, txn: &MempoolTransaction) -> &mut TimelineIndex {
+        // The last bucket is read first by `read_timeline`.
+        let index = if txn.priority_lane {
+            self.timelines.len() - 1
+        } else {
+            self.bucket_mins
+                .binary_search(&txn.ranking_score)
+                .unwrap_or_else(|i| i - 1)
+        };
         self.timelines.get_mut(index).unwrap()
     }
 
     pub(crate) fn insert(&mut self, txn: &mut MempoolTransaction) {
-        self.get_timeline(txn.ranking_score).insert(txn);
+        self.get_timeline(txn).insert(txn);
     }
 
     pub(crate) fn remove(&mut self, txn: &MempoolTransaction) {
-        self.get_timeline(txn.ranking_score).remove(txn);
+        self.get_timeline(txn).remove(txn);
     }
 
     pub(crate) fn size(&self) -> usize {
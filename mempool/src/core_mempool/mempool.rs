@@ -21,8 +21,9 @@ use aptos_types::{
     account_address::AccountAddress,
     account_config::AccountSequenceInfo,
     mempool_status::{MempoolStatus, MempoolStatusCode},
-    transaction::SignedTransaction,
+    transaction::{SignedTransaction, TransactionPayload},
 };
+use cfx_parameters::internal_contract_addresses::CROSS_SPACE_CONTRACT_ADDRESS;
 use std::{
     collections::HashSet,
     time::{Duration, SystemTime},
@@ -33,6 +34,8 @@ pub struct Mempool {
     transactions: TransactionStore,
 
     pub system_transaction_timeout: Duration,
+    // Whether cross-space transactions are broadcast before the others.
+    cross_space_priority_lane: bool,
 }
 
 impl Mempool {
@@ -42,6 +45,7 @@ impl Mempool {
             system_transaction_timeout: Duration::from_secs(
                 config.mempool.system_transaction_timeout_secs,
             ),
+            cross_space_priority_lane: config.mempool.cross_space_priority_lane,
         }
     }
 
@@ -138,7 +142,8 @@ impl Mempool {
         let expiration_time =
             aptos_infallible::duration_since_epoch_at(&now) + self.system_transaction_timeout;
 
-        let txn_info = MempoolTransaction::new(
+        let priority_lane = self.cross_space_priority_lane && is_cross_space(&txn);
+        let mut txn_info = MempoolTransaction::new(
             txn,
             expiration_time,
             ranking_score,
@@ -146,6 +151,7 @@ impl Mempool {
             AccountSequenceInfo::Sequential(db_sequence_number),
             now,
         );
+        txn_info.priority_lane = priority_lane;
 
         let status = self.transactions.insert(txn_info);
         counters::core_mempool_txn_ranking_score(
@@ -286,6 +292,16 @@ impl Mempool {
         self.transactions.timeline_range(start_end_pairs)
     }
 
+    /// Number of transactions in mempool, including the ones not ready for broadcast.
+    pub fn size(&self) -> usize {
+        self.transactions.size()
+    }
+
+    /// Whether mempool has reached its capacity in number of transactions or in bytes.
+    pub fn is_full(&self) -> bool {
+        self.transactions.is_full()
+    }
+
     pub fn gen_snapshot(&self) -> TxnsLog {
         self.transactions.gen_snapshot()
     }
@@ -300,3 +316,14 @@ impl Mempool {
         &self.transactions
     }
 }
+
+/// Whether the transaction is an Ethereum transaction calling the cross-space contract, i.e., it
+/// triggers a call into the Move space.
+pub(crate) fn is_cross_space(txn: &SignedTransaction) -> bool {
+    match txn.payload() {
+        TransactionPayload::EthTransactionPayload(payload) => {
+            payload.receiver() == Some(*CROSS_SPACE_CONTRACT_ADDRESS)
+        },
+        _ => false,
+    }
+}
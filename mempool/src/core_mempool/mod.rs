@@ -12,3 +12,4 @@ pub use self::{
     transaction::{MempoolTransaction, TimelineState},
     transaction_store::TXN_INDEX_ESTIMATED_BYTES,
 };
+pub(crate) use self::mempool::is_cross_space;
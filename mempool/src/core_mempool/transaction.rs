@@ -25,6 +25,8 @@ pub struct MempoolTransaction {
     pub timeline_state: TimelineState,
     pub sequence_info: SequenceInfo,
     pub insertion_time: SystemTime,
+    // Whether the transaction is broadcast before the others regardless of its ranking score.
+    pub priority_lane: bool,
 }
 
 impl MempoolTransaction {
@@ -46,6 +48,7 @@ impl MempoolTransaction {
             ranking_score,
             timeline_state,
             insertion_time,
+            priority_lane: false,
         }
     }
 
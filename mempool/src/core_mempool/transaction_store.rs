@@ -343,10 +343,15 @@ impl TransactionStore {
         self.is_full()
     }
 
-    fn is_full(&self) -> bool {
+    pub(crate) fn is_full(&self) -> bool {
         self.system_ttl_index.size() >= self.capacity || self.size_bytes >= self.capacity_bytes
     }
 
+    /// Number of transactions in the store, including the parked ones.
+    pub(crate) fn size(&self) -> usize {
+        self.system_ttl_index.size()
+    }
+
     /// Check if a transaction would be ready for broadcast in mempool upon insertion (without inserting it).
     /// Two ways this can happen:
     /// 1. txn sequence number == curr_sequence_number
@@ -58,10 +58,10 @@
 #[cfg(any(test, feature = "fuzzing"))]
 mod tests;
 pub use shared_mempool::{
-    bootstrap, network,
+    bootstrap, bootstrap_with_validator, network,
     types::{
-        MempoolClientRequest, MempoolClientSender, MempoolEventsReceiver, QuorumStoreRequest,
-        QuorumStoreResponse, SubmissionStatus,
+        MempoolClientRequest, MempoolClientSender, MempoolEventsReceiver, MempoolHandle,
+        QuorumStoreRequest, QuorumStoreResponse, SharedMempoolCounters, SubmissionStatus,
     },
 };
 #[cfg(any(test, feature = "fuzzing"))]
@@ -182,6 +182,7 @@ pub enum LogEvent {
     Start,
     Live,
     Terminated,
+    Shutdown,
 
     // VM reconfig events
     Received,
@@ -11,7 +11,10 @@ use crate::{
     shared_mempool::{
         tasks,
         tasks::process_committed_transactions,
-        types::{notify_subscribers, ScheduledBroadcast, SharedMempool, SharedMempoolNotification},
+        types::{
+            notify_subscribers, ScheduledBroadcast, SharedMempool, SharedMempoolCounters,
+            SharedMempoolNotification,
+        },
     },
     MempoolEventsReceiver, QuorumStoreRequest,
 };
@@ -34,7 +37,7 @@ use std::{
     sync::Arc,
     time::{Duration, Instant, SystemTime},
 };
-use tokio::{runtime::Handle, time::interval};
+use tokio::{runtime::Handle, sync::watch, time::interval};
 use tokio_stream::wrappers::IntervalStream;
 
 /// Coordinator that handles inbound network events and outbound txn broadcasts.
@@ -46,14 +49,15 @@ pub(crate) async fn coordinator<NetworkClient, TransactionValidator>(
     mut quorum_store_requests: mpsc::Receiver<QuorumStoreRequest>,
     mut mempool_listener: MempoolNotificationListener,
     mut mempool_reconfig_events: ReconfigNotificationListener,
+    shutdown: watch::Receiver<bool>,
 ) where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg> + 'static,
     TransactionValidator: TransactionValidation + 'static,
 {
-    info!(LogSchema::event_log(
-        LogEntry::CoordinatorRuntime,
-        LogEvent::Start
-    ));
+    info!(
+        LogSchema::event_log(LogEntry::CoordinatorRuntime, LogEvent::Start),
+        shared_mempool_batch_size = smp.config.shared_mempool_batch_size,
+    );
     // Combine `NetworkEvents` for each `NetworkId` into one stream
     let smp_events: Vec<_> = network_events
         .into_iter()
@@ -78,6 +82,11 @@ pub(crate) async fn coordinator<NetworkClient, TransactionValidator>(
     )
     .await;
 
+    // Broadcasts are executed inline by the select loop, so an in-flight broadcast always
+    // completes before the shutdown signal is observed.
+    let shutdown = wait_for_shutdown(shutdown).fuse();
+    futures::pin_mut!(shutdown);
+
     loop {
         let _timer = counters::MAIN_LOOP.start_timer();
         ::futures::select! {
@@ -99,6 +108,10 @@ pub(crate) async fn coordinator<NetworkClient, TransactionValidator>(
             (network_id, event) = events.select_next_some() => {
                 handle_network_event(&executor, &bounded_executor, &mut scheduled_broadcasts, &mut smp, network_id, event).await;
             },
+            _ = shutdown => {
+                info!(LogSchema::event_log(LogEntry::CoordinatorRuntime, LogEvent::Shutdown));
+                return;
+            },
             complete => break,
         }
     }
@@ -349,30 +362,58 @@ async fn handle_network_event<NetworkClient, TransactionValidator>(
 }
 
 /// Garbage collect all expired transactions by SystemTTL.
-pub(crate) async fn gc_coordinator(mempool: Arc<Mutex<CoreMempool>>, gc_interval_ms: u64) {
+pub(crate) async fn gc_coordinator(
+    mempool: Arc<Mutex<CoreMempool>>,
+    gc_interval_ms: u64,
+    counters: Arc<SharedMempoolCounters>,
+    shutdown: watch::Receiver<bool>,
+) {
     debug!(LogSchema::event_log(LogEntry::GCRuntime, LogEvent::Start));
-    let mut interval = IntervalStream::new(interval(Duration::from_millis(gc_interval_ms)));
+    let mut interval = IntervalStream::new(interval(Duration::from_millis(gc_interval_ms)))
+        .take_until(wait_for_shutdown(shutdown));
     while let Some(_interval) = interval.next().await {
         sample!(
             SampleRate::Duration(Duration::from_secs(60)),
             debug!(LogSchema::event_log(LogEntry::GCRuntime, LogEvent::Live))
         );
-        mempool.lock().gc();
+        let mut mempool = mempool.lock();
+        let size_before_gc = mempool.size();
+        mempool.gc();
+        counters.add_garbage_collected(size_before_gc - mempool.size());
     }
 
-    error!(LogSchema::event_log(
+    // The interval stream only ends once shutdown is requested.
+    info!(LogSchema::event_log(
         LogEntry::GCRuntime,
-        LogEvent::Terminated
+        LogEvent::Shutdown
     ));
 }
 
 /// Periodically logs a snapshot of transactions in core mempool.
 /// In the future we may want an interactive way to directly query mempool's internal state.
 /// For now, we will rely on this periodic snapshot to observe the internal state.
-pub(crate) async fn snapshot_job(mempool: Arc<Mutex<CoreMempool>>, snapshot_interval_secs: u64) {
-    let mut interval = IntervalStream::new(interval(Duration::from_secs(snapshot_interval_secs)));
+pub(crate) async fn snapshot_job(
+    mempool: Arc<Mutex<CoreMempool>>,
+    snapshot_interval_secs: u64,
+    shutdown: watch::Receiver<bool>,
+) {
+    let mut interval = IntervalStream::new(interval(Duration::from_secs(snapshot_interval_secs)))
+        .take_until(wait_for_shutdown(shutdown));
     while let Some(_interval) = interval.next().await {
         let snapshot = mempool.lock().gen_snapshot();
         trace!(LogSchema::new(LogEntry::MempoolSnapshot).txns(snapshot));
     }
 }
+
+/// Resolves once shutdown is requested. If the sender is dropped without requesting shutdown,
+/// this never resolves and the task keeps running.
+async fn wait_for_shutdown(mut shutdown: watch::Receiver<bool>) {
+    loop {
+        if *shutdown.borrow() {
+            return;
+        }
+        if shutdown.changed().await.is_err() {
+            futures::future::pending::<()>().await;
+        }
+    }
+}
@@ -4,8 +4,8 @@
 pub mod network;
 mod runtime;
 pub(crate) mod types;
-pub use runtime::bootstrap;
+pub use runtime::{bootstrap, bootstrap_with_validator};
 #[cfg(any(test, feature = "fuzzing"))]
 pub(crate) use runtime::start_shared_mempool;
-mod coordinator;
+pub(crate) mod coordinator;
 pub(crate) mod tasks;
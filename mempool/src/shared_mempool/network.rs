@@ -472,6 +472,7 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         let num_pending_broadcasts =
             self.update_broadcast_state(peer, batch_id.clone(), send_time)?;
         notify_subscribers(SharedMempoolNotification::Broadcast, &smp.subscribers);
+        smp.counters.add_broadcast(num_txns);
 
         // Log all the metrics
         let latency = start_time.elapsed();
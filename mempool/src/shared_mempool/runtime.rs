@@ -6,7 +6,10 @@ use crate::{
     network::{MempoolNetworkEvents, MempoolSyncMsg},
     shared_mempool::{
         coordinator::{coordinator, gc_coordinator, snapshot_job},
-        types::{MempoolEventsReceiver, SharedMempool, SharedMempoolNotification},
+        types::{
+            MempoolEventsReceiver, MempoolHandle, SharedMempool, SharedMempoolCounters,
+            SharedMempoolNotification,
+        },
     },
     QuorumStoreRequest,
 };
@@ -23,13 +26,18 @@ use aptos_block_executor::state_view::DbReader;
 use aptos_vm_validator::vm_validator::{TransactionValidation, VMValidator};
 use futures::channel::mpsc::{self, Receiver, UnboundedSender};
 use std::{collections::HashMap, sync::Arc};
-use tokio::runtime::{Handle, Runtime};
+use tokio::{
+    runtime::{Handle, Runtime},
+    sync::watch,
+};
 
 /// Bootstrap of SharedMempool.
 /// Creates a separate Tokio Runtime that runs the following routines:
 ///   - outbound_sync_task (task that periodically broadcasts transactions to peers).
 ///   - inbound_network_task (task that handles inbound mempool messages and network events).
 ///   - gc_task (task that performs GC of all expired transactions by SystemTTL).
+/// All routines exit once `shutdown` is set to `true`. Returns the counters updated by the
+/// routines.
 pub(crate) fn start_shared_mempool<TransactionValidator>(
     executor: &Handle,
     config: &NodeConfig,
@@ -49,9 +57,17 @@ pub(crate) fn start_shared_mempool<TransactionValidator>(
     validator: Arc<RwLock<TransactionValidator>>,
     subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
     peer_metadata_storage: Arc<PeerMetadataStorage>,
-) where
+    shutdown: watch::Receiver<bool>,
+) -> Arc<SharedMempoolCounters>
+where
     TransactionValidator: TransactionValidation + 'static,
 {
+    // An empty broadcast batch would never make progress.
+    assert_ne!(
+        config.mempool.shared_mempool_batch_size, 0,
+        "mempool.shared_mempool_batch_size must be nonzero"
+    );
+
     let mut all_network_events = vec![];
     let mut network_senders = HashMap::new();
     for (network_id, network_sender, network_events) in mempool_network_handles.into_iter() {
@@ -75,6 +91,7 @@ pub(crate) fn start_shared_mempool<TransactionValidator>(
             subscribers,
             config.base.role,
         );
+    let counters = smp.counters.clone();
 
     executor.spawn(coordinator(
         smp,
@@ -84,21 +101,28 @@ pub(crate) fn start_shared_mempool<TransactionValidator>(
         quorum_store_requests,
         mempool_listener,
         mempool_reconfig_events,
+        shutdown.clone(),
     ));
 
     executor.spawn(gc_coordinator(
         mempool.clone(),
         config.mempool.system_transaction_gc_interval_ms,
+        counters.clone(),
+        shutdown.clone(),
     ));
 
     if aptos_logger::enabled!(Level::Trace) {
         executor.spawn(snapshot_job(
             mempool,
             config.mempool.mempool_snapshot_interval_secs,
+            shutdown,
         ));
     }
+    counters
 }
 
+/// Bootstrap of SharedMempool, returns the runtime of its routines and a handle to query the
+/// mempool occupancy and to shut its routines down. Transactions are validated by `VMValidator`.
 pub fn bootstrap(
     config: &NodeConfig,
     db: Arc<dyn DbReader>,
@@ -114,23 +138,61 @@ pub fn bootstrap(
     mempool_listener: MempoolNotificationListener,
     mempool_reconfig_events: ReconfigNotificationListener,
     peer_metadata_storage: Arc<PeerMetadataStorage>,
-) -> Runtime {
+) -> (Runtime, MempoolHandle) {
+    bootstrap_with_validator(
+        config,
+        db,
+        mempool_network_handles,
+        client_events,
+        quorum_store_requests,
+        mempool_listener,
+        mempool_reconfig_events,
+        peer_metadata_storage,
+        |db| Arc::new(RwLock::new(VMValidator::new(db))),
+    )
+}
+
+/// Same as `bootstrap`, with the transaction validator built by `validator_factory` from the DB
+/// reader, e.g., a composite validator which also pre-checks the cross-space calls so that the
+/// malformed ones never enter mempool.
+pub fn bootstrap_with_validator<TransactionValidator, ValidatorFactory>(
+    config: &NodeConfig,
+    db: Arc<dyn DbReader>,
+    mempool_network_handles: Vec<(
+        NetworkId,
+        NetworkSender<MempoolSyncMsg>,
+        MempoolNetworkEvents,
+    )>,
+    client_events: MempoolEventsReceiver,
+    quorum_store_requests: Receiver<QuorumStoreRequest>,
+    mempool_listener: MempoolNotificationListener,
+    mempool_reconfig_events: ReconfigNotificationListener,
+    peer_metadata_storage: Arc<PeerMetadataStorage>,
+    validator_factory: ValidatorFactory,
+) -> (Runtime, MempoolHandle)
+where
+    TransactionValidator: TransactionValidation + 'static,
+    ValidatorFactory: FnOnce(Arc<dyn DbReader>) -> Arc<RwLock<TransactionValidator>>,
+{
     let runtime = aptos_runtimes::spawn_named_runtime("shared-mem".into(), None);
     let mempool = Arc::new(Mutex::new(CoreMempool::new(config)));
-    let vm_validator = Arc::new(RwLock::new(VMValidator::new(Arc::clone(&db))));
-    start_shared_mempool(
+    let (shutdown_sender, shutdown) = watch::channel(false);
+    let validator = validator_factory(Arc::clone(&db));
+    let counters = start_shared_mempool(
         runtime.handle(),
         config,
-        mempool,
+        mempool.clone(),
         mempool_network_handles,
         client_events,
         quorum_store_requests,
         mempool_listener,
         mempool_reconfig_events,
         db,
-        vm_validator,
+        validator,
         vec![],
         peer_metadata_storage,
+        shutdown,
     );
-    runtime
+    let mempool_handle = MempoolHandle::new(mempool, shutdown_sender, counters);
+    (runtime, mempool_handle)
 }
@@ -3,7 +3,7 @@
 
 //! Tasks that are executed by coordinators (short-lived compared to coordinators)
 use crate::{
-    core_mempool::{CoreMempool, TimelineState, TxnPointer},
+    core_mempool::{is_cross_space, CoreMempool, TimelineState, TxnPointer},
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
     network::{BroadcastError, MempoolSyncMsg},
@@ -253,6 +253,7 @@ where
     TransactionValidator: TransactionValidation,
 {
     let mut statuses = vec![];
+    smp.counters.add_received(transactions.len());
 
     let start_storage_read = Instant::now();
     let state_view = smp
@@ -311,6 +312,15 @@ where
 
     validate_and_add_transactions(transactions, smp, timeline_state, &mut statuses);
     notify_subscribers(SharedMempoolNotification::NewTransactions, &smp.subscribers);
+    for (txn, (mempool_status, _)) in statuses.iter() {
+        if mempool_status.code == MempoolStatusCode::Accepted && is_cross_space(txn) {
+            smp.counters.inc_cross_space();
+            notify_subscribers(
+                SharedMempoolNotification::CrossSpaceSubmitted(txn.committed_hash(), txn.sender()),
+                &smp.subscribers,
+            );
+        }
+    }
     statuses
 }
 
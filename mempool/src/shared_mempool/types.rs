@@ -19,7 +19,8 @@ use aptos_network::{
 };
 use aptos_block_executor::state_view::DbReader;
 use aptos_types::{
-    mempool_status::MempoolStatus, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
+    account_address::AccountAddress, mempool_status::MempoolStatus, transaction::SignedTransaction,
+    vm_status::DiscardedVMStatus,
 };
 use aptos_vm_validator::vm_validator::TransactionValidation;
 use futures::{
@@ -33,11 +34,14 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     fmt,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering as AtomicOrdering},
+        Arc,
+    },
     task::Waker,
     time::{Instant, SystemTime},
 };
-use tokio::runtime::Handle;
+use tokio::{runtime::Handle, sync::watch};
 
 /// Struct that owns all dependencies required by shared mempool routines.
 #[derive(Clone)]
@@ -49,6 +53,7 @@ pub(crate) struct SharedMempool<NetworkClient, TransactionValidator> {
     pub validator: Arc<RwLock<TransactionValidator>>,
     pub subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
     pub broadcast_within_validator_network: Arc<RwLock<bool>>,
+    pub counters: Arc<SharedMempoolCounters>,
 }
 
 impl<
@@ -74,6 +79,7 @@ impl<
             validator,
             subscribers,
             broadcast_within_validator_network: Arc::new(RwLock::new(true)),
+            counters: Arc::new(SharedMempoolCounters::default()),
         }
     }
 
@@ -88,12 +94,110 @@ impl<
     }
 }
 
+/// Running totals of the shared mempool routines, e.g., to alert on a regression of the bridge
+/// throughput. They are plain atomics, so updating them never locks the hot path.
+#[derive(Debug, Default)]
+pub struct SharedMempoolCounters {
+    received: AtomicU64,
+    broadcast: AtomicU64,
+    garbage_collected: AtomicU64,
+    cross_space: AtomicU64,
+}
+
+impl SharedMempoolCounters {
+    /// Number of transactions submitted by clients or received from peers.
+    pub fn received(&self) -> u64 {
+        self.received.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Number of transactions sent to peers, counting each peer a transaction is sent to.
+    pub fn broadcast(&self) -> u64 {
+        self.broadcast.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Number of transactions removed by the system TTL garbage collection.
+    pub fn garbage_collected(&self) -> u64 {
+        self.garbage_collected.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Number of accepted transactions calling the cross-space contract.
+    pub fn cross_space(&self) -> u64 {
+        self.cross_space.load(AtomicOrdering::Relaxed)
+    }
+
+    pub(crate) fn add_received(&self, count: usize) {
+        self.received
+            .fetch_add(count as u64, AtomicOrdering::Relaxed);
+    }
+
+    pub(crate) fn add_broadcast(&self, count: usize) {
+        self.broadcast
+            .fetch_add(count as u64, AtomicOrdering::Relaxed);
+    }
+
+    pub(crate) fn add_garbage_collected(&self, count: usize) {
+        self.garbage_collected
+            .fetch_add(count as u64, AtomicOrdering::Relaxed);
+    }
+
+    pub(crate) fn inc_cross_space(&self) {
+        self.cross_space.fetch_add(1, AtomicOrdering::Relaxed);
+    }
+}
+
+/// A cheap handle to query the occupancy of a running mempool, e.g., to back-pressure
+/// transaction submission when mempool saturates.
+#[derive(Clone)]
+pub struct MempoolHandle {
+    mempool: Arc<Mutex<CoreMempool>>,
+    shutdown: Arc<watch::Sender<bool>>,
+    counters: Arc<SharedMempoolCounters>,
+}
+
+impl MempoolHandle {
+    pub(crate) fn new(
+        mempool: Arc<Mutex<CoreMempool>>,
+        shutdown: watch::Sender<bool>,
+        counters: Arc<SharedMempoolCounters>,
+    ) -> Self {
+        Self {
+            mempool,
+            shutdown: Arc::new(shutdown),
+            counters,
+        }
+    }
+
+    /// Number of transactions in mempool.
+    pub fn pending_count(&self) -> usize {
+        self.mempool.lock().size()
+    }
+
+    /// Whether mempool rejects new transactions because it is at capacity.
+    pub fn is_full(&self) -> bool {
+        self.mempool.lock().is_full()
+    }
+
+    /// Running totals of the shared mempool routines.
+    pub fn counters(&self) -> &SharedMempoolCounters {
+        &self.counters
+    }
+
+    /// Signals the shared mempool routines to exit. The coordinator finishes any in-flight
+    /// broadcast before it stops.
+    pub fn shutdown(&self) {
+        let _ = self.shutdown.send(true);
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum SharedMempoolNotification {
     PeerStateChange,
     NewTransactions,
     ACK,
     Broadcast,
+    /// A transaction calling the cross-space contract was accepted into mempool, identified by
+    /// its committed hash and its sender.
+    CrossSpaceSubmitted(HashValue, AccountAddress),
 }
 
 pub(crate) fn notify_subscribers(
@@ -14,8 +14,9 @@ use aptos_types::{
     account_config::AccountSequenceInfo,
     chain_id::ChainId,
     mempool_status::MempoolStatusCode,
-    transaction::{RawTransaction, Script, SignedTransaction},
+    transaction::{EthAction, EthTransactionPayload, RawTransaction, Script, SignedTransaction},
 };
+use cfx_parameters::internal_contract_addresses::CROSS_SPACE_CONTRACT_ADDRESS;
 use once_cell::sync::Lazy;
 use rand::{rngs::StdRng, SeedableRng};
 use serde::{Deserialize, Serialize};
@@ -80,6 +81,24 @@ impl TestTransaction {
         self.make_signed_transaction_impl(100, u64::MAX)
     }
 
+    /// An Ethereum transaction calling the cross-space contract.
+    pub(crate) fn make_signed_cross_space_transaction(&self) -> SignedTransaction {
+        let raw_txn = RawTransaction::new_eth(
+            TestTransaction::get_address(self.address),
+            self.sequence_number,
+            EthTransactionPayload::new(
+                Default::default(),
+                EthAction::Call(*CROSS_SPACE_CONTRACT_ADDRESS),
+                vec![],
+            ),
+            100,
+            self.gas_price,
+            u64::MAX,
+            ChainId::test(),
+        );
+        Self::sign(raw_txn)
+    }
+
     fn make_signed_transaction_impl(
         &self,
         max_gas_amount: u64,
@@ -94,6 +113,10 @@ impl TestTransaction {
             exp_timestamp_secs,
             ChainId::test(),
         );
+        Self::sign(raw_txn)
+    }
+
+    fn sign(raw_txn: RawTransaction) -> SignedTransaction {
         let mut seed: [u8; 32] = [0u8; 32];
         seed[..4].copy_from_slice(&[1, 2, 3, 4]);
         let mut rng: StdRng = StdRng::from_seed(seed);
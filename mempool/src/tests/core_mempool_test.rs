@@ -386,6 +386,25 @@ fn test_multi_bucket_timeline() {
     assert_eq!(0, pool.get_parking_lot_size());
 }
 
+#[test]
+fn test_cross_space_priority_lane() {
+    let mut config = NodeConfig::random();
+    config.mempool.broadcast_buckets = vec![0, 101, 201];
+    for enabled in [true, false] {
+        config.mempool.cross_space_priority_lane = enabled;
+        let mut pool = CoreMempool::new(&config);
+        let ordinary = TestTransaction::new(1, 0, 1).make_signed_transaction();
+        let cross_space = TestTransaction::new(2, 0, 1).make_signed_cross_space_transaction();
+        add_signed_txn(&mut pool, ordinary.clone()).unwrap();
+        add_signed_txn(&mut pool, cross_space.clone()).unwrap();
+
+        // The cross-space transaction is in the top bucket, which is read first.
+        let (timeline, _) = pool.read_timeline(&vec![0, 0, 0].into(), 1);
+        let expected = if enabled { cross_space } else { ordinary };
+        assert_eq!(timeline, vec![expected]);
+    }
+}
+
 #[test]
 fn test_multi_bucket_gas_ranking_update() {
     let mut pool = setup_mempool_with_broadcast_buckets(vec![0, 101, 201]).0;
@@ -471,6 +490,23 @@ fn test_capacity() {
     assert!(add_txn(&mut pool, TestTransaction::new(1, 2, 1)).is_ok());
 }
 
+#[test]
+fn test_size_and_is_full() {
+    let mut config = NodeConfig::random();
+    config.mempool.capacity = 2;
+    let mut pool = CoreMempool::new(&config);
+    assert_eq!(pool.size(), 0);
+
+    add_txn(&mut pool, TestTransaction::new(1, 0, 1)).unwrap();
+    assert_eq!(pool.size(), 1);
+    assert!(!pool.is_full());
+
+    // Transactions not ready for broadcast count as well.
+    add_txn(&mut pool, TestTransaction::new(1, 2, 1)).unwrap();
+    assert_eq!(pool.size(), 2);
+    assert!(pool.is_full());
+}
+
 #[test]
 fn test_capacity_bytes() {
     let capacity_bytes = 2_048;
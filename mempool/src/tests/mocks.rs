@@ -5,7 +5,7 @@ use crate::{
     core_mempool::{CoreMempool, TimelineState},
     network::MempoolNetworkEvents,
     shared_mempool::start_shared_mempool,
-    MempoolClientSender, QuorumStoreRequest,
+    MempoolClientSender, MempoolHandle, QuorumStoreRequest, SharedMempoolCounters,
 };
 use anyhow::{format_err, Result};
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
@@ -34,7 +34,10 @@ use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
 };
-use tokio::runtime::{Handle, Runtime};
+use tokio::{
+    runtime::{Handle, Runtime},
+    sync::watch,
+};
 
 /// Mock of a running instance of shared mempool.
 pub struct MockSharedMempool {
@@ -44,6 +47,11 @@ pub struct MockSharedMempool {
     pub mempool: Arc<Mutex<CoreMempool>>,
     pub consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
     pub mempool_notifier: MempoolNotifier,
+    pub counters: Arc<SharedMempoolCounters>,
+    /// Handle to the running mempool, e.g., to shut its routines down.
+    pub handle: MempoolHandle,
+    /// The shutdown signal observed by the routines.
+    pub shutdown: watch::Receiver<bool>,
 }
 
 impl MockSharedMempool {
@@ -52,18 +60,23 @@ impl MockSharedMempool {
     /// and the channel through which shared mempool receives client events.
     pub fn new() -> Self {
         let runtime = aptos_runtimes::spawn_named_runtime("shared-mem".into(), None);
-        let (ac_client, mempool, quorum_store_sender, mempool_notifier) = Self::start(
+        let (shutdown_sender, shutdown) = watch::channel(false);
+        let (ac_client, mempool, quorum_store_sender, mempool_notifier, counters) = Self::start(
             runtime.handle(),
             &DbReaderWriter::new(MockDbReaderWriter),
             MockVMValidator,
+            shutdown.clone(),
         );
         Self {
             _runtime: Some(runtime),
             _handle: None,
             ac_client,
+            handle: MempoolHandle::new(mempool.clone(), shutdown_sender, counters.clone()),
             mempool,
             consensus_to_mempool_sender: quorum_store_sender,
             mempool_notifier,
+            counters,
+            shutdown,
         }
     }
 
@@ -74,15 +87,19 @@ impl MockSharedMempool {
         validator: V,
     ) -> Self {
         let handle = Handle::current();
-        let (ac_client, mempool, quorum_store_sender, mempool_notifier) =
-            Self::start(&handle, db, validator);
+        let (shutdown_sender, shutdown) = watch::channel(false);
+        let (ac_client, mempool, quorum_store_sender, mempool_notifier, counters) =
+            Self::start(&handle, db, validator, shutdown.clone());
         Self {
             _runtime: None,
             _handle: Some(handle),
             ac_client,
+            handle: MempoolHandle::new(mempool.clone(), shutdown_sender, counters.clone()),
             mempool,
             consensus_to_mempool_sender: quorum_store_sender,
             mempool_notifier,
+            counters,
+            shutdown,
         }
     }
 
@@ -90,11 +107,13 @@ impl MockSharedMempool {
         handle: &Handle,
         db: &DbReaderWriter,
         validator: V,
+        shutdown: watch::Receiver<bool>,
     ) -> (
         MempoolClientSender,
         Arc<Mutex<CoreMempool>>,
         mpsc::Sender<QuorumStoreRequest>,
         MempoolNotifier,
+        Arc<SharedMempoolCounters>,
     ) {
         let mut config = NodeConfig::random();
         config.validator_network = Some(NetworkConfig::network_with_id(NetworkId::Validator));
@@ -126,7 +145,7 @@ impl MockSharedMempool {
         let network_handles = vec![(NetworkId::Validator, network_sender, network_events)];
         let peer_metadata_storage = PeerMetadataStorage::new(&[NetworkId::Validator]);
 
-        start_shared_mempool(
+        let counters = start_shared_mempool(
             handle,
             &config,
             mempool.clone(),
@@ -139,9 +158,16 @@ impl MockSharedMempool {
             Arc::new(RwLock::new(validator)),
             vec![],
             peer_metadata_storage,
+            shutdown,
         );
 
-        (ac_client, mempool, quorum_store_sender, mempool_notifier)
+        (
+            ac_client,
+            mempool,
+            quorum_store_sender,
+            mempool_notifier,
+            counters,
+        )
     }
 
     pub fn add_txns(&self, txns: Vec<SignedTransaction>) -> Result<()> {
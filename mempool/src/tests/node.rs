@@ -41,7 +41,7 @@ use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
 };
-use tokio::runtime::Runtime;
+use tokio::{runtime::Runtime, sync::watch};
 
 type MempoolNetworkHandle = (
     NetworkId,
@@ -595,6 +595,7 @@ fn start_node_mempool(
         Arc::new(RwLock::new(MockVMValidator)),
         vec![sender],
         peer_metadata_storage,
+        watch::channel(false).1,
     );
 
     (mempool, runtime, subscriber)
@@ -3,13 +3,17 @@
 
 use crate::{
     mocks::MockSharedMempool,
+    shared_mempool::coordinator::{gc_coordinator, snapshot_job},
     tests::common::{batch_add_signed_txn, TestTransaction},
-    QuorumStoreRequest,
+    MempoolClientRequest, QuorumStoreRequest,
 };
 use aptos_consensus_types::common::RejectedTransactionSummary;
 use aptos_mempool_notifications::MempoolNotificationSender;
+use aptos_storage_interface::{mock::MockDbReaderWriter, DbReaderWriter};
 use aptos_types::transaction::Transaction;
+use aptos_vm_validator::mocks::mock_vm_validator::MockVMValidator;
 use futures::{channel::oneshot, executor::block_on, sink::SinkExt};
+use std::{sync::Arc, time::Duration};
 
 #[test]
 fn test_consensus_events_rejected_txns() {
@@ -94,3 +98,56 @@ fn test_mempool_notify_committed_txns() {
     assert_eq!(timeline.len(), 1);
     assert_eq!(timeline.first().unwrap(), &kept_txn);
 }
+
+#[test]
+fn test_counters_received_transactions() {
+    let smp = MockSharedMempool::new();
+    assert_eq!(smp.counters.received(), 0);
+
+    let txn = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    let (callback, callback_rcv) = oneshot::channel();
+    let mut ac_client = smp.ac_client.clone();
+    block_on(async {
+        assert!(ac_client
+            .send(MempoolClientRequest::SubmitTransaction(txn, callback))
+            .await
+            .is_ok());
+        assert!(callback_rcv.await.is_ok());
+    });
+
+    assert_eq!(smp.counters.received(), 1);
+    // The transaction does not call the cross-space contract.
+    assert_eq!(smp.counters.cross_space(), 0);
+    assert_eq!(smp.counters.garbage_collected(), 0);
+}
+
+#[tokio::test]
+async fn test_mempool_handle_shutdown() {
+    let smp = MockSharedMempool::new_in_runtime(
+        &DbReaderWriter::new(MockDbReaderWriter),
+        MockVMValidator,
+    );
+    // The snapshot job is only spawned when trace logging is enabled, so the routines are also
+    // run here to observe their exit.
+    let gc = tokio::spawn(gc_coordinator(
+        smp.mempool.clone(),
+        10,
+        smp.counters.clone(),
+        smp.shutdown.clone(),
+    ));
+    let snapshot = tokio::spawn(snapshot_job(smp.mempool.clone(), 1, smp.shutdown.clone()));
+
+    smp.handle.shutdown();
+    let exited = async {
+        gc.await.unwrap();
+        snapshot.await.unwrap();
+        // The coordinator drops the client channel when it exits, and the routines spawned by
+        // the mock drop their references to mempool, leaving those of the mock and the handle.
+        while !smp.ac_client.is_closed() || Arc::strong_count(&smp.mempool) > 2 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(10), exited)
+        .await
+        .expect("The mempool routines should exit on shutdown");
+}
@@ -42,7 +42,7 @@ use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
 };
-use tokio::{runtime::Handle, time::Duration};
+use tokio::{runtime::Handle, sync::watch, time::Duration};
 use tokio_stream::StreamExt;
 
 /// An individual mempool node that runs in it's own runtime.
@@ -506,6 +506,7 @@ fn setup_mempool(
         vm_validator,
         vec![sender],
         peer_metadata_storage,
+        watch::channel(false).1,
     );
 
     (
@@ -14,12 +14,16 @@ const VERSION_SIZE: usize = std::mem::size_of::<Version>();
 pub(super) fn ledger_db_column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
+        BLOCK_HASH_BY_VERSION_CF_NAME,
         BLOCK_VERSION_BY_HASH_CF_NAME,
+        BLOCK_VERSION_BY_HASH_WITH_CHECKSUM_CF_NAME,
+        CROSS_SPACE_RECEIPT_CF_NAME,
         EPOCH_BY_VERSION_CF_NAME,
         EVENT_ACCUMULATOR_CF_NAME,
         EVENT_BY_KEY_CF_NAME,
         EVENT_BY_VERSION_CF_NAME,
         EVENT_CF_NAME,
+        EVM_TO_MOVE_MAPPING_CF_NAME,
         LEDGER_INFO_CF_NAME,
         STALE_STATE_VALUE_INDEX_CF_NAME,
         STATE_VALUE_CF_NAME,
@@ -9,8 +9,11 @@ use super::AptosDB;
 use crate::{
     errors::AptosDbError,
     schema::{
-        block_version_by_hash::BlockVersionByHashSchema, event::EventSchema,
-        event_accumulator::EventAccumulatorSchema, event_by_key::EventByKeySchema,
+        block_hash_by_version::BlockHashByVersionSchema,
+        block_version_by_hash::{BlockVersionByHashSchema, BlockVersionByHashWithChecksumSchema},
+        event::EventSchema,
+        event_accumulator::EventAccumulatorSchema,
+        event_by_key::EventByKeySchema,
         event_by_version::EventByVersionSchema,
     },
     utils::iterators::EventsByVersionIter,
@@ -34,17 +37,30 @@ use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     convert::{TryFrom, TryInto},
     iter::Peekable,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
 #[derive(Debug)]
 pub struct EventStore {
     db: Arc<DB>,
+    /// Whether new blocks are indexed by `BlockVersionByHashWithChecksumSchema` instead of
+    /// `BlockVersionByHashSchema`.
+    block_index_checksum: AtomicBool,
 }
 
 impl EventStore {
     pub fn new(db: Arc<DB>) -> Self {
-        Self { db }
+        Self {
+            db,
+            block_index_checksum: AtomicBool::new(false),
+        }
+    }
+
+    pub fn set_block_index_checksum(&self, enabled: bool) {
+        self.block_index_checksum.store(enabled, Ordering::Relaxed);
     }
 
     /// Get all of the events given a transaction version.
@@ -477,13 +493,88 @@ impl EventStore {
         Ok(())
     }
 
+    /// Blocks indexed with a checksum are looked up first, a checksum mismatch is an error.
     pub fn get_block_version_by_hash(&self, block_hash: HashValue) -> Result<Option<Version>> {
-        self.db.get::<BlockVersionByHashSchema>(&block_hash)
+        match self
+            .db
+            .get::<BlockVersionByHashWithChecksumSchema>(&block_hash)?
+        {
+            Some(version) => Ok(Some(version)),
+            None => self.db.get::<BlockVersionByHashSchema>(&block_hash),
+        }
     }
 
-    pub fn put_block_version_by_hash(&self, block_hash: HashValue, version: Version) -> Result<()> {
-        self.db
-            .put::<BlockVersionByHashSchema>(&block_hash, &version)
+    /// Returns the hashes of the blocks whose `BlockMetadata` version is in `[start, end)`, in
+    /// ascending order of the version.
+    pub fn get_block_hashes_in_range(
+        &self,
+        start: Version,
+        end: Version,
+    ) -> Result<Vec<(Version, HashValue)>> {
+        let mut iter = self
+            .db
+            .iter::<BlockHashByVersionSchema>(ReadOptions::default())?;
+        iter.seek(&start)?;
+
+        let mut hashes = vec![];
+        while let Some((version, block_hash)) = iter.next().transpose()? {
+            if version >= end {
+                break;
+            }
+            hashes.push((version, block_hash));
+        }
+        Ok(hashes)
+    }
+
+    /// Builds `BlockHashByVersionSchema` from the hash-to-version indices if it is empty, e.g.,
+    /// for a DB written before the index existed, so that `get_block_hashes_in_range` covers the
+    /// earlier blocks too. Returns the number of blocks backfilled.
+    pub fn backfill_block_hash_by_version(&self) -> Result<usize> {
+        let mut iter = self
+            .db
+            .iter::<BlockHashByVersionSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        if iter.next().transpose()?.is_some() {
+            return Ok(0);
+        }
+
+        let batch = SchemaBatch::new();
+        let mut num_blocks = 0;
+        let mut iter = self
+            .db
+            .iter::<BlockVersionByHashSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let (block_hash, version) = item?;
+            batch.put::<BlockHashByVersionSchema>(&version, &block_hash)?;
+            num_blocks += 1;
+        }
+        let mut iter = self
+            .db
+            .iter::<BlockVersionByHashWithChecksumSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let (block_hash, version) = item?;
+            batch.put::<BlockHashByVersionSchema>(&version, &block_hash)?;
+            num_blocks += 1;
+        }
+        self.db.write_schemas(batch)?;
+        Ok(num_blocks)
+    }
+
+    /// Index the block at `version` by its hash and the other way around, in the same batch.
+    pub fn put_block_version_by_hash(
+        &self,
+        block_hash: HashValue,
+        version: Version,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        if self.block_index_checksum.load(Ordering::Relaxed) {
+            batch.put::<BlockVersionByHashWithChecksumSchema>(&block_hash, &version)?;
+        } else {
+            batch.put::<BlockVersionByHashSchema>(&block_hash, &version)?;
+        }
+        batch.put::<BlockHashByVersionSchema>(&version, &block_hash)
     }
 }
 
@@ -51,6 +51,99 @@ fn test_error_on_get_from_empty() {
     assert!(store.get_event_by_version_and_index(100, 0).is_err());
 }
 
+#[test]
+fn test_get_block_hashes_in_range() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.event_store;
+
+    let batch = SchemaBatch::new();
+    let blocks: Vec<_> = [3, 10, 11, 20]
+        .iter()
+        .map(|version| (*version, HashValue::random()))
+        .collect();
+    for (version, block_hash) in &blocks {
+        store
+            .put_block_version_by_hash(*block_hash, *version, &batch)
+            .unwrap();
+    }
+    store.db.write_schemas(batch).unwrap();
+
+    assert_eq!(store.get_block_hashes_in_range(0, 100).unwrap(), blocks);
+    assert_eq!(
+        store.get_block_hashes_in_range(10, 20).unwrap(),
+        blocks[1..3].to_vec()
+    );
+    assert!(store.get_block_hashes_in_range(12, 20).unwrap().is_empty());
+    assert_eq!(
+        store.get_block_version_by_hash(blocks[0].1).unwrap(),
+        Some(3)
+    );
+}
+
+#[test]
+fn test_backfill_block_hash_by_version() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.event_store;
+
+    // A DB written before `BlockHashByVersionSchema` only has the hash-to-version indices.
+    let blocks = vec![(3, HashValue::random()), (10, HashValue::random())];
+    let batch = SchemaBatch::new();
+    batch
+        .put::<BlockVersionByHashSchema>(&blocks[0].1, &blocks[0].0)
+        .unwrap();
+    batch
+        .put::<BlockVersionByHashWithChecksumSchema>(&blocks[1].1, &blocks[1].0)
+        .unwrap();
+    store.db.write_schemas(batch).unwrap();
+    assert!(store.get_block_hashes_in_range(0, 100).unwrap().is_empty());
+
+    assert_eq!(store.backfill_block_hash_by_version().unwrap(), 2);
+    assert_eq!(store.get_block_hashes_in_range(0, 100).unwrap(), blocks);
+
+    // The index is only backfilled once.
+    let batch = SchemaBatch::new();
+    store
+        .put_block_version_by_hash(HashValue::random(), 20, &batch)
+        .unwrap();
+    store.db.write_schemas(batch).unwrap();
+    assert_eq!(store.backfill_block_hash_by_version().unwrap(), 0);
+    assert_eq!(store.get_block_hashes_in_range(0, 100).unwrap().len(), 3);
+}
+
+#[test]
+fn test_block_index_checksum() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.event_store;
+
+    let (legacy_hash, checked_hash) = (HashValue::random(), HashValue::random());
+    let batch = SchemaBatch::new();
+    store
+        .put_block_version_by_hash(legacy_hash, 1, &batch)
+        .unwrap();
+    store.set_block_index_checksum(true);
+    store
+        .put_block_version_by_hash(checked_hash, 2, &batch)
+        .unwrap();
+    store.db.write_schemas(batch).unwrap();
+
+    assert_eq!(
+        store.get_block_version_by_hash(legacy_hash).unwrap(),
+        Some(1)
+    );
+    assert_eq!(
+        store.get_block_version_by_hash(checked_hash).unwrap(),
+        Some(2)
+    );
+    assert!(store
+        .db
+        .get::<BlockVersionByHashSchema>(&checked_hash)
+        .unwrap()
+        .is_none());
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(10))]
 
@@ -107,6 +107,7 @@ use aptos_types::{
     write_set::WriteSet,
 };
 use aptos_block_executor::data_cache::AsMoveResolver;
+use cfx_primitives::CrossSpaceReceipt;
 use itertools::zip_eq;
 use move_resource_viewer::MoveValueAnnotator;
 use once_cell::sync::Lazy;
@@ -365,6 +366,16 @@ impl AptosDB {
             readonly,
         );
 
+        if !readonly {
+            let num_blocks = myself.event_store.backfill_block_hash_by_version()?;
+            if num_blocks > 0 {
+                info!(
+                    num_blocks = num_blocks,
+                    "Backfilled the block hash by version index."
+                );
+            }
+        }
+
         if !readonly && enable_indexer {
             myself.open_indexer(db_root_path, rocksdb_configs.index_db_config)?;
         }
@@ -542,6 +553,12 @@ impl AptosDB {
         self.state_store.buffered_state()
     }
 
+    /// Store a checksum with the block hash index of the blocks committed from now on, so that
+    /// disk corruption is reported instead of returning a wrong block version.
+    pub fn enable_block_index_checksum(&self) {
+        self.event_store.set_block_index_checksum(true)
+    }
+
     /// This force the db to update rocksdb properties immediately.
     pub fn update_rocksdb_properties(&self) -> Result<()> {
         update_rocksdb_properties(&self.ledger_db, &self.state_merkle_db)
@@ -779,8 +796,11 @@ impl AptosDB {
                         if let Transaction::BlockMetadata(block_metadata) =
                             txn_to_commit.transaction()
                         {
-                            self.event_store
-                                .put_block_version_by_hash(block_metadata.id(), ver)?;
+                            self.event_store.put_block_version_by_hash(
+                                block_metadata.id(),
+                                ver,
+                                cs,
+                            )?;
                         }
                         self.event_store.put_events(ver, txn_to_commit.events(), cs)
                     })
@@ -800,7 +820,12 @@ impl AptosDB {
                             cs,
                         )?;
                         self.transaction_store
-                            .put_write_set(ver, txn_to_commit.write_set(), cs)
+                            .put_write_set(ver, txn_to_commit.write_set(), cs)?;
+                        self.transaction_store.put_cross_space_receipts(
+                            ver,
+                            txn_to_commit.cross_space_receipts(),
+                            cs,
+                        )
                     },
                 )?;
                 // Transaction accumulator updates. Get result root hash.
@@ -1438,6 +1463,22 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_block_hashes_in_range(
+        &self,
+        start: Version,
+        end: Version,
+    ) -> Result<Vec<(Version, HashValue)>> {
+        gauged_api("get_block_hashes_in_range", || {
+            self.event_store.get_block_hashes_in_range(start, end)
+        })
+    }
+
+    fn get_cross_space_receipts(&self, version: Version) -> Result<Vec<CrossSpaceReceipt>> {
+        gauged_api("get_cross_space_receipts", || {
+            self.transaction_store.get_cross_space_receipts(version)
+        })
+    }
+
     fn get_block_info_by_height(&self, height: u64) -> Result<(Version, Version, NewBlockEvent)> {
         gauged_api("get_block_info_by_height", || {
             let latest_li = self.get_latest_ledger_info()?;
@@ -0,0 +1,56 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema mapping the version of a `BlockMetadata` event to
+//! the block hash. It is the reverse index of `BlockVersionByHashSchema`.
+//!
+//! ```text
+//! |<---key--->|<-value->|
+//! | block_ver |  hash   |
+//! ```
+//!
+//! `block_ver` is serialized in big endian so that records in RocksDB will be in order of their
+//! numeric value.
+
+use crate::schema::{ensure_slice_len_eq, BLOCK_HASH_BY_VERSION_CF_NAME};
+use anyhow::Result;
+use aptos_crypto::HashValue;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt};
+use std::mem::size_of;
+
+define_schema!(
+    BlockHashByVersionSchema,
+    Version,
+    HashValue,
+    BLOCK_HASH_BY_VERSION_CF_NAME
+);
+
+impl KeyCodec<BlockHashByVersionSchema> for Version {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_key(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Self>())?;
+        Ok(data.read_u64::<BigEndian>()?)
+    }
+}
+
+impl ValueCodec<BlockHashByVersionSchema> for HashValue {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Self>())?;
+        Ok(HashValue::from_slice(data)?)
+    }
+}
+
+#[cfg(test)]
+mod test;
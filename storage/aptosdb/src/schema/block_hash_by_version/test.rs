@@ -0,0 +1,18 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        version in any::<Version>(),
+        hash in any::<HashValue>(),
+    ) {
+        assert_encode_decode::<BlockHashByVersionSchema>(&version, &hash);
+    }
+}
+
+test_no_panic_decoding!(BlockHashByVersionSchema);
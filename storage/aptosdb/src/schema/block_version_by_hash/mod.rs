@@ -9,9 +9,20 @@
 //! |<--key-->|<-value->|
 //! |   hash  | block_ver |
 //! ```
+//!
+//! `BlockVersionByHashWithChecksumSchema` is the opt-in variant which stores a CRC-32 of the
+//! version next to it, so that a corrupted record fails to decode instead of returning a wrong
+//! block anchor.
+//!
+//! ```text
+//! |<--key-->|<--------value-------->|
+//! |   hash  | block_ver | crc32(ver) |
+//! ```
 
-use crate::schema::{ensure_slice_len_eq, BLOCK_VERSION_BY_HASH_CF_NAME};
-use anyhow::Result;
+use crate::schema::{
+    ensure_slice_len_eq, BLOCK_VERSION_BY_HASH_CF_NAME, BLOCK_VERSION_BY_HASH_WITH_CHECKSUM_CF_NAME,
+};
+use anyhow::{ensure, Result};
 use aptos_crypto::HashValue;
 use aptos_schemadb::{
     define_schema,
@@ -50,3 +61,60 @@ impl ValueCodec<BlockVersionByHashSchema> for Version {
         Ok(data.read_u64::<BigEndian>()?)
     }
 }
+
+define_schema!(
+    BlockVersionByHashWithChecksumSchema,
+    HashValue,
+    Version,
+    BLOCK_VERSION_BY_HASH_WITH_CHECKSUM_CF_NAME
+);
+
+impl KeyCodec<BlockVersionByHashWithChecksumSchema> for HashValue {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Self>())?;
+        Ok(HashValue::from_slice(data)?)
+    }
+}
+
+impl ValueCodec<BlockVersionByHashWithChecksumSchema> for Version {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        let mut encoded = self.to_be_bytes().to_vec();
+        encoded.extend_from_slice(&crc32(&encoded).to_be_bytes());
+        Ok(encoded)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Self>() + size_of::<u32>())?;
+
+        let (mut version, mut checksum) = data.split_at(size_of::<Self>());
+        let expected = checksum.read_u32::<BigEndian>()?;
+        let actual = crc32(version);
+        ensure!(
+            actual == expected,
+            "Block version checksum mismatch, stored {:#x}, computed {:#x}.",
+            expected,
+            actual,
+        );
+        Ok(version.read_u64::<BigEndian>()?)
+    }
+}
+
+/// CRC-32 (IEEE 802.3), the same checksum used by zip and gzip.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,40 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        hash in any::<HashValue>(),
+        version in any::<Version>(),
+    ) {
+        assert_encode_decode::<BlockVersionByHashSchema>(&hash, &version);
+        assert_encode_decode::<BlockVersionByHashWithChecksumSchema>(&hash, &version);
+    }
+
+    #[test]
+    fn test_detect_corruption(
+        version in any::<Version>(),
+        index in 0..12usize,
+        flip in 1..=u8::MAX,
+    ) {
+        let mut encoded =
+            <Version as ValueCodec<BlockVersionByHashWithChecksumSchema>>::encode_value(&version)
+                .unwrap();
+        encoded[index] ^= flip;
+        prop_assert!(
+            <Version as ValueCodec<BlockVersionByHashWithChecksumSchema>>::decode_value(&encoded)
+                .is_err()
+        );
+    }
+}
+
+#[test]
+fn test_crc32() {
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+}
+
+test_no_panic_decoding!(BlockVersionByHashWithChecksumSchema);
@@ -0,0 +1,106 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the receipts of the cross-space calls made
+//! from the Move space, so that indexers can query the past cross-space interactions.
+//! A call is identified by the version of its transaction and its index among the cross-space
+//! calls of the transaction. Both are encoded in big endian, so a scan returns the receipts in
+//! version order.
+//!
+//! ```text
+//! |<------key------>|<----------------------value----------------------->|
+//! | txn_ver | index | receiver | selector | value | gas_used | success |
+//! ```
+
+use crate::schema::{ensure_slice_len_eq, CROSS_SPACE_RECEIPT_CF_NAME};
+use anyhow::{bail, Result};
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use cfx_primitives::CrossSpaceReceipt;
+use cfx_types::{Address, U256};
+use std::{convert::TryInto, mem::size_of};
+
+define_schema!(
+    CrossSpaceReceiptSchema,
+    Key,
+    CrossSpaceReceipt,
+    CROSS_SPACE_RECEIPT_CF_NAME
+);
+
+type CallIndex = u16;
+type Key = (Version, CallIndex);
+
+const KEY_LEN: usize = size_of::<Version>() + size_of::<CallIndex>();
+const ADDRESS_LEN: usize = size_of::<Address>();
+const SELECTOR_LEN: usize = 4;
+const U256_LEN: usize = size_of::<U256>();
+const VALUE_LEN: usize = ADDRESS_LEN + SELECTOR_LEN + 2 * U256_LEN + size_of::<u8>();
+
+impl KeyCodec<CrossSpaceReceiptSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (version, index) = *self;
+
+        let mut encoded = Vec::with_capacity(KEY_LEN);
+        encoded.write_u64::<BigEndian>(version)?;
+        encoded.write_u16::<BigEndian>(index)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, KEY_LEN)?;
+
+        let version = data.read_u64::<BigEndian>()?;
+        let index = data.read_u16::<BigEndian>()?;
+
+        Ok((version, index))
+    }
+}
+
+impl ValueCodec<CrossSpaceReceiptSchema> for CrossSpaceReceipt {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        let mut encoded = Vec::with_capacity(VALUE_LEN);
+        encoded.extend_from_slice(self.receiver.as_bytes());
+        encoded.extend_from_slice(&self.selector);
+        encoded.extend_from_slice(&u256_to_be_bytes(&self.value));
+        encoded.extend_from_slice(&u256_to_be_bytes(&self.gas_used));
+        encoded.push(self.success as u8);
+
+        Ok(encoded)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, VALUE_LEN)?;
+
+        let (receiver, data) = data.split_at(ADDRESS_LEN);
+        let (selector, data) = data.split_at(SELECTOR_LEN);
+        let (value, data) = data.split_at(U256_LEN);
+        let (gas_used, success) = data.split_at(U256_LEN);
+        let success = match success[0] {
+            0 => false,
+            1 => true,
+            flag => bail!("Unexpected success flag {}.", flag),
+        };
+
+        Ok(CrossSpaceReceipt {
+            receiver: Address::from_slice(receiver),
+            selector: selector.try_into()?,
+            value: U256::from_big_endian(value),
+            gas_used: U256::from_big_endian(gas_used),
+            success,
+        })
+    }
+}
+
+fn u256_to_be_bytes(value: &U256) -> [u8; U256_LEN] {
+    let mut bytes = [0u8; U256_LEN];
+    value.to_big_endian(&mut bytes);
+    bytes
+}
+
+#[cfg(test)]
+mod test;
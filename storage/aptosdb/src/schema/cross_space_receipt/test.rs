@@ -0,0 +1,49 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+fn encode_key(key: &Key) -> Vec<u8> {
+    <Key as KeyCodec<CrossSpaceReceiptSchema>>::encode_key(key).unwrap()
+}
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        version in any::<Version>(),
+        index in any::<CallIndex>(),
+        receiver in any::<[u8; 20]>(),
+        selector in any::<[u8; 4]>(),
+        value in any::<[u64; 4]>(),
+        gas_used in any::<u64>(),
+        success in any::<bool>(),
+    ) {
+        let receipt = CrossSpaceReceipt {
+            receiver: Address::from(receiver),
+            selector,
+            value: U256(value),
+            gas_used: gas_used.into(),
+            success,
+        };
+        assert_encode_decode::<CrossSpaceReceiptSchema>(&(version, index), &receipt);
+    }
+
+    #[test]
+    fn test_key_order(key1 in any::<Key>(), key2 in any::<Key>()) {
+        prop_assert_eq!(key1.cmp(&key2), encode_key(&key1).cmp(&encode_key(&key2)));
+    }
+
+    #[test]
+    fn test_invalid_success_flag(flag in 2..=u8::MAX) {
+        let mut encoded = vec![0u8; VALUE_LEN];
+        encoded[VALUE_LEN - 1] = flag;
+        prop_assert!(
+            <CrossSpaceReceipt as ValueCodec<CrossSpaceReceiptSchema>>::decode_value(&encoded)
+                .is_err()
+        );
+    }
+}
+
+test_no_panic_decoding!(CrossSpaceReceiptSchema);
@@ -0,0 +1,54 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema mapping an EVM contract address to the Move module
+//! it corresponds to on the bridge, so that the cross-space dispatcher can resolve the Move target
+//! of an EVM address.
+//!
+//! ```text
+//! |<---key--->|<-----value----->|
+//! |  address  | module id bytes |
+//! ```
+
+use crate::schema::{ensure_slice_len_eq, ensure_slice_len_gt, EVM_TO_MOVE_MAPPING_CF_NAME};
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use cfx_types::Address;
+use move_core_types::{account_address::AccountAddress, language_storage::ModuleId};
+use std::mem::size_of;
+
+define_schema!(
+    EvmToMoveMappingSchema,
+    Address,
+    ModuleId,
+    EVM_TO_MOVE_MAPPING_CF_NAME
+);
+
+impl KeyCodec<EvmToMoveMappingSchema> for Address {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.as_bytes().to_vec())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Self>())?;
+        Ok(Address::from_slice(data))
+    }
+}
+
+impl ValueCodec<EvmToMoveMappingSchema> for ModuleId {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        bcs::to_bytes(self).map_err(Into::into)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        // The module address followed by the non-empty module name.
+        ensure_slice_len_gt(data, size_of::<AccountAddress>())?;
+        bcs::from_bytes(data).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,45 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use move_core_types::identifier::Identifier;
+use proptest::prelude::*;
+
+fn module_id(address: [u8; AccountAddress::LENGTH], name: &str) -> ModuleId {
+    ModuleId::new(AccountAddress::new(address), Identifier::new(name).unwrap())
+}
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        evm_address in any::<[u8; 20]>(),
+        module_address in any::<[u8; AccountAddress::LENGTH]>(),
+        module_name in "[a-zA-Z][a-zA-Z0-9_]{0,31}",
+    ) {
+        assert_encode_decode::<EvmToMoveMappingSchema>(
+            &Address::from(evm_address),
+            &module_id(module_address, &module_name),
+        );
+    }
+}
+
+#[test]
+fn test_encode_decode_zero_address() {
+    assert_encode_decode::<EvmToMoveMappingSchema>(
+        &Address::zero(),
+        &module_id([0; AccountAddress::LENGTH], "bridge"),
+    );
+    assert_eq!(
+        <Address as KeyCodec<EvmToMoveMappingSchema>>::encode_key(&Address::zero()).unwrap(),
+        vec![0; 20]
+    );
+}
+
+#[test]
+fn test_decode_key_length() {
+    assert!(<Address as KeyCodec<EvmToMoveMappingSchema>>::decode_key(&[0; 19]).is_err());
+    assert!(<Address as KeyCodec<EvmToMoveMappingSchema>>::decode_key(&[0; 21]).is_err());
+}
+
+test_no_panic_decoding!(EvmToMoveMappingSchema);
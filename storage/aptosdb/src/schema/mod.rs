@@ -6,13 +6,16 @@
 //!
 //! All schemas are `pub(crate)` so not shown in rustdoc, refer to the source code to see details.
 
+pub(crate) mod block_hash_by_version;
 pub(crate) mod block_version_by_hash;
+pub(crate) mod cross_space_receipt;
 pub(crate) mod db_metadata;
 pub(crate) mod epoch_by_version;
 pub(crate) mod event;
 pub(crate) mod event_accumulator;
 pub(crate) mod event_by_key;
 pub(crate) mod event_by_version;
+pub(crate) mod evm_to_move_mapping;
 pub(crate) mod jellyfish_merkle_node;
 pub(crate) mod ledger_info;
 pub(crate) mod stale_node_index;
@@ -31,13 +34,18 @@ pub(crate) mod write_set;
 use anyhow::{ensure, Result};
 use aptos_schemadb::ColumnFamilyName;
 
+pub const BLOCK_HASH_BY_VERSION_CF_NAME: ColumnFamilyName = "block_hash_by_version";
 pub const BLOCK_VERSION_BY_HASH_CF_NAME: ColumnFamilyName = "block_version_by_hash";
+pub const BLOCK_VERSION_BY_HASH_WITH_CHECKSUM_CF_NAME: ColumnFamilyName =
+    "block_version_by_hash_with_checksum";
+pub const CROSS_SPACE_RECEIPT_CF_NAME: ColumnFamilyName = "cross_space_receipt";
 pub const DB_METADATA_CF_NAME: ColumnFamilyName = "db_metadata";
 pub const EPOCH_BY_VERSION_CF_NAME: ColumnFamilyName = "epoch_by_version";
 pub const EVENT_ACCUMULATOR_CF_NAME: ColumnFamilyName = "event_accumulator";
 pub const EVENT_BY_KEY_CF_NAME: ColumnFamilyName = "event_by_key";
 pub const EVENT_BY_VERSION_CF_NAME: ColumnFamilyName = "event_by_version";
 pub const EVENT_CF_NAME: ColumnFamilyName = "event";
+pub const EVM_TO_MOVE_MAPPING_CF_NAME: ColumnFamilyName = "evm_to_move_mapping";
 pub const JELLYFISH_MERKLE_NODE_CF_NAME: ColumnFamilyName = "jellyfish_merkle_node";
 pub const LEDGER_INFO_CF_NAME: ColumnFamilyName = "ledger_info";
 pub const STALE_NODE_INDEX_CF_NAME: ColumnFamilyName = "stale_node_index";
@@ -80,11 +88,22 @@ pub mod fuzzing {
     pub fn fuzz_decode(data: &[u8]) {
         #[allow(unused_must_use)]
         {
+            assert_no_panic_decoding::<super::block_hash_by_version::BlockHashByVersionSchema>(
+                data,
+            );
+            assert_no_panic_decoding::<super::block_version_by_hash::BlockVersionByHashSchema>(
+                data,
+            );
+            assert_no_panic_decoding::<
+                super::block_version_by_hash::BlockVersionByHashWithChecksumSchema,
+            >(data);
+            assert_no_panic_decoding::<super::cross_space_receipt::CrossSpaceReceiptSchema>(data);
             assert_no_panic_decoding::<super::epoch_by_version::EpochByVersionSchema>(data);
             assert_no_panic_decoding::<super::event::EventSchema>(data);
             assert_no_panic_decoding::<super::event_accumulator::EventAccumulatorSchema>(data);
             assert_no_panic_decoding::<super::event_by_key::EventByKeySchema>(data);
             assert_no_panic_decoding::<super::event_by_version::EventByVersionSchema>(data);
+            assert_no_panic_decoding::<super::evm_to_move_mapping::EvmToMoveMappingSchema>(data);
             assert_no_panic_decoding::<super::jellyfish_merkle_node::JellyfishMerkleNodeSchema>(
                 data,
             );
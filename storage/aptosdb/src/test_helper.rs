@@ -17,6 +17,7 @@ use aptos_types::{
     proof::accumulator::InMemoryAccumulator,
     proptest_types::{AccountInfoUniverse, BlockGen},
 };
+use cfx_types::{Address, U256};
 use proptest::{collection::vec, prelude::*, sample::Index};
 use std::fmt::Debug;
 
@@ -183,6 +184,22 @@ prop_compose! {
                 );
                 txn_accumulator = txn_accumulator.append(&[txn_info.hash()]);
                 txn.set_transaction_info(txn_info);
+
+                // Let the user transactions make up to two cross-space calls.
+                if !txn.is_state_checkpoint() {
+                    let num_calls = txn_accumulator.num_leaves() % 3;
+                    txn.set_cross_space_receipts(
+                        (0..num_calls)
+                            .map(|index| CrossSpaceReceipt {
+                                receiver: Address::from_low_u64_be(txn_accumulator.num_leaves()),
+                                selector: [index as u8; 4],
+                                value: U256::from(index),
+                                gas_used: U256::from(21000),
+                                success: index == 0,
+                            })
+                            .collect(),
+                    );
+                }
             }
 
             // updated ledger info with real root hash and sign
@@ -737,6 +754,12 @@ pub fn verify_committed_transactions(
             txn_to_commit.transaction().hash()
         );
 
+        // Fetch and verify the receipts of the cross-space calls.
+        assert_eq!(
+            db.get_cross_space_receipts(cur_ver).unwrap(),
+            txn_to_commit.cross_space_receipts()
+        );
+
         // Fetch and verify account states.
         for (state_key, state_value) in txn_to_commit.state_updates() {
             updates.insert(state_key, state_value);
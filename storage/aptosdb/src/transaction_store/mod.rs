@@ -6,7 +6,8 @@
 use crate::{
     errors::AptosDbError,
     schema::{
-        transaction::TransactionSchema, transaction_by_account::TransactionByAccountSchema,
+        cross_space_receipt::CrossSpaceReceiptSchema, transaction::TransactionSchema,
+        transaction_by_account::TransactionByAccountSchema,
         transaction_by_eth_hash::TransactionByEthHashSchema,
         transaction_by_hash::TransactionByHashSchema, write_set::WriteSetSchema,
     },
@@ -23,7 +24,8 @@ use aptos_types::{
     transaction::{Transaction, Version},
     write_set::WriteSet,
 };
-use std::sync::Arc;
+use cfx_primitives::CrossSpaceReceipt;
+use std::{convert::TryFrom, sync::Arc};
 
 #[cfg(test)]
 mod test;
@@ -217,6 +219,43 @@ impl TransactionStore {
         batch.put::<WriteSetSchema>(&version, write_set)
     }
 
+    /// Get the receipts of the cross-space calls made by the transaction at `version`, in the
+    /// order of the calls. A transaction without cross-space calls has no receipts.
+    pub fn get_cross_space_receipts(&self, version: Version) -> Result<Vec<CrossSpaceReceipt>> {
+        let mut iter = self
+            .db
+            .iter::<CrossSpaceReceiptSchema>(ReadOptions::default())?;
+        iter.seek(&(version, 0))?;
+
+        let mut receipts = vec![];
+        while let Some(((receipt_version, _index), receipt)) = iter.next().transpose()? {
+            if receipt_version != version {
+                break;
+            }
+            receipts.push(receipt);
+        }
+        Ok(receipts)
+    }
+
+    /// Save the receipts of the cross-space calls made by the transaction at `version`, indexed
+    /// by their order.
+    pub fn put_cross_space_receipts(
+        &self,
+        version: Version,
+        receipts: &[CrossSpaceReceipt],
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        receipts
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, receipt)| {
+                let index = u16::try_from(index).map_err(|_| {
+                    format_err!("Too many cross-space receipts at version {}", version)
+                })?;
+                batch.put::<CrossSpaceReceiptSchema>(&(version, index), receipt)
+            })
+    }
+
     /// Prune the transaction by hash store given a list of transaction
     pub fn prune_transaction_by_hash(
         &self,
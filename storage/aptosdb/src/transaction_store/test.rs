@@ -9,6 +9,7 @@ use aptos_types::{
     proptest_types::{AccountInfoUniverse, SignatureCheckedTransactionGen},
     transaction::Transaction,
 };
+use cfx_types::{Address, U256};
 use proptest::{collection::vec, prelude::*};
 use std::collections::BTreeMap;
 
@@ -198,6 +199,42 @@ proptest! {
     }
 }
 
+#[test]
+fn test_put_get_cross_space_receipts() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.transaction_store;
+
+    let receipt = |index: u64| CrossSpaceReceipt {
+        receiver: Address::from_low_u64_be(index),
+        selector: [index as u8; 4],
+        value: U256::from(index),
+        gas_used: U256::from(21000 + index),
+        success: index % 2 == 0,
+    };
+    let receipts = vec![
+        (1, vec![receipt(0), receipt(1), receipt(2)]),
+        (2, vec![receipt(3)]),
+        (4, vec![receipt(4), receipt(5)]),
+    ];
+    let batch = SchemaBatch::new();
+    for (version, receipts) in &receipts {
+        store
+            .put_cross_space_receipts(*version, receipts, &batch)
+            .unwrap();
+    }
+    store.db.write_schemas(batch).unwrap();
+
+    // The receipts of a transaction are read back in the order of the calls, without the
+    // receipts of the next transaction.
+    for (version, receipts) in &receipts {
+        assert_eq!(&store.get_cross_space_receipts(*version).unwrap(), receipts);
+    }
+    for version in [0, 3, 5] {
+        assert!(store.get_cross_space_receipts(version).unwrap().is_empty());
+    }
+}
+
 fn init_store(
     mut universe: AccountInfoUniverse,
     gens: Vec<(Index, SignatureCheckedTransactionGen)>,
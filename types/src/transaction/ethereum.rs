@@ -66,4 +66,12 @@ impl EthTransactionPayload {
             data,
         }
     }
+
+    /// The receiver of a call, or `None` for a contract creation.
+    pub fn receiver(&self) -> Option<EthAddress> {
+        match self.action {
+            Action::Create => None,
+            Action::Call(address) => Some(address),
+        }
+    }
 }
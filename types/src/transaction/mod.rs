@@ -24,7 +24,7 @@ use aptos_crypto::{
 };
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
 use cfx_primitives::{
-    transaction::TxType, Eip155Transaction, SignedTransaction as EthSignedTransaction, Transaction as EthRawTransaction, TransactionWithSignature as EthTransaction
+    transaction::TxType, CrossSpaceReceipt, Eip155Transaction, SignedTransaction as EthSignedTransaction, Transaction as EthRawTransaction, TransactionWithSignature as EthTransaction
 };
 use move_core_types::transaction_argument::convert_txn_args;
 #[cfg(any(test, feature = "fuzzing"))]
@@ -50,7 +50,7 @@ use aptos_crypto::secp256k1::{Secp256k1PublicKey, Secp256k1Signature};
 #[cfg(any(test, feature = "fuzzing"))]
 pub use change_set::NoOpChangeSetChecker;
 pub use change_set::{ChangeSet, CheckChangeSet};
-pub use ethereum::{EthAddress, EthTransactionPayload};
+pub use ethereum::{Action as EthAction, EthAddress, EthTransactionPayload};
 use ethereum_types::Public;
 pub use module::{Module, ModuleBundle};
 use move_core_types::vm_status::AbortLocation;
@@ -1154,6 +1154,8 @@ pub struct TransactionToCommit {
     write_set: WriteSet,
     events: Vec<ContractEvent>,
     is_reconfig: bool,
+    /// The receipts of the cross-space calls made by the transaction, in the order of the calls.
+    cross_space_receipts: Vec<CrossSpaceReceipt>,
 }
 
 impl TransactionToCommit {
@@ -1172,6 +1174,7 @@ impl TransactionToCommit {
             write_set,
             events,
             is_reconfig,
+            cross_space_receipts: vec![],
         }
     }
 
@@ -1215,6 +1218,14 @@ impl TransactionToCommit {
     pub fn is_reconfig(&self) -> bool {
         self.is_reconfig
     }
+
+    pub fn cross_space_receipts(&self) -> &[CrossSpaceReceipt] {
+        &self.cross_space_receipts
+    }
+
+    pub fn set_cross_space_receipts(&mut self, cross_space_receipts: Vec<CrossSpaceReceipt>) {
+        self.cross_space_receipts = cross_space_receipts
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]